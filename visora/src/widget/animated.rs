@@ -0,0 +1,192 @@
+use std::{sync::Arc, time::Duration};
+
+use visora_core::{
+    animation::{curve::Curve, implicit::ImplicitAnimation, tween::Lerp},
+    color::Color,
+    renderer::Renderer,
+    state::State,
+    widget::{Render, RenderAble, Widget},
+    BuildContext, WidgetContext,
+};
+
+use super::{container::{Container, EdgeInsets}, opacity::Opacity};
+
+type Child<R> = Arc<dyn RenderAble<R> + Send + Sync>;
+
+/// the values of a [`Container`] that [`AnimatedContainer`] animates
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ContainerStyle {
+    pub bg: Color,
+    pub insets: EdgeInsets,
+    pub width: Option<f32>,
+    pub height: Option<f32>
+}
+impl Lerp for ContainerStyle {
+    fn lerp(&self, other: &Self, t: f64) -> Self {
+        // a size that is only set on one side can't be interpolated and jumps at the start
+        let size = |a: Option<f32>, b: Option<f32>| match (a, b) {
+            (Some(a), Some(b)) => Some(a.lerp(&b, t)),
+            _ => b
+        };
+        Self {
+            bg: Lerp::lerp(&self.bg, &other.bg, t),
+            insets: self.insets.lerp(&other.insets, t),
+            width: size(self.width, other.width),
+            height: size(self.height, other.height)
+        }
+    }
+}
+
+/// a [`Container`] that animates to its new background, insets and size whenever it is rebuilt with different ones
+pub struct AnimatedContainer<R> {
+    child: Option<Child<R>>,
+    style: ContainerStyle,
+    duration: Duration,
+    curve: Curve
+}
+impl<R> Clone for AnimatedContainer<R> {
+    fn clone(&self) -> Self {
+        Self {
+            child: self.child.clone(),
+            style: self.style,
+            duration: self.duration,
+            curve: self.curve
+        }
+    }
+}
+impl<R: Renderer> AnimatedContainer<R> {
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            child: None,
+            style: ContainerStyle {
+                bg: Color::new_argb(255, 255, 255, 255),
+                insets: EdgeInsets::all(0),
+                width: None,
+                height: None
+            },
+            duration,
+            curve: Curve::Linear
+        }
+    }
+    pub fn with_child(mut self, x: impl RenderAble<R> + Send + Sync + 'static) -> Self {
+        self.child = Some(Arc::new(x));
+        self
+    }
+    pub fn with_bg(mut self, bg: Color) -> Self {
+        self.style.bg = bg;
+        self
+    }
+    pub fn with_insets(mut self, insets: EdgeInsets) -> Self {
+        self.style.insets = insets;
+        self
+    }
+    pub fn with_width(mut self, width: f32) -> Self {
+        self.style.width = Some(width);
+        self
+    }
+    pub fn with_height(mut self, height: f32) -> Self {
+        self.style.height = Some(height);
+        self
+    }
+    pub fn with_curve(mut self, curve: Curve) -> Self {
+        self.curve = curve;
+        self
+    }
+}
+impl<R> RenderAble<R> for AnimatedContainer<R>
+where R: Renderer + Render<Container<R>>
+{
+    fn mount<'gui>(&self, context: WidgetContext<'gui, R>) -> WidgetContext<'gui, R> {
+        context.mount_widget(self)
+    }
+}
+impl<R> Widget<R> for AnimatedContainer<R>
+where R: Renderer + Render<Container<R>>
+{
+    type State = ImplicitAnimation<ContainerStyle>;
+    fn create_state(&self) -> Self::State {
+        ImplicitAnimation::new(self.style)
+    }
+    fn build<'gui>(&self, state: State<Self>, _context: &mut BuildContext<'gui>) -> impl RenderAble<R> + 'static {
+        let style = state.read::<R>().value();
+        let mut container = Container::new()
+            .with_bg(style.bg)
+            .with_insets(style.insets);
+        if let Some(width) = style.width {
+            container = container.with_width(width);
+        }
+        if let Some(height) = style.height {
+            container = container.with_height(height);
+        }
+        match &self.child {
+            Some(child) => container.with_child(child.clone()),
+            None => container
+        }
+    }
+    fn init_state<'gui>(&self, context: &mut BuildContext<'gui>) {
+        let state = context.get_component_mut::<Self::State>().unwrap();
+        state.init(context, self.duration);
+    }
+    fn did_update_widget<'gui>(&self, _old: &Self, context: &mut BuildContext<'gui>) {
+        let state = context.get_component_mut::<Self::State>().unwrap();
+        state.animate_to(self.style, self.duration, self.curve);
+    }
+}
+
+/// an [`Opacity`] that animates to its new opacity whenever it is rebuilt with a different one
+pub struct AnimatedOpacity<R> {
+    child: Child<R>,
+    opacity: f32,
+    duration: Duration,
+    curve: Curve
+}
+impl<R> Clone for AnimatedOpacity<R> {
+    fn clone(&self) -> Self {
+        Self {
+            child: self.child.clone(),
+            opacity: self.opacity,
+            duration: self.duration,
+            curve: self.curve
+        }
+    }
+}
+impl<R: Renderer> AnimatedOpacity<R> {
+    pub fn new(opacity: f32, duration: Duration, child: impl RenderAble<R> + Send + Sync + 'static) -> Self {
+        Self {
+            child: Arc::new(child),
+            opacity: opacity.clamp(0.0, 1.0),
+            duration,
+            curve: Curve::Linear
+        }
+    }
+    pub fn with_curve(mut self, curve: Curve) -> Self {
+        self.curve = curve;
+        self
+    }
+}
+impl<R> RenderAble<R> for AnimatedOpacity<R>
+where R: Renderer + Render<Opacity<R>>
+{
+    fn mount<'gui>(&self, context: WidgetContext<'gui, R>) -> WidgetContext<'gui, R> {
+        context.mount_widget(self)
+    }
+}
+impl<R> Widget<R> for AnimatedOpacity<R>
+where R: Renderer + Render<Opacity<R>>
+{
+    type State = ImplicitAnimation<f32>;
+    fn create_state(&self) -> Self::State {
+        ImplicitAnimation::new(self.opacity)
+    }
+    fn build<'gui>(&self, state: State<Self>, _context: &mut BuildContext<'gui>) -> impl RenderAble<R> + 'static {
+        Opacity::new(state.read::<R>().value(), self.child.clone())
+    }
+    fn init_state<'gui>(&self, context: &mut BuildContext<'gui>) {
+        let state = context.get_component_mut::<Self::State>().unwrap();
+        state.init(context, self.duration);
+    }
+    fn did_update_widget<'gui>(&self, _old: &Self, context: &mut BuildContext<'gui>) {
+        let state = context.get_component_mut::<Self::State>().unwrap();
+        state.animate_to(self.opacity, self.duration, self.curve);
+    }
+}
@@ -0,0 +1,127 @@
+use std::sync::Arc;
+
+use visora_core::{
+    color::Color,
+    error::{Catcher, WidgetError},
+    renderer::Renderer,
+    state::{State, StateHandle},
+    widget::{Render, RenderAble, Widget},
+    BuildContext, WidgetContext,
+};
+
+use super::{container::{Container, EdgeInsets}, text::Text};
+
+type Child<R> = Arc<dyn RenderAble<R> + Send + Sync>;
+type Fallback<R> = dyn Fn(&WidgetError) -> Box<dyn RenderAble<R>> + Send + Sync;
+type Reporter = dyn Fn(&WidgetError) + Send + Sync;
+
+/// catches the panics of its descendants while they build or mount and shows a fallback in their place
+///
+/// the fallback stays until the boundary is removed, rebuilding the parent does not retry the child
+pub struct ErrorBoundary<R> {
+    child: Child<R>,
+    fallback: Arc<Fallback<R>>,
+    on_error: Arc<Reporter>
+}
+impl<R> Clone for ErrorBoundary<R> {
+    fn clone(&self) -> Self {
+        Self {
+            child: self.child.clone(),
+            fallback: self.fallback.clone(),
+            on_error: self.on_error.clone()
+        }
+    }
+}
+impl<R> ErrorBoundary<R>
+where R: Renderer + Render<Container<R>> + Render<Text> + 'static
+{
+    /// shows an [`error_box`] and prints the error when `child` panics
+    pub fn new(child: impl RenderAble<R> + Send + Sync + 'static) -> Self {
+        Self {
+            child: Arc::new(child),
+            fallback: Arc::new(|error| Box::new(error_box::<R>(error))),
+            on_error: Arc::new(|error| tracing::error!(%error, "caught by an error boundary"))
+        }
+    }
+    pub fn with_fallback<F, W>(mut self, fallback: F) -> Self
+    where F: Fn(&WidgetError) -> W + Send + Sync + 'static,
+          W: RenderAble<R> + 'static
+    {
+        self.fallback = Arc::new(move |error| Box::new(fallback(error)));
+        self
+    }
+    /// called with every caught error, instead of printing it
+    pub fn with_on_error<F>(mut self, on_error: F) -> Self
+    where F: Fn(&WidgetError) + Send + Sync + 'static
+    {
+        self.on_error = Arc::new(on_error);
+        self
+    }
+}
+
+/// the fallback of [`ErrorBoundary::new`], a red box with the error in debug builds and an empty box in release builds
+pub fn error_box<R>(error: &WidgetError) -> Container<R>
+where R: Renderer + Render<Text> + 'static
+{
+    if cfg!(debug_assertions) {
+        Container::new()
+            .with_bg(Color::new_hex(0xcc0000ff))
+            .with_insets(EdgeInsets::all(8))
+            .with_child(Text::new(&error.to_string()))
+    } else {
+        Container::new().with_bg(Color::new_argb(0, 0, 0, 0))
+    }
+}
+
+impl<R> RenderAble<R> for ErrorBoundary<R>
+where R: Renderer + Render<Container<R>> + Render<Text> + 'static
+{
+    fn mount<'gui>(&self, mut context: WidgetContext<'gui, R>) -> WidgetContext<'gui, R> {
+        // panics of later rebuilds and transactions below the boundary reach it through the catcher
+        let on_error = self.on_error.clone();
+        context.insert_component(Catcher::new(move |error, context| {
+            on_error(&error);
+            *context.get_component_mut::<Option<WidgetError>>().unwrap() = Some(error);
+        }));
+        context.mount_widget(self)
+    }
+}
+impl<R> Widget<R> for ErrorBoundary<R>
+where R: Renderer + Render<Container<R>> + Render<Text> + 'static
+{
+    type State = Option<WidgetError>;
+    fn create_state(&self) -> Self::State {
+        None
+    }
+    fn build<'gui>(&self, state: State<Self>, _context: &mut BuildContext<'gui>) -> impl RenderAble<R> + 'static {
+        Guarded {
+            boundary: self.clone(),
+            handle: state.handle(),
+            error: state.read::<R>().clone()
+        }
+    }
+}
+
+/// the child of an [`ErrorBoundary`], it catches the panics while the boundary mounts its child
+struct Guarded<R> {
+    boundary: ErrorBoundary<R>,
+    handle: StateHandle<ErrorBoundary<R>>,
+    error: Option<WidgetError>
+}
+impl<R> RenderAble<R> for Guarded<R>
+where R: Renderer + Render<Container<R>> + Render<Text> + 'static
+{
+    fn mount<'gui>(&self, context: WidgetContext<'gui, R>) -> WidgetContext<'gui, R> {
+        if let Some(error) = &self.error {
+            return context.mount_child(&*(self.boundary.fallback)(error));
+        }
+        let (context, error) = context.try_mount_child(&*self.boundary.child);
+        let Some(error) = error else {
+            return context;
+        };
+        (self.boundary.on_error)(&error);
+        let stored = error.clone();
+        context.get_buildcontext().runtime().queue(self.handle.update::<_, R>(move |_, state| *state = Some(stored.clone())));
+        context.mount_child(&*(self.boundary.fallback)(&error))
+    }
+}
@@ -1,6 +1,6 @@
 use std::marker::PhantomData;
 
-use visora_core::{BuildContext, Component, WidgetContext, renderer::Renderer, state::StateTransaction, widget::{Render, RenderAble, Widget}};
+use visora_core::{BuildContext, Component, WidgetContext, event::{Event, Key, KeyState, PointerButton, Propagation}, focus::{FocusNode, Focusable}, renderer::Renderer, state::StateTransaction, widget::{Render, RenderAble, Widget}};
 
 use super::text::Text;
 
@@ -8,6 +8,7 @@ struct CallBack<State>(Box<dyn Fn(&mut State) + 'static + Send + Sync>);
 
 
 
+#[derive(Clone)]
 pub struct TextButton{
     child: Text,
     update: Option<StateTransaction>
@@ -37,9 +38,25 @@ impl<R> RenderAble<R> for TextButton
 where R: Renderer + Render<Self> + Render<Text>
 {
     fn mount<'gui>(&self, mut context: WidgetContext<'gui, R>) -> WidgetContext<'gui, R> {
-        R::mount(self, &mut context);
-        let child = context.new_child();
-        self.child.mount(child).to_parent().unwrap()
+        context.mount_render(self);
+        let update = self.update.clone();
+        context.insert_component(Focusable::new());
+        context.on_event(move |event, context| match event {
+            Event::PointerDown { button: PointerButton::Primary, .. } => {
+                FocusNode::new(context.key()).request_focus(context.runtime());
+                Propagation::Stop
+            }
+            // a focused button is activated by the keyboard like by a click
+            Event::PointerUp { button: PointerButton::Primary, .. }
+            | Event::Key { key: Key::Enter | Key::Space, state: KeyState::Pressed, .. } => {
+                if let Some(update) = &update {
+                    context.runtime().queue(update.clone());
+                }
+                Propagation::Stop
+            }
+            _ => Propagation::Continue
+        });
+        context.mount_child(&self.child)
     }
 }
 
@@ -1,9 +1,10 @@
 use std::marker::PhantomData;
 
-use visora_core::{renderer::Renderer, widget::{Render, RenderAble, Widget}, WidgetContext};
+use visora_core::{layout::Centered, renderer::Renderer, widget::{Render, RenderAble, Widget}, WidgetContext};
 use visora_macros::RenderAble;
 
 
+#[derive(Clone)]
 pub struct Center<W>{
     child: W
 }
@@ -16,13 +17,13 @@ impl<W:'static> Center<W>
     }
 }
 impl<R, W> RenderAble<R> for Center<W>
-where W: Widget<R>,
+where W: RenderAble<R> + Clone + Send + Sync + 'static,
       R: Renderer + Render<Self>
 {
     fn mount<'gui>(&self, mut context: WidgetContext<'gui, R>) -> WidgetContext<'gui, R> {
-        R::mount(self, &mut context);
-        let child = context.new_child();
-        self.child.mount(child).to_parent().unwrap()
+        context.mount_render(self);
+        context.set_layout(Centered);
+        context.mount_child(&self.child)
     }
 }
 
@@ -1,6 +1,8 @@
-use visora_core::{color::Color, renderer::Renderer, widget::{Render, Widget}, WidgetContext};
+use std::sync::Arc;
 
-#[derive(Debug, Clone, Copy)]
+use visora_core::{animation::tween::Lerp, color::Color, layout::Padded, renderer::Renderer, widget::{Render, RenderAble}, WidgetContext};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct EdgeInsets{
     left: u8,
     top: u8,
@@ -34,23 +36,50 @@ impl EdgeInsets {
         self.top == self.bottom && self.top == self.left && self.top == self.right
     }
 }
+impl Lerp for EdgeInsets {
+    fn lerp(&self, other: &Self, t: f64) -> Self {
+        let lerp = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+        Self {
+            left: lerp(self.left, other.left),
+            top: lerp(self.top, other.top),
+            right: lerp(self.right, other.right),
+            bottom: lerp(self.bottom, other.bottom)
+        }
+    }
+}
+
+type Child<R> = Arc<dyn RenderAble<R> + Send + Sync>;
 
-/*
 pub struct Container<R>{
-    child: Option<Box<dyn Widget<R>>>,
+    child: Option<Child<R>>,
     insets: EdgeInsets,
-    bg: Color
+    bg: Color,
+    width: Option<f32>,
+    height: Option<f32>
+}
+impl<R> Clone for Container<R> {
+    fn clone(&self) -> Self {
+        Self {
+            child: self.child.clone(),
+            insets: self.insets,
+            bg: self.bg,
+            width: self.width,
+            height: self.height
+        }
+    }
 }
 impl<R: Renderer> Container<R>{
     pub fn new() -> Self {
         Self {
             child: None,
             insets: EdgeInsets::all(0),
-            bg: Color::new_argb(255, 255, 255, 255)
+            bg: Color::new_argb(255, 255, 255, 255),
+            width: None,
+            height: None
         }
     }
-    pub fn with_child(mut self, x: impl Widget<R> + 'static) -> Self {
-        self.child = Some(Box::new(x));
+    pub fn with_child(mut self, x: impl RenderAble<R> + Send + Sync + 'static) -> Self {
+        self.child = Some(Arc::new(x));
         self
     }
     pub fn with_insets(mut self, insets: EdgeInsets) -> Self {
@@ -67,20 +96,40 @@ impl<R: Renderer> Container<R>{
         self.bg = bg;
         self
     }
+    /// a fixed width instead of the width of the child
+    pub fn with_width(mut self, width: f32) -> Self {
+        self.width = Some(width);
+        self
+    }
+    pub fn width(&self) -> Option<f32> {
+        self.width
+    }
+    /// a fixed height instead of the height of the child
+    pub fn with_height(mut self, height: f32) -> Self {
+        self.height = Some(height);
+        self
+    }
+    pub fn height(&self) -> Option<f32> {
+        self.height
+    }
 }
 
-impl<R> Widget<R> for Container<R>
+impl<R> RenderAble<R> for Container<R>
 where R: Renderer + Render<Self>
 {
     fn mount<'gui>(&self, mut context: WidgetContext<'gui, R>) -> WidgetContext<'gui, R> {
-        R::mount(self, &mut context);
+        context.mount_render(self);
+        context.set_layout(Padded {
+            left: self.insets.left as f32,
+            top: self.insets.top as f32,
+            right: self.insets.right as f32,
+            bottom: self.insets.bottom as f32,
+            width: self.width,
+            height: self.height
+        });
         match &self.child {
-            Some(x) => {
-                let child = context.new_child();
-                x.mount(child).to_parent().unwrap()
-            },
+            Some(x) => context.mount_child(&**x),
             None => context
         }
     }
 }
-*/
\ No newline at end of file
@@ -0,0 +1,160 @@
+use std::sync::Arc;
+
+use visora_core::{
+    layout::{flex::{CrossAxisAlignment, Flex, FlexChild, MainAxisAlignment, MainAxisSize}, Axis, Proxy},
+    renderer::Renderer,
+    widget::{Render, RenderAble},
+    WidgetContext,
+};
+
+type Child<R> = Arc<dyn RenderAble<R> + Send + Sync>;
+
+macro_rules! flex_widget {
+    ($(#[$doc:meta])* $name:ident, $axis:expr) => {
+        $(#[$doc])*
+        pub struct $name<R> {
+            layout: Flex,
+            children: Vec<Child<R>>,
+        }
+        impl<R> Clone for $name<R> {
+            fn clone(&self) -> Self {
+                Self { layout: self.layout, children: self.children.clone() }
+            }
+        }
+        impl<R: Renderer> Default for $name<R> {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+        impl<R: Renderer> $name<R> {
+            pub fn new() -> Self {
+                Self { layout: Flex::new($axis), children: Vec::new() }
+            }
+            // a builder like the add of `Hlist`, not an addition
+            #[allow(clippy::should_implement_trait)]
+            pub fn add(mut self, x: impl RenderAble<R> + Send + Sync + 'static) -> Self {
+                self.children.push(Arc::new(x));
+                self
+            }
+            pub fn with_main_alignment(mut self, alignment: MainAxisAlignment) -> Self {
+                self.layout.main_alignment = alignment;
+                self
+            }
+            pub fn with_cross_alignment(mut self, alignment: CrossAxisAlignment) -> Self {
+                self.layout.cross_alignment = alignment;
+                self
+            }
+            pub fn with_main_size(mut self, size: MainAxisSize) -> Self {
+                self.layout.main_size = size;
+                self
+            }
+            /// the gap between two children
+            pub fn with_spacing(mut self, spacing: f32) -> Self {
+                self.layout.spacing = spacing;
+                self
+            }
+            pub fn layout(&self) -> &Flex {
+                &self.layout
+            }
+        }
+        impl<R> RenderAble<R> for $name<R>
+        where R: Renderer + Render<Self>
+        {
+            fn mount<'gui>(&self, mut context: WidgetContext<'gui, R>) -> WidgetContext<'gui, R> {
+                context.mount_render(self);
+                context.set_layout(self.layout);
+                for x in &self.children {
+                    context = context.mount_child(&**x);
+                }
+                context
+            }
+        }
+    };
+}
+flex_widget!(
+    /// places its children from left to right
+    Row, Axis::Horizontal
+);
+flex_widget!(
+    /// places its children from top to bottom
+    Column, Axis::Vertical
+);
+
+macro_rules! flexible_widget {
+    ($(#[$doc:meta])* $name:ident, $loosen:expr) => {
+        $(#[$doc])*
+        pub struct $name<R> {
+            flex: u32,
+            child: Child<R>,
+        }
+        impl<R> Clone for $name<R> {
+            fn clone(&self) -> Self {
+                Self { flex: self.flex, child: self.child.clone() }
+            }
+        }
+        impl<R: Renderer> $name<R> {
+            pub fn new(child: impl RenderAble<R> + Send + Sync + 'static) -> Self {
+                Self { flex: 1, child: Arc::new(child) }
+            }
+            /// the parts of the free space it gets, out of the flex of all children of the row or column
+            pub fn with_flex(mut self, flex: u32) -> Self {
+                self.flex = flex;
+                self
+            }
+            pub fn flex(&self) -> FlexChild {
+                FlexChild { flex: self.flex }
+            }
+        }
+        impl<R> RenderAble<R> for $name<R>
+        where R: Renderer + Render<Self>
+        {
+            fn mount<'gui>(&self, mut context: WidgetContext<'gui, R>) -> WidgetContext<'gui, R> {
+                context.mount_render(self);
+                context.insert_component(self.flex());
+                context.set_layout(Proxy { loosen: $loosen });
+                context.mount_child(&*self.child)
+            }
+        }
+    };
+}
+flexible_widget!(
+    /// its child fills its share of the free space of a row or column
+    Expanded, false
+);
+flexible_widget!(
+    /// takes its share of the free space of a row or column, its child can be smaller
+    Flexible, true
+);
+
+/// empty space that fills its share of the free space of a row or column
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Spacer {
+    flex: u32,
+}
+impl Default for Spacer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl Spacer {
+    pub fn new() -> Self {
+        Self { flex: 1 }
+    }
+    pub fn with_flex(mut self, flex: u32) -> Self {
+        self.flex = flex;
+        self
+    }
+    pub fn flex(&self) -> FlexChild {
+        FlexChild { flex: self.flex }
+    }
+}
+impl<R> RenderAble<R> for Spacer
+where R: Renderer + Render<Self>
+{
+    fn mount<'gui>(&self, mut context: WidgetContext<'gui, R>) -> WidgetContext<'gui, R> {
+        context.mount_render(self);
+        context.insert_component(self.flex());
+        context.set_layout(Proxy::default());
+        context
+    }
+}
@@ -0,0 +1,147 @@
+use std::sync::Arc;
+
+use visora_core::{
+    layout::{grid::{auto_place, CellAlignment, Grid as GridLayout, GridArea, GridChild, Placement, Track}, Proxy},
+    renderer::Renderer,
+    widget::{Render, RenderAble},
+    WidgetContext,
+};
+
+type Child<R> = Arc<dyn RenderAble<R> + Send + Sync>;
+
+/// puts its children in the cells of rows and columns, like the grid of css
+///
+/// the children flow into the free cells row by row, unless a [`GridItem`] puts them at a cell. the rows are auto
+/// rows unless they are given
+pub struct Grid<R> {
+    layout: GridLayout,
+    items: Vec<GridItem<R>>,
+}
+impl<R> Clone for Grid<R> {
+    fn clone(&self) -> Self {
+        Self { layout: self.layout.clone(), items: self.items.clone() }
+    }
+}
+impl<R: Renderer> Grid<R> {
+    pub fn new(columns: Vec<Track>) -> Self {
+        Self { layout: GridLayout::new(columns), items: Vec::new() }
+    }
+    pub fn with_rows(mut self, rows: Vec<Track>) -> Self {
+        self.layout.rows = rows;
+        self
+    }
+    /// the gaps between two columns and between two rows
+    pub fn with_gap(mut self, column_gap: f32, row_gap: f32) -> Self {
+        self.layout.column_gap = column_gap;
+        self.layout.row_gap = row_gap;
+        self
+    }
+    /// how the children are aligned in their cells, a [`GridItem`] can align itself otherwise
+    pub fn with_alignment(mut self, horizontal: CellAlignment, vertical: CellAlignment) -> Self {
+        self.layout.horizontal_alignment = horizontal;
+        self.layout.vertical_alignment = vertical;
+        self
+    }
+    /// adds a child in the next free cell
+    // a builder like the add of `Hlist`, not an addition
+    #[allow(clippy::should_implement_trait)]
+    pub fn add(self, x: impl RenderAble<R> + Send + Sync + 'static) -> Self {
+        self.add_item(GridItem::new(x))
+    }
+    pub fn add_item(mut self, item: GridItem<R>) -> Self {
+        self.items.push(item);
+        self
+    }
+    pub fn layout(&self) -> &GridLayout {
+        &self.layout
+    }
+}
+impl<R> RenderAble<R> for Grid<R>
+where R: Renderer + Render<Self> + Render<GridItem<R>>
+{
+    fn mount<'gui>(&self, mut context: WidgetContext<'gui, R>) -> WidgetContext<'gui, R> {
+        context.mount_render(self);
+        context.set_layout(self.layout.clone());
+        let placements: Vec<Placement> = self.items.iter().map(|x| x.placement).collect();
+        let areas = auto_place(self.layout.columns.len(), &placements);
+        for (item, area) in self.items.iter().zip(areas) {
+            context = context.mount_child(&GridItem { placement: Placement::At(area), ..item.clone() });
+        }
+        context
+    }
+}
+
+/// a child of a [`Grid`] with the cells it covers and how it is aligned in them
+pub struct GridItem<R> {
+    placement: Placement,
+    horizontal: Option<CellAlignment>,
+    vertical: Option<CellAlignment>,
+    child: Child<R>,
+}
+impl<R> Clone for GridItem<R> {
+    fn clone(&self) -> Self {
+        Self {
+            placement: self.placement,
+            horizontal: self.horizontal,
+            vertical: self.vertical,
+            child: self.child.clone(),
+        }
+    }
+}
+impl<R: Renderer> GridItem<R> {
+    /// a child in the next free cell
+    pub fn new(child: impl RenderAble<R> + Send + Sync + 'static) -> Self {
+        Self {
+            placement: Placement::Auto { row_span: 1, column_span: 1 },
+            horizontal: None,
+            vertical: None,
+            child: Arc::new(child),
+        }
+    }
+    /// puts the child at the cell of `row` and `column`, counted from 0, instead of the next free one
+    pub fn at(mut self, row: usize, column: usize) -> Self {
+        let (row_span, column_span) = self.spans();
+        self.placement = Placement::At(GridArea { row, column, row_span, column_span });
+        self
+    }
+    /// how many rows and columns the child covers
+    pub fn with_span(mut self, row_span: usize, column_span: usize) -> Self {
+        self.placement = match self.placement {
+            Placement::At(area) => Placement::At(GridArea { row_span, column_span, ..area }),
+            Placement::Auto { .. } => Placement::Auto { row_span, column_span },
+        };
+        self
+    }
+    pub fn with_alignment(mut self, horizontal: CellAlignment, vertical: CellAlignment) -> Self {
+        self.horizontal = Some(horizontal);
+        self.vertical = Some(vertical);
+        self
+    }
+    fn spans(&self) -> (usize, usize) {
+        match self.placement {
+            Placement::At(area) => (area.row_span, area.column_span),
+            Placement::Auto { row_span, column_span } => (row_span, column_span),
+        }
+    }
+}
+impl<R> GridItem<R> {
+    /// the cells of the child, the grid finds the ones of a child that flows into the free cells
+    pub fn grid_child(&self) -> GridChild {
+        let area = match self.placement {
+            Placement::At(area) => area,
+            Placement::Auto { row_span, column_span } => GridArea { row: 0, column: 0, row_span, column_span },
+        };
+        GridChild { area, horizontal: self.horizontal, vertical: self.vertical }
+    }
+}
+impl<R> RenderAble<R> for GridItem<R>
+where R: Renderer + Render<Self>
+{
+    fn mount<'gui>(&self, mut context: WidgetContext<'gui, R>) -> WidgetContext<'gui, R> {
+        context.mount_render(self);
+        context.insert_component(self.grid_child());
+        // the grid stretches the item, its child keeps its own size in it
+        context.set_layout(Proxy { loosen: true });
+        context.mount_child(&*self.child)
+    }
+}
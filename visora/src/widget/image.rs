@@ -1,10 +1,10 @@
 use std::path::{Path, PathBuf};
 
-use visora_core::{renderer::Renderer, widget::{Render, Widget}, WidgetContext};
+use visora_core::{layout::Growable, renderer::Renderer, widget::{Render, RenderAble}, WidgetContext};
 
 
-
-/*
+/// an image that grows to the space it gets, up to its max width and height
+#[derive(Debug, Clone, PartialEq)]
 pub struct Image{
     // an image widget is only a path, this keeps it cheap
     pub path: PathBuf,
@@ -29,11 +29,15 @@ impl Image {
         self
     }
 }
-impl<R> Widget<R> for Image
+impl<R> RenderAble<R> for Image
 where R: Renderer + Render<Self>
 {
     fn mount<'gui>(&self, mut context: WidgetContext<'gui, R>) -> WidgetContext<'gui, R> {
-        R::mount(self, &mut context);
+        context.mount_render(self);
+        context.set_layout(Growable {
+            max_width: self.max_width.map(|x| x as f32),
+            max_height: self.max_height.map(|x| x as f32)
+        });
         context
     }
-}*/
\ No newline at end of file
+}
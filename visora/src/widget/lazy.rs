@@ -0,0 +1,174 @@
+use std::sync::Arc;
+
+use visora_core::{
+    layout::{lazy::{Lazy, LazyItem}, Axis},
+    renderer::Renderer,
+    widget::{Render, RenderAble},
+    WidgetContext,
+};
+
+use super::scroll::{ScrollController, ScrollPosition, ScrollView, ScrollViewport, Scrollbar};
+
+type Child<R> = Arc<dyn RenderAble<R> + Send + Sync>;
+type Builder<R> = Arc<dyn Fn(usize) -> Child<R> + Send + Sync>;
+
+/// a scroll view of `item_count` items that only mounts the items in view, like the list view builder of flutter
+///
+/// every item is `item_extent` long along the axis, so the items in view are known without building the others.
+/// `builder` builds an item when it comes within the cache extent of the viewport. the slots of the items that
+/// leave are reused for the ones that come in, the item of a slot starts with a new state
+pub struct LazyList<R> {
+    layout: Lazy,
+    builder: Builder<R>,
+    cache_extent: f32,
+    scrollbars: bool,
+    controller: Option<ScrollController>,
+}
+impl<R> Clone for LazyList<R> {
+    fn clone(&self) -> Self {
+        Self {
+            layout: self.layout,
+            builder: self.builder.clone(),
+            cache_extent: self.cache_extent,
+            scrollbars: self.scrollbars,
+            controller: self.controller.clone(),
+        }
+    }
+}
+impl<R: Renderer> LazyList<R> {
+    /// a vertical list of `item_count` items of `item_extent`, built by `builder` from their index
+    pub fn new<W, F>(item_count: usize, item_extent: f32, builder: F) -> Self
+    where W: RenderAble<R> + Send + Sync + 'static,
+          F: Fn(usize) -> W + Send + Sync + 'static
+    {
+        Self {
+            layout: Lazy { axis: Axis::Vertical, extent: item_extent, count: item_count },
+            builder: Arc::new(move |index| Arc::new(builder(index)) as Child<R>),
+            cache_extent: 250.0,
+            scrollbars: true,
+            controller: None,
+        }
+    }
+    pub fn with_axis(mut self, axis: Axis) -> Self {
+        self.layout.axis = axis;
+        self
+    }
+    /// how far before and after the viewport items are kept mounted, 250 by default
+    pub fn with_cache_extent(mut self, cache_extent: f32) -> Self {
+        self.cache_extent = cache_extent;
+        self
+    }
+    pub fn with_scrollbars(mut self, scrollbars: bool) -> Self {
+        self.scrollbars = scrollbars;
+        self
+    }
+    pub fn with_controller(mut self, controller: ScrollController) -> Self {
+        self.controller = Some(controller);
+        self
+    }
+}
+
+impl<R> RenderAble<R> for LazyList<R>
+where R: Renderer + Render<ScrollViewport<R>> + Render<Scrollbar> + Render<LazyItems<R>> + Render<LazySlot<R>>
+{
+    fn mount<'gui>(&self, context: WidgetContext<'gui, R>) -> WidgetContext<'gui, R> {
+        let items = LazyItems {
+            layout: self.layout,
+            builder: self.builder.clone(),
+            cache_extent: self.cache_extent,
+        };
+        let mut view = ScrollView::new(items)
+            .with_axis(self.layout.axis)
+            .with_scrollbars(self.scrollbars)
+            .with_sized_child();
+        if let Some(controller) = &self.controller {
+            view = view.with_controller(controller.clone());
+        }
+        view.mount(context)
+    }
+}
+
+/// the content of a [`LazyList`], as long as all the items with a slot for every item it can show at once
+pub struct LazyItems<R> {
+    layout: Lazy,
+    builder: Builder<R>,
+    cache_extent: f32,
+}
+impl<R> Clone for LazyItems<R> {
+    fn clone(&self) -> Self {
+        Self {
+            layout: self.layout,
+            builder: self.builder.clone(),
+            cache_extent: self.cache_extent,
+        }
+    }
+}
+impl<R> LazyItems<R> {
+    pub fn layout(&self) -> &Lazy {
+        &self.layout
+    }
+}
+impl<R> RenderAble<R> for LazyItems<R>
+where R: Renderer + Render<Self> + Render<LazySlot<R>>
+{
+    fn mount<'gui>(&self, mut context: WidgetContext<'gui, R>) -> WidgetContext<'gui, R> {
+        context.mount_render(self);
+        context.set_layout(self.layout);
+        let position = context.get_buildcontext().read::<ScrollPosition>().copied().unwrap_or_default();
+        let (offset, viewport) = match self.layout.axis {
+            Axis::Horizontal => (position.offset.x, position.viewport.width),
+            Axis::Vertical => (position.offset.y, position.viewport.height),
+        };
+        let items = self.layout.items_in_view(offset, viewport, self.cache_extent);
+        // an item stays in its slot while it is in view, the slot of an item that left is reused by the next one
+        let capacity = self.layout.capacity(viewport, self.cache_extent);
+        for slot in 0..capacity {
+            let index = items.start + (slot + capacity - items.start % capacity) % capacity;
+            context = context.mount_child(&LazySlot {
+                layout: self.layout,
+                index: items.contains(&index).then_some(index),
+                builder: self.builder.clone(),
+            });
+        }
+        context
+    }
+}
+
+/// a recycled entity of a [`LazyItems`], it shows the item at `index` or is hidden
+pub struct LazySlot<R> {
+    layout: Lazy,
+    index: Option<usize>,
+    builder: Builder<R>,
+}
+impl<R> Clone for LazySlot<R> {
+    fn clone(&self) -> Self {
+        Self {
+            layout: self.layout,
+            index: self.index,
+            builder: self.builder.clone(),
+        }
+    }
+}
+impl<R> LazySlot<R> {
+    pub fn layout(&self) -> &Lazy {
+        &self.layout
+    }
+    pub fn index(&self) -> Option<usize> {
+        self.index
+    }
+}
+impl<R> RenderAble<R> for LazySlot<R>
+where R: Renderer + Render<Self>
+{
+    fn mount<'gui>(&self, mut context: WidgetContext<'gui, R>) -> WidgetContext<'gui, R> {
+        context.mount_render(self);
+        context.set_hidden(self.index.is_none());
+        let Some(index) = self.index else { return context };
+        // the state of the item that left the slot doesn't go to the next one
+        if context.get_component::<LazyItem>().is_some_and(|x| x.index != index) {
+            context = context.remove_unmounted_children();
+        }
+        context.insert_component(LazyItem { index });
+        context.mount_child(&*(self.builder)(index))
+    }
+}
@@ -1,17 +1,24 @@
-use visora_core::{WidgetContext, renderer::Renderer, widget::{Render, RenderAble, Widget}};
+use std::sync::Arc;
+
+use visora_core::{WidgetContext, layout::{Axis, Linear}, renderer::Renderer, widget::{Render, RenderAble, Widget}};
 
 
 
 
 pub struct Hlist<R> {
-    pub data: Vec<Box<dyn RenderAble<R>>>,
+    pub data: Vec<Arc<dyn RenderAble<R> + Send + Sync>>,
+}
+impl<R> Clone for Hlist<R> {
+    fn clone(&self) -> Self {
+        Self { data: self.data.clone() }
+    }
 }
 impl<R: Renderer> Hlist<R> {
     pub fn new() -> Self {
         Self { data: vec![] }
     }
-    pub fn add(mut self, x: impl RenderAble<R> + 'static) -> Self {
-        self.data.push(Box::new(x));
+    pub fn add(mut self, x: impl RenderAble<R> + Send + Sync + 'static) -> Self {
+        self.data.push(Arc::new(x));
         self
     }
 }
@@ -19,10 +26,10 @@ impl<R> RenderAble<R> for Hlist<R>
 where R: Renderer + Render<Self>
 {
     fn mount<'gui>(&self, mut context: WidgetContext<'gui, R>) -> WidgetContext<'gui, R> {
-        R::mount(self, &mut context);
+        context.mount_render(self);
+        context.set_layout(Linear::new(Axis::Horizontal));
         for x in &self.data {
-            let child = context.new_child();
-            context = x.mount(child).to_parent().unwrap();
+            context = context.mount_child(&**x);
         }
         context
     }
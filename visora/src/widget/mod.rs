@@ -6,8 +6,18 @@ pub mod text;
 pub mod center;
 pub mod container;
 pub mod list;
+pub mod flex;
+pub mod grid;
+pub mod scroll;
+pub mod lazy;
 pub mod button;
 pub mod image;
+pub mod provider;
+pub mod opacity;
+pub mod animated;
+pub mod boundary;
+pub mod navigator;
+pub mod reactive;
 macro_rules! trim_plus {
     (+ $($rest:tt)*) => {
         $($rest:tt)*
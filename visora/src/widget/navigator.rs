@@ -0,0 +1,447 @@
+use std::{any::Any, marker::PhantomData, sync::{Arc, Mutex}};
+
+use visora_core::{
+    renderer::Renderer,
+    state::{State, StateTransaction},
+    treecs::{iterators::breadth::{BreadthInfo, BreadthIter}, query::Query, EntityKey, Treecs},
+    widget::{RenderAble, Widget},
+    BuildContext, WidgetContext,
+};
+
+type Page<R> = Arc<dyn RenderAble<R> + Send + Sync>;
+type Builder<R> = dyn Fn(&RouteParams) -> Page<R> + Send + Sync;
+type Observer = dyn Fn(&Navigation) + Send + Sync;
+type ResultHandler = Box<dyn FnOnce(Option<Box<dyn Any + Send>>) -> Option<StateTransaction> + Send + Sync>;
+
+/// a page of a [`Navigator`]
+pub struct Route<R> {
+    name: Option<String>,
+    page: Page<R>,
+}
+impl<R> Clone for Route<R> {
+    fn clone(&self) -> Self {
+        Self {
+            name: self.name.clone(),
+            page: self.page.clone(),
+        }
+    }
+}
+impl<R> Route<R> {
+    pub fn new(page: impl RenderAble<R> + Send + Sync + 'static) -> Self {
+        Self {
+            name: None,
+            page: Arc::new(page),
+        }
+    }
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_owned());
+        self
+    }
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+}
+
+/// the path a named route was pushed with and the parameters its pattern captured
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct RouteParams {
+    path: String,
+    params: Vec<(String, String)>,
+}
+impl RouteParams {
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+    /// the segment of the path captured by `:name` in the pattern
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.params.iter().find(|(x, _)| x == name).map(|(_, value)| value.as_str())
+    }
+}
+
+/// matches `path` against `pattern`, a segment of the pattern starting with `:` captures any segment
+///
+/// `/users/:id` matches `/users/42?tab=posts` with `id` set to `42`, the query and the fragment are ignored
+pub fn match_path(pattern: &str, path: &str) -> Option<RouteParams> {
+    let segments = |x: &str| x.split('/').filter(|x| !x.is_empty()).map(str::to_owned).collect::<Vec<_>>();
+    let pattern = segments(pattern);
+    let actual = segments(path.split(['?', '#']).next().unwrap_or_default());
+    if pattern.len() != actual.len() {
+        return None;
+    }
+    let mut params = Vec::new();
+    for (expected, segment) in pattern.into_iter().zip(actual) {
+        match expected.strip_prefix(':') {
+            Some(name) => params.push((name.to_owned(), segment)),
+            None if expected == segment => {}
+            None => return None,
+        }
+    }
+    Some(RouteParams {
+        path: path.to_owned(),
+        params,
+    })
+}
+
+/// a change of the stack of a [`Navigator`], see [`Navigator::with_on_change`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Navigation {
+    pub kind: NavigationKind,
+    /// the amount of routes after the change
+    pub depth: usize,
+    /// the name of the new top route
+    pub path: Option<String>,
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NavigationKind {
+    Push,
+    Replace,
+    Pop,
+    /// the stack was changed by [`NavigatorHandle::restore`]
+    Restore,
+}
+
+/// shows the top of a stack of routes, the pages below it keep their state but are hidden
+///
+/// descendants change the stack through the handle returned by [`Navigator::of`]
+pub struct Navigator<R> {
+    home: Route<R>,
+    routes: Vec<(String, Arc<Builder<R>>)>,
+    unknown: Option<Arc<Builder<R>>>,
+    initial_path: Option<String>,
+    on_change: Option<Arc<Observer>>,
+}
+impl<R> Clone for Navigator<R> {
+    fn clone(&self) -> Self {
+        Self {
+            home: self.home.clone(),
+            routes: self.routes.clone(),
+            unknown: self.unknown.clone(),
+            initial_path: self.initial_path.clone(),
+            on_change: self.on_change.clone(),
+        }
+    }
+}
+impl<R: Renderer + 'static> Navigator<R> {
+    /// a navigator that starts with `home`, the route named `/`
+    pub fn new(home: impl RenderAble<R> + Send + Sync + 'static) -> Self {
+        Self {
+            home: Route::new(home).with_name("/"),
+            routes: Vec::new(),
+            unknown: None,
+            initial_path: None,
+            on_change: None,
+        }
+    }
+    /// a route that can be pushed by a path matching `pattern`, see [`match_path`]
+    ///
+    /// `page` builds a new page every time the route is pushed, the first registered pattern that matches wins
+    pub fn with_route<F, W>(mut self, pattern: &str, page: F) -> Self
+    where F: Fn(&RouteParams) -> W + Send + Sync + 'static,
+          W: RenderAble<R> + Send + Sync + 'static
+    {
+        self.routes.push((pattern.to_owned(), Arc::new(move |params| Arc::new(page(params)) as Page<R>)));
+        self
+    }
+    /// the page of the paths no route matches, without it pushing an unknown path panics
+    pub fn with_unknown_route<F, W>(mut self, page: F) -> Self
+    where F: Fn(&RouteParams) -> W + Send + Sync + 'static,
+          W: RenderAble<R> + Send + Sync + 'static
+    {
+        self.unknown = Some(Arc::new(move |params| Arc::new(page(params)) as Page<R>));
+        self
+    }
+    /// starts with the route of `path` on top of the home route, like a deep link or the url of a request
+    ///
+    /// a path without a route starts at the home route
+    pub fn with_initial_path(mut self, path: &str) -> Self {
+        self.initial_path = Some(path.to_owned());
+        self
+    }
+    /// called after every change of the stack, while the transaction that changed it is dispatched
+    pub fn with_on_change<F>(mut self, on_change: F) -> Self
+    where F: Fn(&Navigation) + Send + Sync + 'static
+    {
+        self.on_change = Some(Arc::new(on_change));
+        self
+    }
+    /// the closest navigator above the entity of `context`
+    pub fn of(context: &BuildContext) -> Option<NavigatorHandle<R>> {
+        context.read::<NavigatorHandle<R>>().copied()
+    }
+    /// the first navigator below `root`, for backends that navigate from outside of the widgets
+    pub fn find(tree: &Treecs, root: EntityKey) -> Option<NavigatorHandle<R>> {
+        let mut query: Query<(EntityKey, &NavigatorState<R>), BreadthIter> = Query::new_at(tree, root);
+        query
+            .find(|(info, _)| *info == BreadthInfo::Other)
+            .map(|(_, (key, _))| NavigatorHandle { key, _ph: PhantomData })
+    }
+    /// the route of `path`, `None` when no route matches and there is no unknown route
+    fn route(&self, path: &str) -> Option<Route<R>> {
+        if match_path("/", path).is_some() {
+            return Some(self.home.clone());
+        }
+        let matched = self.routes.iter().find_map(|(pattern, page)| Some((match_path(pattern, path)?, page)));
+        let (params, page) = match matched {
+            Some(x) => x,
+            None => (RouteParams { path: path.to_owned(), params: Vec::new() }, self.unknown.as_ref()?),
+        };
+        Some(Route {
+            name: Some(path.to_owned()),
+            page: page(&params),
+        })
+    }
+    fn named(&self, path: &str) -> Route<R> {
+        self.route(path).unwrap_or_else(|| panic!("the navigator has no route matching `{path}`"))
+    }
+}
+
+struct Entry<R> {
+    /// a replaced route gets a new id, so its page doesn't inherit the state of the old one
+    id: u64,
+    route: Route<R>,
+    /// receives the result of [`NavigatorHandle::pop_with`], it belongs to the page that pushed this route
+    on_result: Option<ResultHandler>,
+}
+
+/// the stack of a [`Navigator`], the last route is shown
+pub struct NavigatorState<R> {
+    stack: Vec<Entry<R>>,
+    next_id: u64,
+}
+/// the names of the routes, for the devtools
+impl<R> std::fmt::Debug for NavigatorState<R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.names().iter().map(|x| x.unwrap_or("unnamed"))).finish()
+    }
+}
+impl<R> NavigatorState<R> {
+    pub fn depth(&self) -> usize {
+        self.stack.len()
+    }
+    /// the name of the top route
+    pub fn path(&self) -> Option<&str> {
+        self.stack.last()?.route.name()
+    }
+    /// the names of the routes from the bottom of the stack to the top, unnamed routes are `None`
+    pub fn names(&self) -> Vec<Option<&str>> {
+        self.stack.iter().map(|x| x.route.name()).collect()
+    }
+    fn push(&mut self, route: Route<R>, on_result: Option<ResultHandler>) {
+        self.stack.push(Entry {
+            id: self.next_id,
+            route,
+            on_result,
+        });
+        self.next_id += 1;
+    }
+    /// the root route is never popped
+    fn pop(&mut self, result: Option<Box<dyn Any + Send>>) -> Option<StateTransaction> {
+        if self.stack.len() < 2 {
+            return None;
+        }
+        let on_result = self.stack.pop()?.on_result?;
+        on_result(result)
+    }
+}
+
+/// changes the stack of a [`Navigator`], the changes are applied when the runtime dispatches its transactions
+pub struct NavigatorHandle<R> {
+    key: EntityKey,
+    _ph: PhantomData<fn() -> R>,
+}
+impl<R> Clone for NavigatorHandle<R> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<R> Copy for NavigatorHandle<R> {}
+impl<R> PartialEq for NavigatorHandle<R> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+impl<R: Renderer + 'static> NavigatorHandle<R> {
+    /// the entity of the navigator
+    pub fn key(&self) -> EntityKey {
+        self.key
+    }
+    pub fn push(&self, context: &BuildContext, route: Route<R>) {
+        self.change(context, NavigationKind::Push, move |_, state| {
+            state.push(route.clone(), None);
+            Vec::new()
+        });
+    }
+    /// pushes `route`, the transaction returned by `on_result` is dispatched when the route is popped
+    ///
+    /// the result is `None` when the route is popped without one or with a value of another type
+    pub fn push_for_result<T, F>(&self, context: &BuildContext, route: Route<R>, on_result: F)
+    where T: 'static,
+          F: FnOnce(Option<T>) -> StateTransaction + Send + Sync + 'static
+    {
+        let on_result = Mutex::new(Some(on_result));
+        self.change(context, NavigationKind::Push, move |_, state| {
+            if let Some(on_result) = on_result.lock().unwrap().take() {
+                state.push(route.clone(), Some(Box::new(move |result: Option<Box<dyn Any + Send>>| {
+                    Some(on_result(result.and_then(|x| x.downcast::<T>().ok()).map(|x| *x)))
+                })));
+            }
+            Vec::new()
+        });
+    }
+    /// pushes the route registered with [`Navigator::with_route`] whose pattern matches `path`
+    ///
+    /// panics when no route matches and the navigator has no unknown route
+    pub fn push_named(&self, context: &BuildContext, path: &str) {
+        let path = path.to_owned();
+        self.change(context, NavigationKind::Push, move |navigator, state| {
+            state.push(navigator.named(&path), None);
+            Vec::new()
+        });
+    }
+    /// replaces the top route, the page of the old one is disposed and its result handler is kept
+    pub fn replace(&self, context: &BuildContext, route: Route<R>) {
+        self.change(context, NavigationKind::Replace, move |_, state| {
+            replace(state, route.clone());
+            Vec::new()
+        });
+    }
+    pub fn replace_named(&self, context: &BuildContext, path: &str) {
+        let path = path.to_owned();
+        self.change(context, NavigationKind::Replace, move |navigator, state| {
+            replace(state, navigator.named(&path));
+            Vec::new()
+        });
+    }
+    /// removes the top route and disposes its page, unless it is the only one
+    pub fn pop(&self, context: &BuildContext) {
+        self.pop_result(context, None);
+    }
+    /// pops the top route and passes `result` to the page that pushed it
+    pub fn pop_with<T: Send + 'static>(&self, context: &BuildContext, result: T) {
+        self.pop_result(context, Some(Box::new(result)));
+    }
+    fn pop_result(&self, context: &BuildContext, result: Option<Box<dyn Any + Send>>) {
+        let result = Mutex::new(result);
+        self.change(context, NavigationKind::Pop, move |_, state| {
+            state.pop(result.lock().unwrap().take()).into_iter().collect()
+        });
+    }
+    /// moves the stack to an entry of a history kept outside of the navigator, like the history of a browser
+    ///
+    /// pops down to `depth` routes, or pushes the route of `path` when the entry is deeper than the stack.
+    /// a path without a route is ignored, the popped routes return no result
+    pub fn restore(&self, context: &BuildContext, depth: usize, path: &str) {
+        let path = path.to_owned();
+        self.change(context, NavigationKind::Restore, move |navigator, state| {
+            let mut transactions = Vec::new();
+            while state.depth() > depth.max(1) {
+                transactions.extend(state.pop(None));
+            }
+            if state.path() != Some(path.as_str()) {
+                match (state.depth() < depth, navigator.route(&path)) {
+                    (true, Some(route)) => state.push(route, None),
+                    (false, Some(route)) => replace(state, route),
+                    (_, None) => {}
+                }
+            }
+            transactions
+        });
+    }
+    /// queues a transaction that changes the stack, the transactions `f` returns are queued afterwards
+    fn change<F>(&self, context: &BuildContext, kind: NavigationKind, f: F)
+    where F: Fn(&Navigator<R>, &mut NavigatorState<R>) -> Vec<StateTransaction> + Send + Sync + 'static
+    {
+        context.runtime().queue(StateTransaction::new(self.key, move |context| {
+            let navigator = context.get_component::<Navigator<R>>().expect("the handle refers to a navigator");
+            let state = context.get_component_mut::<NavigatorState<R>>().unwrap();
+            let top = state.stack.last().map(|x| x.id);
+            let depth = state.depth();
+            for transaction in f(navigator, state) {
+                context.runtime().queue(transaction);
+            }
+            // popping the root route changes nothing
+            if let Some(on_change) = &navigator.on_change {
+                if state.stack.last().map(|x| x.id) != top || state.depth() != depth {
+                    on_change(&Navigation {
+                        kind,
+                        depth: state.depth(),
+                        path: state.path().map(str::to_owned),
+                    });
+                }
+            }
+        }));
+    }
+}
+fn replace<R>(state: &mut NavigatorState<R>, route: Route<R>) {
+    let on_result = state.stack.pop().and_then(|x| x.on_result);
+    state.push(route, on_result);
+}
+
+impl<R: Renderer + 'static> RenderAble<R> for Navigator<R> {
+    fn mount<'gui>(&self, mut context: WidgetContext<'gui, R>) -> WidgetContext<'gui, R> {
+        context.provide(NavigatorHandle::<R> {
+            key: context.key(),
+            _ph: PhantomData,
+        });
+        context.mount_widget(self)
+    }
+}
+impl<R: Renderer + 'static> Widget<R> for Navigator<R> {
+    type State = NavigatorState<R>;
+    fn create_state(&self) -> Self::State {
+        let mut state = NavigatorState {
+            stack: Vec::new(),
+            next_id: 0,
+        };
+        state.push(self.home.clone(), None);
+        if let Some(route) = self.initial_path.as_deref().and_then(|x| self.route(x)) {
+            if route.name() != self.home.name() {
+                state.push(route, None);
+            }
+        }
+        state
+    }
+    fn build<'gui>(&self, state: State<Self>, _context: &mut BuildContext<'gui>) -> impl RenderAble<R> + 'static {
+        let stack = &state.read::<R>().stack;
+        Pages {
+            slots: stack.iter().enumerate().map(|(i, x)| PageSlot {
+                id: x.id,
+                page: x.route.page.clone(),
+                hidden: i + 1 != stack.len(),
+            }).collect(),
+        }
+    }
+}
+
+/// the pages of the stack, a popped route loses its slot and its page is disposed
+struct Pages<R> {
+    slots: Vec<PageSlot<R>>,
+}
+impl<R: Renderer + 'static> RenderAble<R> for Pages<R> {
+    fn mount<'gui>(&self, mut context: WidgetContext<'gui, R>) -> WidgetContext<'gui, R> {
+        for slot in &self.slots {
+            context = context.mount_child(slot);
+        }
+        context
+    }
+}
+
+/// component with the id of the route a slot shows
+#[derive(PartialEq)]
+struct RouteId(u64);
+
+struct PageSlot<R> {
+    id: u64,
+    page: Page<R>,
+    hidden: bool,
+}
+impl<R: Renderer + 'static> RenderAble<R> for PageSlot<R> {
+    fn mount<'gui>(&self, mut context: WidgetContext<'gui, R>) -> WidgetContext<'gui, R> {
+        context.set_hidden(self.hidden);
+        if context.get_component::<RouteId>() != Some(&RouteId(self.id)) {
+            context.insert_component(RouteId(self.id));
+            context = context.remove_unmounted_children();
+        }
+        context.mount_child(&*self.page)
+    }
+}
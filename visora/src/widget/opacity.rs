@@ -0,0 +1,36 @@
+use std::sync::Arc;
+
+use visora_core::{renderer::Renderer, widget::{Render, RenderAble}, WidgetContext};
+
+/// draws its child partially transparent, 0 is invisible and 1 is opaque
+pub struct Opacity<R> {
+    opacity: f32,
+    child: Arc<dyn RenderAble<R> + Send + Sync>
+}
+impl<R> Clone for Opacity<R> {
+    fn clone(&self) -> Self {
+        Self {
+            opacity: self.opacity,
+            child: self.child.clone()
+        }
+    }
+}
+impl<R: Renderer> Opacity<R> {
+    pub fn new(opacity: f32, child: impl RenderAble<R> + Send + Sync + 'static) -> Self {
+        Self {
+            opacity: opacity.clamp(0.0, 1.0),
+            child: Arc::new(child)
+        }
+    }
+    pub fn opacity(&self) -> f32 {
+        self.opacity
+    }
+}
+impl<R> RenderAble<R> for Opacity<R>
+where R: Renderer + Render<Self>
+{
+    fn mount<'gui>(&self, mut context: WidgetContext<'gui, R>) -> WidgetContext<'gui, R> {
+        context.mount_render(self);
+        context.mount_child(&*self.child)
+    }
+}
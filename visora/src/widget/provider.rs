@@ -0,0 +1,27 @@
+use visora_core::{renderer::Renderer, widget::RenderAble, Component, WidgetContext};
+
+/// makes `value` available to every widget below it, they read it with [`visora_core::BuildContext::watch`]
+///
+/// descendants that watch the value are rebuilt when a different value is provided
+pub struct Provider<T, W> {
+    value: T,
+    child: W,
+}
+impl<T, W> Provider<T, W> {
+    pub fn new(value: T, child: W) -> Self {
+        Self {
+            value,
+            child,
+        }
+    }
+}
+impl<R, T, W> RenderAble<R> for Provider<T, W>
+where T: Component + PartialEq + Clone,
+      W: RenderAble<R> + 'static,
+      R: Renderer
+{
+    fn mount<'gui>(&self, mut context: WidgetContext<'gui, R>) -> WidgetContext<'gui, R> {
+        context.provide(self.value.clone());
+        context.mount_child(&self.child)
+    }
+}
@@ -0,0 +1,58 @@
+use std::{marker::PhantomData, sync::Arc};
+
+use visora_core::{
+    renderer::Renderer,
+    state::State,
+    widget::{RenderAble, Widget},
+    BuildContext, WidgetContext,
+};
+
+/// builds its child with a closure, the signals and memos it reads rebuild only this widget and not its parents
+///
+/// ```ignore
+/// let count = context.use_signal(0);
+/// Reactive::new(move |context| Text::new(&format!("count {}", count.get(context))))
+/// ```
+pub struct Reactive<R, F> {
+    build: Arc<F>,
+    _ph: PhantomData<fn() -> R>,
+}
+impl<R, F> Clone for Reactive<R, F> {
+    fn clone(&self) -> Self {
+        Self {
+            build: self.build.clone(),
+            _ph: PhantomData
+        }
+    }
+}
+impl<R, F, W> Reactive<R, F>
+where F: Fn(&BuildContext) -> W + Send + Sync + 'static,
+      W: RenderAble<R> + 'static
+{
+    pub fn new(build: F) -> Self {
+        Self {
+            build: Arc::new(build),
+            _ph: PhantomData
+        }
+    }
+}
+impl<R, F, W> RenderAble<R> for Reactive<R, F>
+where R: Renderer + 'static,
+      F: Fn(&BuildContext) -> W + Send + Sync + 'static,
+      W: RenderAble<R> + 'static
+{
+    fn mount<'gui>(&self, context: WidgetContext<'gui, R>) -> WidgetContext<'gui, R> {
+        context.mount_widget(self)
+    }
+}
+impl<R, F, W> Widget<R> for Reactive<R, F>
+where R: Renderer + 'static,
+      F: Fn(&BuildContext) -> W + Send + Sync + 'static,
+      W: RenderAble<R> + 'static
+{
+    type State = ();
+    fn create_state(&self) -> Self::State {}
+    fn build<'gui>(&self, _state: State<Self>, context: &mut BuildContext<'gui>) -> impl RenderAble<R> + 'static {
+        (self.build)(context)
+    }
+}
@@ -0,0 +1,353 @@
+use std::sync::{Arc, Mutex};
+
+use visora_core::{
+    event::{Event, Point, PointerButton, Propagation},
+    layout::{scroll::{Scroll, ScrollMetrics, ScrollbarThumb}, Axis, LayoutRect, Proxy, Size},
+    renderer::Renderer,
+    state::{State, StateHandle, StateTransaction},
+    treecs::EntityKey,
+    widget::{Render, RenderAble, Widget},
+    BuildContext, WidgetContext,
+};
+
+type Child<R> = Arc<dyn RenderAble<R> + Send + Sync>;
+
+/// shows a window onto its child that the wheel and dragging with the pointer move
+///
+/// the child is laid out unbounded along the scrolled axes, so the scroll view needs bounded constraints, like
+/// the window or an [`super::flex::Expanded`]. the wheel and the drags that reach the end of the content bubble
+/// on, to the scroll views around it
+pub struct ScrollView<R> {
+    child: Child<R>,
+    horizontal: bool,
+    vertical: bool,
+    scrollbars: bool,
+    controller: Option<ScrollController>,
+    sized_child: bool,
+}
+impl<R> Clone for ScrollView<R> {
+    fn clone(&self) -> Self {
+        Self {
+            child: self.child.clone(),
+            horizontal: self.horizontal,
+            vertical: self.vertical,
+            scrollbars: self.scrollbars,
+            controller: self.controller.clone(),
+            sized_child: self.sized_child,
+        }
+    }
+}
+impl<R: Renderer> ScrollView<R> {
+    /// scrolls `child` vertically and shows the scrollbars
+    pub fn new(child: impl RenderAble<R> + Send + Sync + 'static) -> Self {
+        Self {
+            child: Arc::new(child),
+            horizontal: false,
+            vertical: true,
+            scrollbars: true,
+            controller: None,
+            sized_child: false,
+        }
+    }
+    /// only scrolls along `axis`
+    pub fn with_axis(mut self, axis: Axis) -> Self {
+        self.horizontal = axis == Axis::Horizontal;
+        self.vertical = axis == Axis::Vertical;
+        self
+    }
+    pub fn with_both_axes(mut self) -> Self {
+        self.horizontal = true;
+        self.vertical = true;
+        self
+    }
+    pub fn with_scrollbars(mut self, scrollbars: bool) -> Self {
+        self.scrollbars = scrollbars;
+        self
+    }
+    pub fn with_controller(mut self, controller: ScrollController) -> Self {
+        self.controller = Some(controller);
+        self
+    }
+    /// builds the child again when the viewport changes size or the page scrolls it, for a child that only builds
+    /// what is visible, see [`ScrollPosition`]
+    pub(super) fn with_sized_child(mut self) -> Self {
+        self.sized_child = true;
+        self
+    }
+    /// `offset` without the axes that don't scroll
+    fn along_axes(&self, offset: Point) -> Point {
+        Point::new(
+            if self.horizontal { offset.x } else { 0.0 },
+            if self.vertical { offset.y } else { 0.0 },
+        )
+    }
+}
+
+/// the offset of a [`ScrollView`], how far its content is moved to the top left
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ScrollState {
+    offset: Point,
+    /// the size of the viewport the page reported, a backend without a layout doesn't know it otherwise
+    reported: Option<Size>,
+}
+impl ScrollState {
+    pub fn offset(&self) -> Point {
+        self.offset
+    }
+}
+
+/// moves a [`ScrollView`] from outside of it, like a button that goes back to the top
+///
+/// a controller belongs to one scroll view at a time. a jump before the scroll view is mounted is the offset it
+/// starts at
+#[derive(Debug, Clone, Default)]
+pub struct ScrollController {
+    inner: Arc<Mutex<Attachment>>,
+}
+#[derive(Debug, Default)]
+struct Attachment {
+    key: Option<EntityKey>,
+    offset: Point,
+}
+impl ScrollController {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// the offset of the last jump or input, the layout clamps it to the content when it is drawn
+    pub fn offset(&self) -> Point {
+        self.inner.lock().unwrap().offset
+    }
+    /// the entity of the scroll view the controller belongs to
+    pub fn key(&self) -> Option<EntityKey> {
+        self.inner.lock().unwrap().key
+    }
+    /// moves the content to `offset`, the scroll view is rebuilt when the runtime dispatches the transaction
+    pub fn jump_to(&self, context: &BuildContext, offset: Point) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.offset = offset;
+        let Some(key) = inner.key else { return };
+        context.runtime().queue(StateTransaction::new(key, move |context| {
+            if let Some(state) = context.get_component_mut::<ScrollState>() {
+                state.offset = offset;
+            }
+        }));
+    }
+    fn set_offset(&self, offset: Point) {
+        self.inner.lock().unwrap().offset = offset;
+    }
+    fn attach(&self, key: EntityKey) {
+        self.inner.lock().unwrap().key = Some(key);
+    }
+    fn detach(&self, key: EntityKey) {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.key == Some(key) {
+            inner.key = None;
+        }
+    }
+}
+
+impl<R> RenderAble<R> for ScrollView<R>
+where R: Renderer + Render<ScrollViewport<R>> + Render<Scrollbar>
+{
+    fn mount<'gui>(&self, mut context: WidgetContext<'gui, R>) -> WidgetContext<'gui, R> {
+        if let Some(controller) = &self.controller {
+            controller.attach(context.key());
+        }
+        context.set_layout(Proxy::default());
+        context.mount_widget(self)
+    }
+}
+impl<R> Widget<R> for ScrollView<R>
+where R: Renderer + Render<ScrollViewport<R>> + Render<Scrollbar>
+{
+    type State = ScrollState;
+    fn create_state(&self) -> Self::State {
+        ScrollState {
+            offset: self.controller.as_ref().map(ScrollController::offset).unwrap_or_default(),
+            reported: None,
+        }
+    }
+    fn build<'gui>(&self, state: State<Self>, _context: &mut BuildContext<'gui>) -> impl RenderAble<R> + 'static {
+        let read = state.read::<R>();
+        ScrollViewport {
+            child: self.child.clone(),
+            layout: Scroll {
+                horizontal: self.horizontal,
+                vertical: self.vertical,
+                offset: read.offset,
+                ..Scroll::both()
+            },
+            scrollbars: self.scrollbars,
+            sized_child: self.sized_child,
+            reported: read.reported,
+            state: state.handle(),
+        }
+    }
+    fn dispose<'gui>(&self, context: &mut BuildContext<'gui>) {
+        if let Some(controller) = &self.controller {
+            controller.detach(context.key());
+        }
+    }
+}
+
+/// what a [`ScrollView`] builds, it clips its child and takes the input
+pub struct ScrollViewport<R> {
+    child: Child<R>,
+    layout: Scroll,
+    scrollbars: bool,
+    sized_child: bool,
+    reported: Option<Size>,
+    state: StateHandle<ScrollView<R>>,
+}
+impl<R> Clone for ScrollViewport<R> {
+    fn clone(&self) -> Self {
+        Self {
+            child: self.child.clone(),
+            layout: self.layout,
+            scrollbars: self.scrollbars,
+            sized_child: self.sized_child,
+            reported: self.reported,
+            state: self.state,
+        }
+    }
+}
+impl<R> ScrollViewport<R> {
+    /// the axes and the offset, which the layout clamps
+    pub fn layout(&self) -> &Scroll {
+        &self.layout
+    }
+    pub fn scrollbars(&self) -> bool {
+        self.scrollbars
+    }
+}
+
+/// where the content of the closest [`ScrollView`] is, for content that only builds the part that is visible
+///
+/// the viewport is the one of the last layout, or the one the page reported. it is empty before either
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ScrollPosition {
+    /// the offset of the scroll view, it can be past the end after a jump
+    pub offset: Point,
+    pub viewport: Size,
+}
+
+/// the position of the pointer while it drags the content, stored on the viewport
+struct Drag(Option<Point>);
+
+impl<R> RenderAble<R> for ScrollViewport<R>
+where R: Renderer + Render<Self> + Render<Scrollbar>
+{
+    fn mount<'gui>(&self, mut context: WidgetContext<'gui, R>) -> WidgetContext<'gui, R> {
+        context.mount_render(self);
+        let viewport = context.get_component::<ScrollMetrics>()
+            .map(|x| x.viewport)
+            .or(self.reported)
+            .unwrap_or_default();
+        context.provide(ScrollPosition { offset: self.layout.offset, viewport });
+        // a child built for another viewport is built again after the layout
+        context.set_layout(Scroll { built_for: self.sized_child.then_some(viewport), ..self.layout });
+        if context.get_component::<Drag>().is_none() {
+            context.insert_component(Drag(None));
+        }
+        let (state, layout, sized_child) = (self.state, self.layout, self.sized_child);
+        context.on_event(move |event, context| handle_input::<R>(event, context, state, layout, sized_child));
+        context = context.mount_child(&*self.child);
+        for (axis, scrolls) in [(Axis::Vertical, self.layout.vertical), (Axis::Horizontal, self.layout.horizontal)] {
+            if self.scrollbars && scrolls {
+                context = context.mount_child(&Scrollbar { axis });
+            }
+        }
+        context
+    }
+}
+
+/// moves the content by the wheel and by drags, and follows the backends that scroll themselves
+fn handle_input<R>(
+    event: &Event,
+    context: &mut BuildContext,
+    state: StateHandle<ScrollView<R>>,
+    layout: Scroll,
+    sized_child: bool,
+) -> Propagation
+where R: Renderer + Render<ScrollViewport<R>> + Render<Scrollbar>
+{
+    let delta = match event {
+        Event::Scroll { delta, .. } => *delta,
+        Event::PointerDown { position, button: PointerButton::Primary } => {
+            context.get_component_mut::<Drag>().unwrap().0 = Some(*position);
+            return Propagation::Stop;
+        }
+        Event::PointerMove { position } => {
+            let drag = context.get_component_mut::<Drag>().unwrap();
+            let Some(last) = drag.0.replace(*position) else { return Propagation::Continue };
+            // the content follows the pointer
+            Point::new(last.x - position.x, last.y - position.y)
+        }
+        Event::PointerUp { .. } => {
+            context.get_component_mut::<Drag>().unwrap().0 = None;
+            return Propagation::Continue;
+        }
+        // the leave events of the children bubble through, the drag ends when the pointer leaves the viewport
+        Event::PointerLeave { position } => {
+            if context.get_component::<LayoutRect>().is_some_and(|x| !x.contains(*position)) {
+                context.get_component_mut::<Drag>().unwrap().0 = None;
+            }
+            return Propagation::Continue;
+        }
+        // the page already shows the offset, it is only kept for the next builds, unless the child is built for
+        // what is visible
+        Event::Scrolled { offset, viewport } => {
+            let (offset, viewport) = (*offset, *viewport);
+            let transaction = state.update::<_, R>(move |widget, state| {
+                state.offset = widget.along_axes(offset);
+                state.reported = Some(viewport);
+                if let Some(controller) = &widget.controller {
+                    controller.set_offset(state.offset);
+                }
+            });
+            context.runtime().queue(if sized_child { transaction } else { transaction.without_rebuild() });
+            return Propagation::Stop;
+        }
+        _ => return Propagation::Continue,
+    };
+    let delta = Point::new(
+        if layout.horizontal { delta.x } else { 0.0 },
+        if layout.vertical { delta.y } else { 0.0 },
+    );
+    // a backend without a layout clamps the offset itself, like the browser
+    let metrics = context.get_component::<ScrollMetrics>().copied();
+    let clamp = move |offset: Point| match metrics {
+        Some(metrics) => metrics.clamp(offset),
+        None => Point::new(offset.x.max(0.0), offset.y.max(0.0)),
+    };
+    if let Some(metrics) = metrics {
+        let moved = clamp(Point::new(metrics.offset.x + delta.x, metrics.offset.y + delta.y));
+        if moved == metrics.offset {
+            return Propagation::Continue;
+        }
+    }
+    // the offset of a jump can be past the end
+    context.runtime().queue(state.update::<_, R>(move |widget, state| {
+        let offset = clamp(state.offset);
+        state.offset = clamp(Point::new(offset.x + delta.x, offset.y + delta.y));
+        if let Some(controller) = &widget.controller {
+            controller.set_offset(state.offset);
+        }
+    }));
+    Propagation::Stop
+}
+
+/// the thumb of a scrollbar of a [`ScrollView`], the layout sizes and places it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Scrollbar {
+    pub axis: Axis,
+}
+impl<R> RenderAble<R> for Scrollbar
+where R: Renderer + Render<Self>
+{
+    fn mount<'gui>(&self, mut context: WidgetContext<'gui, R>) -> WidgetContext<'gui, R> {
+        context.mount_render(self);
+        context.insert_component(ScrollbarThumb { axis: self.axis });
+        context
+    }
+}
@@ -1,8 +1,9 @@
 use lipsum::lipsum;
 use visora_core::{
-    BuildContext, Component, WidgetContext, color::Color, renderer::Renderer, widget::{Render, RenderAble, Widget}
+    BuildContext, Component, WidgetContext, color::Color, layout::{TextLayout, TextStyle}, renderer::Renderer, widget::{Render, RenderAble, Widget}
 };
 
+#[derive(Clone)]
 pub struct Text {
     pub data: String,
     pub is_bold: bool
@@ -24,17 +25,23 @@ impl Text {
         self.is_bold = true;
         self
     }
+    /// the style the text is laid out with
+    pub fn style(&self) -> TextStyle {
+        TextStyle { bold: self.is_bold, ..Default::default() }
+    }
 }
 impl<R> RenderAble<R> for Text 
-where R: Render<Self>
+where R: Renderer + Render<Self>
 {
     fn mount<'gui>(&self, mut context: WidgetContext<'gui, R>) -> WidgetContext<'gui, R> {
-        R::mount(&self, &mut context);
+        context.mount_render(self);
+        context.set_layout(TextLayout::new(&self.data, self.style()));
         context
     }
 }
 
 
+#[derive(Clone, Copy, PartialEq, Eq)]
 pub enum Align{
     /// aligns text on the left edge
     Left,
@@ -45,10 +52,12 @@ pub enum Align{
     /// stretches the lines so that each line has equal width
     Justify
 }
+#[derive(Clone, Copy, PartialEq, Eq)]
 pub enum Direction {
     LeftToRight,
     RightToLeft
 }
+#[derive(Clone)]
 pub struct RichText{
     text: String,
     alignment: Align,
@@ -98,9 +107,10 @@ impl RichText {
         &self.color
     }
 }
-impl<R: Render<Self>> RenderAble<R> for RichText {
+impl<R: Renderer + Render<Self>> RenderAble<R> for RichText {
     fn mount<'gui>(&self, mut context: WidgetContext<'gui, R>) -> WidgetContext<'gui, R> {
-        R::mount(&self, &mut context);
+        context.mount_render(self);
+        context.set_layout(TextLayout::new(&self.text, TextStyle::default()));
         context
     }
 }
@@ -0,0 +1,46 @@
+use std::{sync::{Arc, Mutex}, time::{Duration, Instant}};
+
+/// the time source of the frames, see [`crate::Gui::with_clock`]
+pub trait Clock: Send + Sync {
+    /// the time since an arbitrary but fixed moment
+    fn now(&self) -> Duration;
+}
+
+/// the real time, used when no other clock is set
+pub struct SystemClock {
+    start: Instant,
+}
+impl SystemClock {
+    pub fn new() -> Self {
+        Self { start: Instant::now() }
+    }
+}
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.start.elapsed()
+    }
+}
+
+/// a clock that only moves when told to, so tests can step through animations without a window
+#[derive(Clone, Default)]
+pub struct ManualClock {
+    now: Arc<Mutex<Duration>>,
+}
+impl ManualClock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn advance(&self, by: Duration) {
+        *self.now.lock().unwrap() += by;
+    }
+}
+impl Clock for ManualClock {
+    fn now(&self) -> Duration {
+        *self.now.lock().unwrap()
+    }
+}
@@ -0,0 +1,58 @@
+/// maps the linear progress of an animation to an eased one, both between 0 and 1
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Curve {
+    #[default]
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+    /// a cubic bezier from (0, 0) to (1, 1) with the control points (x1, y1) and (x2, y2), like css `cubic-bezier`
+    Cubic(f64, f64, f64, f64),
+}
+impl Curve {
+    pub fn transform(&self, t: f64) -> f64 {
+        let t = t.clamp(0.0, 1.0);
+        match *self {
+            Self::Linear => t,
+            Self::EaseIn => cubic(0.42, 0.0, 1.0, 1.0, t),
+            Self::EaseOut => cubic(0.0, 0.0, 0.58, 1.0, t),
+            Self::EaseInOut => cubic(0.42, 0.0, 0.58, 1.0, t),
+            Self::Cubic(x1, y1, x2, y2) => cubic(x1, y1, x2, y2, t),
+        }
+    }
+}
+/// the y of the bezier at the point where its x is `t`
+fn cubic(x1: f64, y1: f64, x2: f64, y2: f64, t: f64) -> f64 {
+    let bezier = |a: f64, b: f64, s: f64| 3.0 * a * s * (1.0 - s).powi(2) + 3.0 * b * s.powi(2) * (1.0 - s) + s.powi(3);
+    // x grows with s for control points between 0 and 1, so bisection finds the s of t
+    let (mut low, mut high) = (0.0, 1.0);
+    for _ in 0..32 {
+        let middle = (low + high) / 2.0;
+        if bezier(x1, x2, middle) < t {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    bezier(y1, y2, (low + high) / 2.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Curve;
+
+    #[test]
+    fn curves_keep_endpoints() {
+        for curve in [Curve::Linear, Curve::EaseIn, Curve::EaseOut, Curve::EaseInOut] {
+            assert!(curve.transform(0.0).abs() < 1e-6, "{curve:?}");
+            assert!((curve.transform(1.0) - 1.0).abs() < 1e-6, "{curve:?}");
+        }
+    }
+    #[test]
+    fn easing() {
+        assert!(Curve::EaseIn.transform(0.25) < 0.25);
+        assert!(Curve::EaseOut.transform(0.25) > 0.25);
+        assert!((Curve::EaseInOut.transform(0.5) - 0.5).abs() < 1e-6);
+        assert_eq!(Curve::Linear.transform(2.0), 1.0);
+    }
+}
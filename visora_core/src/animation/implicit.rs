@@ -0,0 +1,105 @@
+use std::time::Duration;
+
+use crate::BuildContext;
+
+use super::{curve::Curve, tween::{Lerp, Tween}, AnimationController};
+
+/// state of an implicitly animated widget, it animates from the shown value to every new target
+///
+/// the controller is created by [`ImplicitAnimation::init`] from `Widget::init_state`,
+/// new targets are set by [`ImplicitAnimation::animate_to`] from `Widget::did_update_widget`
+pub struct ImplicitAnimation<T> {
+    controller: Option<AnimationController>,
+    tween: Tween<T>,
+}
+impl<T: Lerp + Clone + PartialEq> ImplicitAnimation<T> {
+    pub fn new(value: T) -> Self {
+        Self {
+            controller: None,
+            tween: Tween::new(value.clone(), value),
+        }
+    }
+    /// creates the controller on the entity of `context`
+    pub fn init(&mut self, context: &BuildContext, duration: Duration) {
+        let controller = AnimationController::new(context, duration);
+        controller.set_value(1.0);
+        self.controller = Some(controller);
+    }
+    /// starts animating from the current value to `target`, nothing changes when it already is the target
+    pub fn animate_to(&mut self, target: T, duration: Duration, curve: Curve) {
+        if *self.tween.end() == target {
+            return;
+        }
+        let Some(controller) = &self.controller else {
+            self.tween = Tween::new(target.clone(), target);
+            return;
+        };
+        self.tween = Tween::new(self.value(), target).with_curve(curve);
+        controller.set_duration(duration);
+        controller.set_value(0.0);
+        controller.forward();
+    }
+    /// the value to show in the current frame
+    pub fn value(&self) -> T {
+        match &self.controller {
+            Some(controller) => self.tween.evaluate(controller),
+            None => self.tween.end().clone(),
+        }
+    }
+    pub fn target(&self) -> &T {
+        self.tween.end()
+    }
+    pub fn is_animating(&self) -> bool {
+        self.controller.as_ref().is_some_and(AnimationController::is_animating)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{
+        animation::{clock::ManualClock, curve::Curve},
+        test_utils::TestRenderer,
+        Gui,
+    };
+
+    use super::ImplicitAnimation;
+
+    #[test]
+    fn animates_from_the_shown_value() {
+        let clock = ManualClock::new();
+        let mut gui = Gui::new(TestRenderer::new()).with_clock(clock.clone());
+        let mut animation = ImplicitAnimation::new(0.0f32);
+        animation.init(&gui.root_build_context(), Duration::from_millis(100));
+        assert_eq!(animation.value(), 0.0);
+
+        animation.animate_to(10.0, Duration::from_millis(100), Curve::Linear);
+        gui.tick();
+        clock.advance(Duration::from_millis(50));
+        gui.tick();
+        assert_eq!(animation.value(), 5.0);
+
+        // a new target starts from where the animation is, not from its old start
+        animation.animate_to(0.0, Duration::from_millis(200), Curve::Linear);
+        assert_eq!(animation.value(), 5.0);
+        gui.tick();
+        clock.advance(Duration::from_millis(100));
+        gui.tick();
+        assert_eq!(animation.value(), 2.5);
+        clock.advance(Duration::from_millis(100));
+        gui.tick();
+        assert_eq!(animation.value(), 0.0);
+        assert!(!animation.is_animating());
+    }
+    #[test]
+    fn same_target_does_not_restart() {
+        let clock = ManualClock::new();
+        let mut gui = Gui::new(TestRenderer::new()).with_clock(clock.clone());
+        let mut animation = ImplicitAnimation::new(1.0f32);
+        animation.init(&gui.root_build_context(), Duration::from_millis(100));
+        animation.animate_to(1.0, Duration::from_millis(100), Curve::Linear);
+        assert!(!animation.is_animating());
+        assert_eq!(gui.tick(), 0);
+    }
+}
@@ -0,0 +1,305 @@
+use std::{sync::{Arc, Mutex}, time::Duration};
+
+use crate::BuildContext;
+
+pub mod clock;
+pub mod curve;
+pub mod tween;
+pub mod implicit;
+
+type OnTick = dyn FnMut(Duration) -> bool + Send;
+
+/// calls a function once per frame while it is active, see [`crate::Gui::tick`]
+///
+/// the entity that created it is marked dirty after every tick, it stops when that entity is removed
+#[derive(Clone)]
+pub struct Ticker {
+    state: Arc<Mutex<TickerState>>,
+}
+struct TickerState {
+    active: bool,
+    /// the frame time of the first tick since the last start
+    start: Option<Duration>,
+    /// called with the time since the start, returns whether the ticker stays active
+    on_tick: Box<OnTick>,
+}
+impl Ticker {
+    /// a stopped ticker for the entity of `context`
+    pub fn new<F>(context: &BuildContext, on_tick: F) -> Self
+    where F: FnMut(Duration) -> bool + Send + 'static
+    {
+        let ticker = Self {
+            state: Arc::new(Mutex::new(TickerState {
+                active: false,
+                start: None,
+                on_tick: Box::new(on_tick),
+            })),
+        };
+        match context.get_component_mut::<Tickers>() {
+            Some(tickers) => tickers.tickers.push(ticker.clone()),
+            None => context.insert_component(Tickers { tickers: vec![ticker.clone()] }),
+        }
+        ticker
+    }
+    /// (re)starts the ticker, the time is counted from the next frame
+    pub fn start(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.active = true;
+        state.start = None;
+    }
+    pub fn stop(&self) {
+        self.state.lock().unwrap().active = false;
+    }
+    pub fn is_active(&self) -> bool {
+        self.state.lock().unwrap().active
+    }
+    /// returns whether the ticker was active
+    pub(crate) fn tick(&self, now: Duration) -> bool {
+        let mut state = self.state.lock().unwrap();
+        if !state.active {
+            return false;
+        }
+        let start = *state.start.get_or_insert(now);
+        state.active = (state.on_tick)(now - start);
+        true
+    }
+}
+
+/// component with the tickers created by an entity
+#[derive(Default)]
+pub struct Tickers {
+    tickers: Vec<Ticker>,
+}
+impl Tickers {
+    /// ticks every active ticker, returns whether one was active
+    pub(crate) fn tick(&self, now: Duration) -> bool {
+        self.tickers.iter().fold(false, |ticked, x| x.tick(now) | ticked)
+    }
+    pub fn is_active(&self) -> bool {
+        self.tickers.iter().any(Ticker::is_active)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnimationStatus {
+    /// stopped at the start
+    Dismissed,
+    Forward,
+    Reverse,
+    /// stopped at the end
+    Completed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Run {
+    /// from the value at the start towards `target`
+    To { from: f64, target: f64 },
+    /// from 0 to 1 over and over, going back to 0 when `reverse`
+    Repeat { reverse: bool },
+}
+
+/// drives a value from 0 to 1 over a duration, rebuilding the entity that created it every frame
+///
+/// clones control the same animation, so it can be kept in the state of a widget
+#[derive(Clone)]
+pub struct AnimationController {
+    state: Arc<Mutex<ControllerState>>,
+    ticker: Ticker,
+}
+struct ControllerState {
+    value: f64,
+    duration: Duration,
+    status: AnimationStatus,
+    run: Option<Run>,
+}
+impl AnimationController {
+    pub fn new(context: &BuildContext, duration: Duration) -> Self {
+        let state = Arc::new(Mutex::new(ControllerState {
+            value: 0.0,
+            duration,
+            status: AnimationStatus::Dismissed,
+            run: None,
+        }));
+        let ticked = state.clone();
+        let ticker = Ticker::new(context, move |elapsed| ticked.lock().unwrap().tick(elapsed));
+        Self { state, ticker }
+    }
+    pub fn value(&self) -> f64 {
+        self.state.lock().unwrap().value
+    }
+    pub fn status(&self) -> AnimationStatus {
+        self.state.lock().unwrap().status
+    }
+    pub fn duration(&self) -> Duration {
+        self.state.lock().unwrap().duration
+    }
+    pub fn is_animating(&self) -> bool {
+        self.ticker.is_active()
+    }
+    pub fn set_duration(&self, duration: Duration) {
+        self.state.lock().unwrap().duration = duration;
+    }
+    /// jumps to `value` and stops
+    pub fn set_value(&self, value: f64) {
+        self.stop();
+        let mut state = self.state.lock().unwrap();
+        state.value = value.clamp(0.0, 1.0);
+        state.status = match state.value {
+            x if x >= 1.0 => AnimationStatus::Completed,
+            _ => AnimationStatus::Dismissed,
+        };
+    }
+    /// animates from the current value to 1
+    pub fn forward(&self) {
+        self.animate_to(1.0, AnimationStatus::Forward);
+    }
+    /// animates from the current value to 0
+    pub fn reverse(&self) {
+        self.animate_to(0.0, AnimationStatus::Reverse);
+    }
+    /// animates from 0 to 1 until stopped, going back to 0 in between when `reverse`
+    pub fn repeat(&self, reverse: bool) {
+        let mut state = self.state.lock().unwrap();
+        state.run = Some(Run::Repeat { reverse });
+        state.status = AnimationStatus::Forward;
+        drop(state);
+        self.ticker.start();
+    }
+    /// stops at the current value
+    pub fn stop(&self) {
+        self.ticker.stop();
+        self.state.lock().unwrap().run = None;
+    }
+    fn animate_to(&self, target: f64, status: AnimationStatus) {
+        let mut state = self.state.lock().unwrap();
+        state.run = Some(Run::To {
+            from: state.value,
+            target,
+        });
+        state.status = status;
+        drop(state);
+        self.ticker.start();
+    }
+}
+impl ControllerState {
+    /// updates the value for the time since the start of the run, returns whether it keeps running
+    fn tick(&mut self, elapsed: Duration) -> bool {
+        let duration = self.duration.as_secs_f64().max(f64::EPSILON);
+        let elapsed = elapsed.as_secs_f64();
+        match self.run {
+            None => false,
+            Some(Run::Repeat { reverse: false }) => {
+                self.value = (elapsed / duration).fract();
+                true
+            }
+            Some(Run::Repeat { reverse: true }) => {
+                let phase = (elapsed / duration) % 2.0;
+                self.value = if phase < 1.0 { phase } else { 2.0 - phase };
+                self.status = if phase < 1.0 { AnimationStatus::Forward } else { AnimationStatus::Reverse };
+                true
+            }
+            Some(Run::To { from, target }) => {
+                // the whole duration is for the whole range, a partial range takes a part of it
+                let span = duration * (target - from).abs();
+                let progress = if span > 0.0 { (elapsed / span).min(1.0) } else { 1.0 };
+                self.value = from + (target - from) * progress;
+                if progress < 1.0 {
+                    return true;
+                }
+                self.run = None;
+                self.status = if target >= 1.0 { AnimationStatus::Completed } else { AnimationStatus::Dismissed };
+                false
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{
+        test_utils::{Label, Lifecycles, TestRenderer},
+        Gui,
+    };
+
+    use super::{clock::ManualClock, AnimationController, AnimationStatus};
+
+    fn gui() -> (Gui<TestRenderer>, ManualClock) {
+        let clock = ManualClock::new();
+        (Gui::new(TestRenderer::new()).with_clock(clock.clone()), clock)
+    }
+    fn assert_close(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "{a} != {b}");
+    }
+    fn step(gui: &mut Gui<TestRenderer>, clock: &ManualClock, millis: u64) {
+        clock.advance(Duration::from_millis(millis));
+        gui.tick();
+    }
+
+    #[test]
+    fn forward_and_reverse() {
+        let (mut gui, clock) = gui();
+        let controller = AnimationController::new(&gui.root_build_context(), Duration::from_millis(100));
+        controller.forward();
+        assert_eq!(controller.status(), AnimationStatus::Forward);
+
+        // the first frame is the start of the animation
+        step(&mut gui, &clock, 0);
+        step(&mut gui, &clock, 50);
+        assert_close(controller.value(), 0.5);
+        step(&mut gui, &clock, 60);
+        assert_close(controller.value(), 1.0);
+        assert_eq!(controller.status(), AnimationStatus::Completed);
+        assert!(!controller.is_animating());
+
+        controller.reverse();
+        step(&mut gui, &clock, 0);
+        step(&mut gui, &clock, 25);
+        assert_close(controller.value(), 0.75);
+        // reversing halfway takes the rest of the time for the rest of the range
+        controller.forward();
+        step(&mut gui, &clock, 0);
+        step(&mut gui, &clock, 25);
+        assert_close(controller.value(), 1.0);
+    }
+    #[test]
+    fn repeat_with_reverse() {
+        let (mut gui, clock) = gui();
+        let controller = AnimationController::new(&gui.root_build_context(), Duration::from_millis(100));
+        controller.repeat(true);
+        step(&mut gui, &clock, 0);
+        step(&mut gui, &clock, 150);
+        assert_close(controller.value(), 0.5);
+        assert_eq!(controller.status(), AnimationStatus::Reverse);
+        step(&mut gui, &clock, 100);
+        assert_close(controller.value(), 0.5);
+        assert_eq!(controller.status(), AnimationStatus::Forward);
+
+        controller.stop();
+        step(&mut gui, &clock, 10);
+        assert_close(controller.value(), 0.5);
+        assert!(!gui.is_animating());
+    }
+    #[test]
+    fn ticks_rebuild_the_entity() {
+        let (mut gui, clock) = gui();
+        let (widget, log) = Lifecycles::new("animated", Label::new("label"));
+        gui.root_widget_context().mount_child(&widget);
+        let entity = *gui.tree().linkdata(gui.tree().root()).unwrap().children().get_left().unwrap();
+        let controller = AnimationController::new(&gui.build_context(entity).unwrap(), Duration::from_millis(20));
+
+        assert_eq!(gui.tick(), 0, "a stopped animation doesn't rebuild");
+        controller.forward();
+        assert!(gui.is_animating());
+        step(&mut gui, &clock, 0);
+        step(&mut gui, &clock, 10);
+        step(&mut gui, &clock, 10);
+        assert_eq!(gui.tick(), 0);
+        assert_eq!(log.lock().unwrap().as_slice(), ["build animated", "init animated", "build animated", "build animated", "build animated"]);
+
+        controller.forward();
+        gui.remove(entity);
+        assert_eq!(gui.tick(), 0);
+    }
+}
@@ -0,0 +1,81 @@
+use crate::color::Color;
+
+use super::{curve::Curve, AnimationController};
+
+/// values that can be interpolated, `t` is 0 for `self` and 1 for `other`
+pub trait Lerp {
+    fn lerp(&self, other: &Self, t: f64) -> Self;
+}
+impl Lerp for f32 {
+    fn lerp(&self, other: &Self, t: f64) -> Self {
+        self + (other - self) * t as f32
+    }
+}
+impl Lerp for f64 {
+    fn lerp(&self, other: &Self, t: f64) -> Self {
+        self + (other - self) * t
+    }
+}
+impl Lerp for Color {
+    fn lerp(&self, other: &Self, t: f64) -> Self {
+        // `Color::lerp` weights its receiver by t
+        Color::lerp(*other, *self, t)
+    }
+}
+
+/// maps the value of an animation to a value between `begin` and `end`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tween<T> {
+    begin: T,
+    end: T,
+    curve: Curve,
+}
+impl<T: Lerp> Tween<T> {
+    pub fn new(begin: T, end: T) -> Self {
+        Self {
+            begin,
+            end,
+            curve: Curve::Linear,
+        }
+    }
+    pub fn with_curve(mut self, curve: Curve) -> Self {
+        self.curve = curve;
+        self
+    }
+    pub fn begin(&self) -> &T {
+        &self.begin
+    }
+    pub fn end(&self) -> &T {
+        &self.end
+    }
+    pub fn transform(&self, t: f64) -> T {
+        self.begin.lerp(&self.end, self.curve.transform(t))
+    }
+    /// the value at the current value of `controller`
+    pub fn evaluate(&self, controller: &AnimationController) -> T {
+        self.transform(controller.value())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{animation::curve::Curve, color::Color};
+
+    use super::Tween;
+
+    #[test]
+    fn tween_f32() {
+        let tween = Tween::new(10.0f32, 20.0);
+        assert_eq!(tween.transform(0.0), 10.0);
+        assert_eq!(tween.transform(0.5), 15.0);
+        assert_eq!(tween.transform(1.0), 20.0);
+        assert!(tween.with_curve(Curve::EaseIn).transform(0.5) < 15.0);
+    }
+    #[test]
+    fn tween_color() {
+        let tween = Tween::new(Color::new_argb(255, 0, 0, 0), Color::new_argb(255, 200, 100, 0));
+        assert_eq!(tween.transform(0.0).value(), Color::new_argb(255, 0, 0, 0).value());
+        assert_eq!(tween.transform(0.5).value(), Color::new_argb(255, 100, 50, 0).value());
+        assert_eq!(tween.transform(1.0).value(), Color::new_argb(255, 200, 100, 0).value());
+    }
+}
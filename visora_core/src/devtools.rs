@@ -0,0 +1,200 @@
+use std::{collections::VecDeque, fmt::{Debug, Write}, time::Duration};
+
+use slotmap::{Key as _, KeyData};
+
+use crate::{event::Point, layout::LayoutRect, renderer::Renderer, treecs::{EntityKey, Treecs}, widget::WidgetType, Component, Gui};
+
+/// how many dispatched transactions the devtools keep
+const TRANSACTIONS: usize = 200;
+
+/// a number for `entity` that stays the same while it is in the tree, the devtools and recordings use it
+pub fn entity_id(entity: EntityKey) -> u64 {
+    entity.data().as_ffi()
+}
+/// the entity of an [`entity_id`]
+pub fn entity_from_id(id: u64) -> EntityKey {
+    KeyData::from_ffi(id).into()
+}
+
+type Format = fn(&Treecs, EntityKey) -> Option<String>;
+
+fn format<T: Component + Debug>(tree: &Treecs, entity: EntityKey) -> Option<String> {
+    tree.get_component::<T>(entity).map(|x| format!("{x:?}"))
+}
+
+/// a transaction the gui dispatched, as shown by the devtools
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dispatched {
+    pub target: EntityKey,
+    /// the widget on the target, when it still had one
+    pub widget: Option<&'static str>,
+    /// the time of the gui clock
+    pub at: Duration,
+}
+
+/// what the devtools inspector needs from a gui, see [`Gui::with_devtools`]
+///
+/// the gui keeps the last dispatched transactions and the entity selected in the inspector,
+/// [`Gui::inspect`] describes the tree for the inspector
+pub struct Devtools {
+    debuggable: Vec<(&'static str, Format)>,
+    transactions: VecDeque<Dispatched>,
+    selected: Option<EntityKey>,
+}
+impl Default for Devtools {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl Devtools {
+    pub fn new() -> Self {
+        Self {
+            debuggable: Vec::new(),
+            transactions: VecDeque::new(),
+            selected: None,
+        }
+    }
+    /// the inspector shows the `T` of every entity that has one, formatted with [`Debug`]
+    pub fn with_debuggable<T: Component + Debug>(mut self) -> Self {
+        self.debuggable.push((std::any::type_name::<T>(), format::<T>));
+        self
+    }
+    /// the entity selected in the inspector, the renderers highlight it
+    pub fn selected(&self) -> Option<EntityKey> {
+        self.selected
+    }
+    pub fn select(&mut self, entity: Option<EntityKey>) {
+        self.selected = entity;
+    }
+    /// the last dispatched transactions, oldest first
+    pub fn transactions(&self) -> impl Iterator<Item = &Dispatched> {
+        self.transactions.iter()
+    }
+    pub(crate) fn dispatched(&mut self, transaction: Dispatched) {
+        if self.transactions.len() == TRANSACTIONS {
+            self.transactions.pop_front();
+        }
+        self.transactions.push_back(transaction);
+    }
+}
+
+/// `text` as a json string
+fn string(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 2);
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+/// ids are strings, javascript numbers can't hold every `u64`
+fn id(entity: EntityKey) -> String {
+    format!("\"{}\"", entity_id(entity))
+}
+fn optional(entity: Option<EntityKey>) -> String {
+    entity.map(id).unwrap_or("null".to_owned())
+}
+
+/// the json the inspector shows, see [`Gui::inspect`]
+///
+/// `{"roots": [id], "selected": id, "focused": id, "entities": [entity], "transactions": [transaction]}`, with
+/// - entity: `{"id", "parent", "children": [id], "widget", "hidden", "bounds": [left, top, right, bottom], "components": {name: value}}`
+/// - transaction: `{"target": id, "widget", "at": micros}`
+pub(crate) fn inspect<R: Renderer>(gui: &Gui<R>, devtools: &Devtools) -> String {
+    let tree = &gui.tree;
+    let mut entities = Vec::new();
+    for root in tree.roots() {
+        let renderer = match gui.roots.iter().find(|x| x.key == root) {
+            Some(x) => &x.renderer,
+            None => &gui.renderer,
+        };
+        let mut stack = vec![root];
+        while let Some(key) = stack.pop() {
+            let Some(link) = tree.linkdata(key) else { continue };
+            let children: Vec<EntityKey> = link.children().iter().copied().collect();
+            stack.extend(children.iter().rev());
+            let bounds = renderer.bounds(key)
+                .or_else(|| tree.get_component::<LayoutRect>(key).map(|x| (Point::new(x.x, x.y), Point::new(x.right(), x.bottom()))))
+                .map(|(a, b)| format!("[{},{},{},{}]", a.x, a.y, b.x, b.y))
+                .unwrap_or("null".to_owned());
+            let components: Vec<String> = devtools.debuggable
+                .iter()
+                .filter_map(|(name, format)| Some(format!("{}:{}", string(name), string(&format(tree, key)?))))
+                .collect();
+            entities.push(format!(
+                "{{\"id\":{},\"parent\":{},\"children\":[{}],\"widget\":{},\"hidden\":{},\"bounds\":{},\"components\":{{{}}}}}",
+                id(key),
+                optional(*link.parent()),
+                children.iter().map(|x| id(*x)).collect::<Vec<_>>().join(","),
+                tree.get_component::<WidgetType>(key).map(|x| string(x.name())).unwrap_or("null".to_owned()),
+                link.is_hidden(),
+                bounds,
+                components.join(",")
+            ));
+        }
+    }
+    let transactions: Vec<String> = devtools.transactions
+        .iter()
+        .map(|x| format!(
+            "{{\"target\":{},\"widget\":{},\"at\":{}}}",
+            id(x.target),
+            x.widget.map(string).unwrap_or("null".to_owned()),
+            x.at.as_micros()
+        ))
+        .collect();
+    format!(
+        "{{\"roots\":[{}],\"selected\":{},\"focused\":{},\"entities\":[{}],\"transactions\":[{}]}}",
+        tree.roots().map(id).collect::<Vec<_>>().join(","),
+        optional(devtools.selected),
+        optional(gui.focused()),
+        entities.join(","),
+        transactions.join(",")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        state::StateTransaction,
+        test_utils::{Label, TestRenderer},
+        Gui,
+    };
+
+    use super::{entity_from_id, entity_id, Devtools};
+
+    #[test]
+    fn inspect_describes_the_tree() {
+        let mut gui = Gui::new(TestRenderer::new()).with_devtools(Devtools::new().with_debuggable::<u32>());
+        let mut context = gui.root_widget_context().mount_child(&Label::new("a \"quoted\" label"));
+        context.insert_component(7u32);
+        let root = gui.tree().root();
+        let label = *gui.tree().linkdata(root).unwrap().children().get_left().unwrap();
+        gui.renderer().areas = vec![(label, 0.0..10.0)];
+        gui.dispatch(&StateTransaction::new(root, |_| ()));
+        gui.devtools_mut().unwrap().select(Some(label));
+        gui.render();
+        assert_eq!(entity_from_id(entity_id(label)), label);
+
+        let (root, label) = (entity_id(root), entity_id(label));
+        assert_eq!(
+            gui.inspect().unwrap(),
+            format!(
+                "{{\"roots\":[\"{root}\"],\"selected\":\"{label}\",\"focused\":null,\"entities\":[\
+                {{\"id\":\"{root}\",\"parent\":null,\"children\":[\"{label}\"],\"widget\":null,\"hidden\":false,\"bounds\":null,\"components\":{{\"u32\":\"7\"}}}},\
+                {{\"id\":\"{label}\",\"parent\":\"{root}\",\"children\":[],\"widget\":\"visora_core::test_utils::Label\",\"hidden\":false,\"bounds\":[0,0,10,0],\"components\":{{}}}}],\
+                \"transactions\":[{{\"target\":\"{root}\",\"widget\":null,\"at\":{}}}]}}",
+                gui.devtools().unwrap().transactions().next().unwrap().at.as_micros()
+            )
+        );
+        assert_eq!(gui.renderer().highlighted, Some(entity_from_id(label)));
+    }
+}
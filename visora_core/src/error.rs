@@ -0,0 +1,243 @@
+use std::{any::Any, fmt};
+
+use crate::{treecs::{iterators::parent::ParentIter, query::Query, EntityKey, Treecs}, widget::WidgetType, BuildContext};
+
+type Handler = dyn Fn(WidgetError, &mut BuildContext) + Send + Sync;
+
+/// a panic caught while building or mounting a widget, or while applying a transaction to its state
+#[derive(Debug, Clone)]
+pub struct WidgetError {
+    message: String,
+    entity: EntityKey,
+    /// the widget types from the root down to the entity that panicked
+    path: Vec<String>,
+}
+impl WidgetError {
+    pub(crate) fn new(tree: &Treecs, entity: EntityKey, payload: &(dyn Any + Send)) -> Self {
+        let message = match (payload.downcast_ref::<&str>(), payload.downcast_ref::<String>()) {
+            (Some(x), _) => x.to_string(),
+            (_, Some(x)) => x.clone(),
+            _ => "the widget panicked".to_owned(),
+        };
+        let mut path: Vec<String> = ParentIter::new(tree, entity)
+            .filter_map(|x| tree.get_component::<WidgetType>(x))
+            .map(|x| short_name(x.name()))
+            .collect();
+        path.reverse();
+        Self { message, entity, path }
+    }
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+    /// the entity that panicked, it is removed by the time the error is reported
+    pub fn entity(&self) -> EntityKey {
+        self.entity
+    }
+    pub fn path(&self) -> &[String] {
+        &self.path
+    }
+}
+impl fmt::Display for WidgetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}\n  in {}", self.message, self.path.join(" > "))
+    }
+}
+impl std::error::Error for WidgetError {}
+
+/// component that catches the panics of the descendants of a stateful widget, like an error boundary
+///
+/// when a rebuild or a transaction below the entity panics the gui passes the error to the handler
+/// and rebuilds the entity, which is expected to mount a fallback in place of its child
+pub struct Catcher {
+    on_error: Box<Handler>,
+}
+impl Catcher {
+    pub fn new<F>(on_error: F) -> Self
+    where F: Fn(WidgetError, &mut BuildContext) + Send + Sync + 'static
+    {
+        Self { on_error: Box::new(on_error) }
+    }
+    pub(crate) fn catch(&self, error: WidgetError, context: &mut BuildContext) {
+        (self.on_error)(error, context);
+    }
+}
+
+/// the closest ancestor of `entity` that catches its panics
+pub(crate) fn find_catcher(tree: &Treecs, entity: EntityKey) -> Option<EntityKey> {
+    let parent = (*tree.linkdata(entity)?.parent())?;
+    let mut query: Query<(EntityKey, &Catcher), ParentIter> = Query::new_parent(tree, parent);
+    query.next().map(|(key, _)| key)
+}
+
+/// the type name without module paths, `visora::widget::list::Hlist<visora_ssr::html::HtmlRenderer>` becomes `Hlist<HtmlRenderer>`
+fn short_name(name: &str) -> String {
+    let mut short = String::new();
+    let mut segment = String::new();
+    for c in name.chars() {
+        match c {
+            ':' => segment.clear(),
+            '<' | '>' | ',' | ' ' | '(' | ')' | '[' | ']' | '&' => {
+                short.push_str(&segment);
+                short.push(c);
+                segment.clear();
+            }
+            _ => segment.push(c),
+        }
+    }
+    short.push_str(&segment);
+    short
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use crate::{
+        state::{State, StateTransaction},
+        test_utils::{Dynamic, Label, TestRenderer},
+        widget::{RenderAble, Widget},
+        BuildContext, Gui, WidgetContext,
+    };
+
+    use super::{short_name, Catcher, WidgetError};
+
+    /// panics while building when its state is `true`
+    #[derive(Clone)]
+    struct Fragile;
+    impl RenderAble<TestRenderer> for Fragile {
+        fn mount<'gui>(&self, context: WidgetContext<'gui, TestRenderer>) -> WidgetContext<'gui, TestRenderer> {
+            context.mount_widget(self)
+        }
+    }
+    impl Widget<TestRenderer> for Fragile {
+        type State = bool;
+        fn create_state(&self) -> Self::State {
+            false
+        }
+        fn build<'gui>(&self, state: State<Self>, _context: &mut BuildContext<'gui>) -> impl RenderAble<TestRenderer> + 'static {
+            assert!(!state.read::<TestRenderer>(), "fragile broke");
+            Label::new("fragile")
+        }
+    }
+
+    /// a boundary made of a catcher on an entity holding a stateful widget
+    #[derive(Clone)]
+    struct Boundary {
+        errors: Arc<Mutex<Vec<WidgetError>>>,
+    }
+    impl RenderAble<TestRenderer> for Boundary {
+        fn mount<'gui>(&self, mut context: WidgetContext<'gui, TestRenderer>) -> WidgetContext<'gui, TestRenderer> {
+            let errors = self.errors.clone();
+            context.insert_component(Catcher::new(move |error, context| {
+                errors.lock().unwrap().push(error);
+                *context.get_component_mut::<bool>().unwrap() = true;
+            }));
+            context.mount_widget(self)
+        }
+    }
+    impl Widget<TestRenderer> for Boundary {
+        type State = bool;
+        fn create_state(&self) -> Self::State {
+            false
+        }
+        fn build<'gui>(&self, state: State<Self>, _context: &mut BuildContext<'gui>) -> impl RenderAble<TestRenderer> + 'static {
+            match state.read::<TestRenderer>() {
+                true => BoundaryChild::Fallback,
+                false => BoundaryChild::Fragile,
+            }
+        }
+    }
+    enum BoundaryChild {
+        Fragile,
+        Fallback,
+    }
+    impl RenderAble<TestRenderer> for BoundaryChild {
+        fn mount<'gui>(&self, context: WidgetContext<'gui, TestRenderer>) -> WidgetContext<'gui, TestRenderer> {
+            match self {
+                Self::Fragile => context.mount_child(&Fragile),
+                Self::Fallback => context.mount_child(&Dynamic { text: "fallback".to_owned() }),
+            }
+        }
+    }
+
+    /// mounts a label and then a child that panics
+    struct Exploding;
+    impl RenderAble<TestRenderer> for Exploding {
+        fn mount<'gui>(&self, context: WidgetContext<'gui, TestRenderer>) -> WidgetContext<'gui, TestRenderer> {
+            context.mount_child(&Label::new("partial")).mount_child(&Bomb)
+        }
+    }
+    struct Bomb;
+    impl RenderAble<TestRenderer> for Bomb {
+        fn mount<'gui>(&self, _context: WidgetContext<'gui, TestRenderer>) -> WidgetContext<'gui, TestRenderer> {
+            panic!("boom")
+        }
+    }
+
+    fn mounted() -> (Gui<TestRenderer>, Arc<Mutex<Vec<WidgetError>>>) {
+        let mut gui = Gui::new(TestRenderer::new());
+        let errors = Arc::new(Mutex::new(Vec::new()));
+        gui.root_widget_context().mount_child(&Boundary { errors: errors.clone() });
+        gui.render();
+        assert_eq!(gui.renderer().rendered(), ["fragile"]);
+        (gui, errors)
+    }
+    fn fragile(gui: &Gui<TestRenderer>) -> crate::treecs::EntityKey {
+        let tree = gui.tree();
+        let boundary = *tree.linkdata(tree.root()).unwrap().children().get_left().unwrap();
+        let child = *tree.linkdata(boundary).unwrap().children().get_left().unwrap();
+        *tree.linkdata(child).unwrap().children().get_left().unwrap()
+    }
+
+    #[test]
+    fn rebuild_panics_go_to_the_catcher() {
+        let (mut gui, errors) = mounted();
+        let target = fragile(&gui);
+        gui.dispatch(&StateTransaction::new(target, |context| *context.get_component_mut::<bool>().unwrap() = true));
+        gui.render();
+
+        assert_eq!(gui.renderer().rendered(), ["fallback"]);
+        assert!(!gui.tree().contains(target));
+        let errors = errors.lock().unwrap();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].message(), "fragile broke");
+        assert_eq!(errors[0].entity(), target);
+        assert_eq!(errors[0].path(), ["Boundary", "BoundaryChild", "Fragile"]);
+    }
+    #[test]
+    fn transaction_panics_go_to_the_catcher() {
+        let (mut gui, errors) = mounted();
+        let target = fragile(&gui);
+        gui.dispatch(&StateTransaction::new(target, |_| todo!()));
+        gui.render();
+
+        assert_eq!(gui.renderer().rendered(), ["fallback"]);
+        assert_eq!(errors.lock().unwrap()[0].message(), "not yet implemented");
+    }
+    #[test]
+    fn mount_panics_are_returned() {
+        let mut gui = Gui::new(TestRenderer::new());
+        let (context, error) = gui.root_widget_context().try_mount_child(&Exploding);
+        context.mount_child(&Label::new("fallback"));
+        gui.render();
+
+        assert_eq!(gui.renderer().rendered(), ["fallback"], "the partially mounted child is removed");
+        let error = error.expect("the panic is caught");
+        assert_eq!(error.message(), "boom");
+        assert_eq!(error.path(), ["Exploding", "Bomb"]);
+        assert_eq!(error.to_string(), "boom\n  in Exploding > Bomb");
+    }
+    #[test]
+    #[should_panic(expected = "fragile broke")]
+    fn panics_without_catcher_propagate() {
+        let mut gui = Gui::new(TestRenderer::new());
+        gui.root_widget_context().mount_child(&Fragile);
+        let target = *gui.tree().linkdata(gui.tree().root()).unwrap().children().get_left().unwrap();
+        gui.dispatch(&StateTransaction::new(target, |context| *context.get_component_mut::<bool>().unwrap() = true));
+    }
+    #[test]
+    fn names_are_shortened() {
+        assert_eq!(short_name("visora::widget::list::Hlist<visora_ssr::html::HtmlRenderer>"), "Hlist<HtmlRenderer>");
+        assert_eq!(short_name("alloc::sync::Arc<dyn a::B + Send>"), "Arc<dyn B + Send>");
+    }
+}
@@ -0,0 +1,249 @@
+use crate::{layout::Size, BuildContext};
+
+/// a position in the coordinates of the window, in logical pixels
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Point {
+    pub x: f32,
+    pub y: f32,
+}
+impl Point {
+    pub fn new(x: f32, y: f32) -> Self {
+        Self { x, y }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PointerButton {
+    Primary,
+    Secondary,
+    Middle,
+    Other(u16),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Key {
+    Enter,
+    Space,
+    Tab,
+    Escape,
+    Backspace,
+    Delete,
+    ArrowUp,
+    ArrowDown,
+    ArrowLeft,
+    ArrowRight,
+    Home,
+    End,
+    /// a key that produces text, like `a` or `1`
+    Character(String),
+    /// a key the backend has no translation for
+    Unidentified,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyState {
+    Pressed,
+    Released,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Modifiers {
+    pub shift: bool,
+    pub ctrl: bool,
+    pub alt: bool,
+    pub meta: bool,
+}
+
+/// input that is delivered to an entity, every backend translates its native input into these
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    PointerDown { position: Point, button: PointerButton },
+    PointerUp { position: Point, button: PointerButton },
+    PointerMove { position: Point },
+    /// the pointer moved onto the target, sent by [`crate::Gui::handle_event`]
+    PointerEnter { position: Point },
+    /// the pointer moved off the target, sent by [`crate::Gui::handle_event`]
+    PointerLeave { position: Point },
+    Key { key: Key, state: KeyState, modifiers: Modifiers },
+    TextInput(String),
+    /// the wheel turned over the target, a positive `delta` scrolls towards the end of the content like in the browser
+    Scroll { position: Point, delta: Point },
+    /// the backend scrolled the target itself to `offset`, like the browser scrolling an element with overflow.
+    /// `viewport` is the size of the element, for backends without a layout
+    Scrolled { offset: Point, viewport: Size },
+}
+impl Event {
+    /// the position of the pointer for pointer and scroll events
+    pub fn position(&self) -> Option<Point> {
+        match self {
+            Self::PointerDown { position, .. }
+            | Self::PointerUp { position, .. }
+            | Self::PointerMove { position }
+            | Self::PointerEnter { position }
+            | Self::PointerLeave { position }
+            | Self::Scroll { position, .. } => Some(*position),
+            Self::Key { .. } | Self::TextInput(_) | Self::Scrolled { .. } => None,
+        }
+    }
+}
+
+/// whether an event keeps bubbling to the ancestors of the entity that handled it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Propagation {
+    Continue,
+    Stop,
+}
+
+type Handler = dyn Fn(&Event, &mut BuildContext) -> Propagation + Send + Sync;
+
+/// component with the function that handles the events delivered to or bubbling through an entity
+///
+/// the build context belongs to the entity of the handler, transactions it queues are dispatched after the event
+pub struct EventHandler {
+    handler: Box<Handler>,
+}
+impl EventHandler {
+    pub fn new<F>(handler: F) -> Self
+    where F: Fn(&Event, &mut BuildContext) -> Propagation + Send + Sync + 'static
+    {
+        Self {
+            handler: Box::new(handler),
+        }
+    }
+    pub fn handle(&self, event: &Event, context: &mut BuildContext) -> Propagation {
+        (self.handler)(event, context)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        test_utils::{Label, Log, TestRenderer},
+        treecs::EntityKey,
+        Gui, WidgetContext,
+    };
+
+    use super::{Event, EventHandler, Point, PointerButton, Propagation};
+
+    fn logging(log: &Log, name: &'static str, propagation: Propagation) -> EventHandler {
+        let log = log.clone();
+        EventHandler::new(move |event, _| {
+            let kind = match event {
+                Event::PointerEnter { .. } => "enter",
+                Event::PointerLeave { .. } => "leave",
+                Event::PointerMove { .. } => "move",
+                _ => "other",
+            };
+            log.lock().unwrap().push(format!("{kind} {name}"));
+            propagation
+        })
+    }
+    fn child<R: crate::renderer::Renderer>(context: WidgetContext<'_, R>) -> (WidgetContext<'_, R>, EntityKey) {
+        let context = context.new_child();
+        let key = context.key();
+        (context, key)
+    }
+    fn down() -> Event {
+        Event::PointerDown { position: Point::new(0.0, 0.0), button: PointerButton::Primary }
+    }
+
+    #[test]
+    fn event_bubbles_to_ancestors() {
+        let mut gui = Gui::new(TestRenderer::new());
+        let log = Log::default();
+        let mut context = gui.root_widget_context();
+        context.insert_component(logging(&log, "root", Propagation::Continue));
+        let (context, _) = child(context);
+        let (mut context, target) = child(context);
+        context.insert_component(logging(&log, "target", Propagation::Continue));
+
+        assert!(!gui.dispatch_event(target, &down()));
+        assert_eq!(log.lock().unwrap().as_slice(), ["other target", "other root"]);
+    }
+    #[test]
+    fn stop_propagation() {
+        let mut gui = Gui::new(TestRenderer::new());
+        let log = Log::default();
+        let mut context = gui.root_widget_context();
+        context.insert_component(logging(&log, "root", Propagation::Continue));
+        let (mut context, middle) = child(context);
+        context.insert_component(logging(&log, "middle", Propagation::Stop));
+        let (_, target) = child(context);
+
+        assert!(gui.dispatch_event(target, &down()));
+        assert_eq!(log.lock().unwrap().as_slice(), ["other middle"]);
+        assert!(gui.dispatch_event(middle, &down()));
+    }
+    #[test]
+    fn handler_transactions_are_dispatched() {
+        let mut gui = Gui::new(TestRenderer::new());
+        let mut context = gui.root_widget_context();
+        context.insert_component(0u32);
+        context.insert_component(EventHandler::new(|_, context| {
+            let key = context.key();
+            context.runtime().queue(crate::state::StateTransaction::new(key, |context| {
+                *context.get_component_mut::<u32>().unwrap() += 1;
+            }));
+            Propagation::Stop
+        }));
+        let root = gui.tree().root();
+
+        gui.dispatch_event(root, &down());
+        gui.dispatch_event(root, &down());
+        assert_eq!(gui.tree().get_component::<u32>(root), Some(&2));
+    }
+    #[test]
+    fn pointer_moves_send_enter_and_leave() {
+        let mut gui = Gui::new(TestRenderer::new());
+        let log = Log::default();
+        let context = gui.root_widget_context();
+        let mut context = context.mount_child(&Label::new("a")).mount_child(&Label::new("b"));
+        context.insert_component(logging(&log, "root", Propagation::Stop));
+        let children: Vec<_> = gui.tree().linkdata(gui.tree().root()).unwrap().children().iter().copied().collect();
+        for (child, name) in children.iter().zip(["a", "b"]) {
+            gui.build_context(*child).unwrap().insert_component(logging(&log, name, Propagation::Stop));
+        }
+        gui.renderer().areas = vec![(children[0], 0.0..10.0), (children[1], 10.0..20.0)];
+
+        gui.handle_event(Event::PointerMove { position: Point::new(5.0, 0.0) });
+        gui.handle_event(Event::PointerMove { position: Point::new(6.0, 0.0) });
+        gui.handle_event(Event::PointerMove { position: Point::new(15.0, 0.0) });
+        gui.handle_event(Event::PointerMove { position: Point::new(30.0, 0.0) });
+        assert_eq!(
+            log.lock().unwrap().as_slice(),
+            ["enter a", "move a", "move a", "leave a", "enter b", "move b", "leave b", "move root"]
+        );
+    }
+    #[test]
+    fn roots_render_and_receive_their_own_events() {
+        let mut gui = Gui::new(TestRenderer::new());
+        let log = Log::default();
+        let mut context = gui.root_widget_context().mount_child(&Label::new("main"));
+        context.insert_component(logging(&log, "main", Propagation::Stop));
+        let palette = gui.add_root(TestRenderer::new());
+        let mut context = gui.root_context(palette).unwrap().mount_child(&Label::new("palette"));
+        context.insert_component(logging(&log, "palette", Propagation::Stop));
+        let label = *gui.tree().linkdata(palette).unwrap().children().get_left().unwrap();
+        gui.build_context(label).unwrap().insert_component(logging(&log, "label", Propagation::Stop));
+        gui.root_renderer(palette).unwrap().areas = vec![(label, 0.0..10.0)];
+
+        gui.render();
+        assert_eq!(gui.renderer().rendered(), ["main"]);
+        assert_eq!(gui.root_renderer(palette).unwrap().rendered(), ["palette"]);
+        assert_eq!(gui.tree().root_of(label), Some(palette));
+
+        // only the renderer of the palette hits the label, events that hit nothing go to the root they were sent to
+        gui.handle_root_event(palette, Event::PointerMove { position: Point::new(5.0, 0.0) });
+        gui.handle_event(Event::PointerMove { position: Point::new(5.0, 0.0) });
+        gui.handle_root_event(palette, Event::PointerMove { position: Point::new(15.0, 0.0) });
+        assert_eq!(
+            log.lock().unwrap().as_slice(),
+            ["enter label", "move label", "move main", "leave label", "move palette"]
+        );
+
+        assert!(gui.remove_root(palette).is_some());
+        assert!(!gui.tree().contains(label));
+        assert_eq!(gui.roots().collect::<Vec<_>>(), [gui.tree().root()]);
+        assert!(gui.remove_root(gui.tree().root()).is_none(), "the main root can't be removed");
+    }
+}
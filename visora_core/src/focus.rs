@@ -0,0 +1,223 @@
+use crate::{runtime::Runtime, treecs::{iterators::parent::ParentIter, query::Query, EntityKey, Treecs}, BuildContext};
+
+type Listener = dyn Fn(bool, &mut BuildContext) + Send + Sync;
+
+/// component that lets an entity receive the keyboard focus
+pub struct Focusable {
+    /// entities with an order are visited first, ascending, the others follow in tree order
+    order: Option<i32>,
+    on_change: Option<Box<Listener>>,
+}
+impl Focusable {
+    pub fn new() -> Self {
+        Self {
+            order: None,
+            on_change: None,
+        }
+    }
+    pub fn with_order(mut self, order: i32) -> Self {
+        self.order = Some(order);
+        self
+    }
+    /// called with the build context of this entity when it gains (`true`) or loses (`false`) the focus
+    pub fn with_on_change<F>(mut self, on_change: F) -> Self
+    where F: Fn(bool, &mut BuildContext) + Send + Sync + 'static
+    {
+        self.on_change = Some(Box::new(on_change));
+        self
+    }
+    pub fn order(&self) -> Option<i32> {
+        self.order
+    }
+    pub(crate) fn notify(&self, focused: bool, context: &mut BuildContext) {
+        if let Some(on_change) = &self.on_change {
+            on_change(focused, context);
+        }
+    }
+}
+impl Default for Focusable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// component that keeps Tab traversal inside the subtree of an entity, like a dialog
+///
+/// the root is the outermost scope
+pub struct FocusScope;
+
+/// refers to a focusable entity, to move the focus to it from anywhere
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FocusNode {
+    key: EntityKey,
+}
+impl FocusNode {
+    pub fn new(key: EntityKey) -> Self {
+        Self { key }
+    }
+    pub fn key(&self) -> EntityKey {
+        self.key
+    }
+    /// moves the focus to this entity once the current event or transaction is dispatched
+    pub fn request_focus(&self, runtime: &Runtime) {
+        runtime.request_focus(self.key);
+    }
+    pub fn has_focus(&self, runtime: &Runtime) -> bool {
+        runtime.focused() == Some(self.key)
+    }
+}
+
+/// the closest scope that contains `entity`, the root of its tree when there is none
+pub(crate) fn scope_of(tree: &Treecs, entity: EntityKey) -> EntityKey {
+    let mut query: Query<(EntityKey, &FocusScope), ParentIter> = Query::new_parent(tree, entity);
+    query.next().map_or_else(|| tree.root_of(entity).unwrap_or(tree.root()), |(key, _)| key)
+}
+
+/// the focusable entities of `scope` in the order Tab visits them, nested scopes are skipped
+pub(crate) fn traversal_order(tree: &Treecs, scope: EntityKey) -> Vec<EntityKey> {
+    let mut found = Vec::new();
+    let mut stack = vec![scope];
+    while let Some(key) = stack.pop() {
+        if key != scope && tree.get_component::<FocusScope>(key).is_some() {
+            continue;
+        }
+        let Some(linkdata) = tree.linkdata(key) else { continue };
+        if linkdata.is_hidden() {
+            continue;
+        }
+        if let Some(focusable) = tree.get_component::<Focusable>(key) {
+            found.push((focusable.order(), key));
+        }
+        stack.extend(linkdata.children().iter().rev());
+    }
+    // the sort is stable, entities without an order keep the tree order
+    found.sort_by_key(|(order, _)| (order.is_none(), order.unwrap_or_default()));
+    found.into_iter().map(|(_, key)| key).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        event::{Event, Key, KeyState, Modifiers},
+        test_utils::{Log, TestRenderer},
+        treecs::EntityKey,
+        Gui,
+    };
+
+    use super::{FocusNode, FocusScope, Focusable};
+
+    fn tab(shift: bool) -> Event {
+        Event::Key {
+            key: Key::Tab,
+            state: KeyState::Pressed,
+            modifiers: Modifiers { shift, ..Modifiers::default() },
+        }
+    }
+    /// adds a child to `parent` for every focusable, `None` adds an entity that can't be focused
+    fn children(gui: &mut Gui<TestRenderer>, parent: EntityKey, focusables: Vec<Option<Focusable>>) -> Vec<EntityKey> {
+        let mut keys = Vec::new();
+        for focusable in focusables {
+            let key = gui.tree.add(parent).unwrap();
+            if let Some(focusable) = focusable {
+                gui.tree.register(key, focusable);
+            }
+            keys.push(key);
+        }
+        keys
+    }
+
+    #[test]
+    fn tab_follows_tree_order_and_wraps() {
+        let mut gui = Gui::new(TestRenderer::new());
+        let root = gui.tree().root();
+        let keys = children(&mut gui, root, vec![Some(Focusable::new()), None, Some(Focusable::new())]);
+        let nested = children(&mut gui, keys[1], vec![Some(Focusable::new())]);
+
+        let mut visited = Vec::new();
+        for _ in 0..4 {
+            gui.handle_event(tab(false));
+            visited.push(gui.focused().unwrap());
+        }
+        assert_eq!(visited, vec![keys[0], nested[0], keys[2], keys[0]]);
+
+        gui.handle_event(tab(true));
+        assert_eq!(gui.focused(), Some(keys[2]));
+    }
+    #[test]
+    fn explicit_order_comes_first() {
+        let mut gui = Gui::new(TestRenderer::new());
+        let root = gui.tree().root();
+        let keys = children(&mut gui, root, vec![
+            Some(Focusable::new()),
+            Some(Focusable::new().with_order(2)),
+            Some(Focusable::new().with_order(1)),
+        ]);
+
+        let mut visited = Vec::new();
+        for _ in 0..3 {
+            gui.handle_event(tab(false));
+            visited.push(gui.focused().unwrap());
+        }
+        assert_eq!(visited, vec![keys[2], keys[1], keys[0]]);
+    }
+    #[test]
+    fn traversal_stays_in_scope() {
+        let mut gui = Gui::new(TestRenderer::new());
+        let root = gui.tree().root();
+        let keys = children(&mut gui, root, vec![Some(Focusable::new()), None]);
+        gui.tree.register(keys[1], FocusScope);
+        let scoped = children(&mut gui, keys[1], vec![Some(Focusable::new()), Some(Focusable::new())]);
+
+        gui.handle_event(tab(false));
+        assert_eq!(gui.focused(), Some(keys[0]), "the outer traversal skips the nested scope");
+
+        FocusNode::new(scoped[0]).request_focus(gui.runtime());
+        gui.dispatch_pending();
+        gui.handle_event(tab(false));
+        gui.handle_event(tab(false));
+        assert_eq!(gui.focused(), Some(scoped[0]));
+    }
+    #[test]
+    fn focus_changes_are_notified() {
+        let mut gui = Gui::new(TestRenderer::new());
+        let root = gui.tree().root();
+        let log = Log::default();
+        let listener = |name: &'static str| {
+            let log = log.clone();
+            Focusable::new().with_on_change(move |focused, _| log.lock().unwrap().push(format!("{name} {focused}")))
+        };
+        let keys = children(&mut gui, root, vec![Some(listener("a")), Some(listener("b"))]);
+
+        FocusNode::new(keys[0]).request_focus(gui.runtime());
+        assert_eq!(gui.focused(), None, "requests are applied when dispatching");
+        gui.dispatch_pending();
+        assert!(FocusNode::new(keys[0]).has_focus(gui.runtime()));
+        gui.handle_event(tab(false));
+        assert_eq!(log.lock().unwrap().as_slice(), ["a true", "a false", "b true"]);
+
+        gui.remove(keys[1]);
+        assert_eq!(gui.focused(), None);
+    }
+    #[test]
+    fn keys_go_to_the_focused_entity() {
+        let mut gui = Gui::new(TestRenderer::new());
+        let root = gui.tree().root();
+        let keys = children(&mut gui, root, vec![Some(Focusable::new())]);
+        let log = Log::default();
+        let handler_log = log.clone();
+        gui.build_context(keys[0]).unwrap().insert_component(crate::event::EventHandler::new(move |event, _| {
+            if let Event::TextInput(x) = event {
+                handler_log.lock().unwrap().push(x.clone());
+            }
+            crate::event::Propagation::Stop
+        }));
+
+        gui.handle_event(Event::TextInput("ignored".to_owned()));
+        FocusNode::new(keys[0]).request_focus(gui.runtime());
+        gui.dispatch_pending();
+        gui.handle_event(Event::TextInput("typed".to_owned()));
+        gui.handle_event(tab(false));
+        assert_eq!(log.lock().unwrap().as_slice(), ["typed"]);
+        assert_eq!(gui.focused(), Some(keys[0]), "a handler that stops tab keeps the focus");
+    }
+}
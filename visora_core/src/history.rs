@@ -0,0 +1,266 @@
+use std::{collections::VecDeque, time::Duration};
+
+use crate::{runtime::Runtime, treecs::{EntityKey, Treecs}, BuildContext, Component};
+
+/// puts a copy of a state back on the entity it was taken from
+pub(crate) type Restore = Box<dyn Fn(&BuildContext) + Send + Sync>;
+/// copies the state of the entity of the context, see [`crate::state::StateHandle::update_undoable`]
+pub(crate) type Snapshot = fn(&BuildContext) -> Option<Restore>;
+
+pub(crate) fn snapshot<S: Component + Clone>(context: &BuildContext) -> Option<Restore> {
+    let state = context.get_component::<S>()?.clone();
+    Some(Box::new(move |context| {
+        if let Some(current) = context.get_component_mut::<S>() {
+            *current = state.clone();
+        }
+    }))
+}
+
+/// a recorded transaction, with the state of its target before and after it
+struct Change {
+    target: EntityKey,
+    snapshot: Snapshot,
+    before: Restore,
+    /// taken when the change is undone
+    after: Option<Restore>,
+}
+
+/// changes that are undone and redone together
+struct Group {
+    changes: Vec<Change>,
+    /// when the last change was recorded, for the merge window
+    recorded: Duration,
+}
+
+/// the undo and redo stacks of a gui, see [`crate::Gui::with_history`]
+///
+/// only transactions created with `update_undoable` are recorded, the state of their target is copied before
+/// they are applied
+pub struct History {
+    undo: VecDeque<Group>,
+    redo: Vec<Group>,
+    max_depth: usize,
+    merge_window: Option<Duration>,
+    /// how many explicit groups are open, while one is open every change goes into the last group
+    open: usize,
+    /// a group was opened but nothing was recorded in it yet
+    fresh: bool,
+}
+impl Default for History {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl History {
+    /// keeps the last 100 groups and doesn't merge changes
+    pub fn new() -> Self {
+        Self {
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            max_depth: 100,
+            merge_window: None,
+            open: 0,
+            fresh: false,
+        }
+    }
+    /// the amount of groups that can be undone, older ones are forgotten
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+    /// changes to the same entity recorded within `window` of each other are undone together, like typed characters
+    pub fn with_merge_window(mut self, window: Duration) -> Self {
+        self.merge_window = Some(window);
+        self
+    }
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+    /// the amount of groups that can be undone
+    pub fn len(&self) -> usize {
+        self.undo.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.undo.is_empty()
+    }
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+    pub(crate) fn begin_group(&mut self) {
+        self.open += 1;
+        self.fresh |= self.open == 1;
+    }
+    pub(crate) fn end_group(&mut self) {
+        self.open = self.open.saturating_sub(1);
+    }
+    pub(crate) fn record(&mut self, target: EntityKey, snapshot: Snapshot, before: Restore, now: Duration) {
+        self.redo.clear();
+        let change = Change { target, snapshot, before, after: None };
+        let merge = match self.undo.back() {
+            Some(_) if self.open > 0 => !std::mem::take(&mut self.fresh),
+            Some(last) => self.merge_window.is_some_and(|window| {
+                now.saturating_sub(last.recorded) <= window && last.changes.iter().all(|x| x.target == target)
+            }),
+            None => false,
+        };
+        self.fresh = false;
+        match self.undo.back_mut() {
+            Some(last) if merge => {
+                last.changes.push(change);
+                last.recorded = now;
+            }
+            _ => {
+                self.undo.push_back(Group { changes: vec![change], recorded: now });
+                if self.undo.len() > self.max_depth {
+                    self.undo.pop_front();
+                }
+            }
+        }
+    }
+    /// restores the states before the last group, returns the entities whose state changed
+    pub(crate) fn undo(&mut self, tree: &Treecs, runtime: &Runtime) -> Option<Vec<EntityKey>> {
+        let mut group = self.undo.pop_back()?;
+        let mut targets = Vec::new();
+        for change in group.changes.iter_mut().rev().filter(|x| tree.contains(x.target)) {
+            let context = BuildContext { tree, runtime, key: change.target };
+            change.after = (change.snapshot)(&context);
+            (change.before)(&context);
+            targets.push(change.target);
+        }
+        self.redo.push(group);
+        Some(targets)
+    }
+    /// restores the states after the last undone group, returns the entities whose state changed
+    pub(crate) fn redo(&mut self, tree: &Treecs, runtime: &Runtime) -> Option<Vec<EntityKey>> {
+        let group = self.redo.pop()?;
+        let mut targets = Vec::new();
+        for change in group.changes.iter().filter(|x| tree.contains(x.target)) {
+            let Some(after) = &change.after else { continue };
+            after(&BuildContext { tree, runtime, key: change.target });
+            targets.push(change.target);
+        }
+        self.undo.push_back(group);
+        Some(targets)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{
+        animation::clock::ManualClock,
+        error::Catcher,
+        state::{State, StateHandle},
+        test_utils::{Dynamic, TestRenderer},
+        widget::{RenderAble, Widget},
+        BuildContext, Gui, WidgetContext,
+    };
+
+    use super::History;
+
+    #[derive(Clone)]
+    struct Counter;
+    impl RenderAble<TestRenderer> for Counter {
+        fn mount<'gui>(&self, context: WidgetContext<'gui, TestRenderer>) -> WidgetContext<'gui, TestRenderer> {
+            context.mount_widget(self)
+        }
+    }
+    impl Widget<TestRenderer> for Counter {
+        type State = u32;
+        fn create_state(&self) -> Self::State {
+            0
+        }
+        fn build<'gui>(&self, state: State<Self>, _context: &mut BuildContext<'gui>) -> impl RenderAble<TestRenderer> + 'static {
+            Dynamic { text: state.read::<TestRenderer>().to_string() }
+        }
+    }
+
+    fn counter(history: History) -> (Gui<TestRenderer>, StateHandle<Counter>, ManualClock) {
+        let clock = ManualClock::new();
+        let mut gui = Gui::new(TestRenderer::new()).with_clock(clock.clone()).with_history(history);
+        gui.root_widget_context().mount_child(&Counter);
+        gui.render();
+        let key = *gui.tree().linkdata(gui.tree().root()).unwrap().children().get_left().unwrap();
+        (gui, StateHandle::new(key), clock)
+    }
+    fn add(gui: &mut Gui<TestRenderer>, counter: StateHandle<Counter>, amount: u32) {
+        gui.dispatch(&counter.update_undoable::<_, TestRenderer>(move |_, x| *x += amount));
+        gui.render();
+    }
+    fn rendered(gui: &mut Gui<TestRenderer>) -> String {
+        gui.render();
+        gui.renderer().rendered().concat()
+    }
+
+    #[test]
+    fn undo_and_redo_restore_the_state() {
+        let (mut gui, counter, _) = counter(History::new());
+        add(&mut gui, counter, 1);
+        add(&mut gui, counter, 2);
+        // transactions that are not undoable are not recorded
+        gui.dispatch(&counter.update::<_, TestRenderer>(|_, x| *x += 10));
+        assert_eq!(rendered(&mut gui), "13");
+
+        assert!(gui.undo());
+        assert_eq!(rendered(&mut gui), "1");
+        assert!(gui.undo());
+        assert_eq!(rendered(&mut gui), "0");
+        assert!(!gui.undo());
+        assert!(gui.redo());
+        assert_eq!(rendered(&mut gui), "1");
+
+        // a new change forgets what was undone
+        add(&mut gui, counter, 5);
+        assert!(!gui.redo());
+        assert_eq!(rendered(&mut gui), "6");
+    }
+    #[test]
+    fn changes_are_grouped() {
+        let (mut gui, counter, clock) = counter(History::new().with_merge_window(Duration::from_millis(500)));
+        add(&mut gui, counter, 1);
+        clock.advance(Duration::from_millis(400));
+        add(&mut gui, counter, 1);
+        clock.advance(Duration::from_secs(1));
+        gui.begin_undo_group();
+        add(&mut gui, counter, 10);
+        clock.advance(Duration::from_secs(1));
+        add(&mut gui, counter, 10);
+        gui.end_undo_group();
+        assert_eq!(gui.history().unwrap().len(), 2);
+
+        assert!(gui.undo());
+        assert_eq!(rendered(&mut gui), "2");
+        assert!(gui.undo());
+        assert_eq!(rendered(&mut gui), "0");
+        assert!(gui.redo());
+        assert!(gui.redo());
+        assert_eq!(rendered(&mut gui), "22");
+    }
+    #[test]
+    fn history_is_bounded() {
+        let (mut gui, counter, _) = counter(History::new().with_max_depth(2));
+        for _ in 0..4 {
+            add(&mut gui, counter, 1);
+        }
+        assert!(gui.undo());
+        assert!(gui.undo());
+        assert!(!gui.undo());
+        assert_eq!(rendered(&mut gui), "2");
+        gui.remove(counter.key());
+        // the changes of removed entities are skipped
+        assert!(gui.redo());
+    }
+    #[test]
+    fn panicked_transactions_are_not_recorded() {
+        let (mut gui, counter, _) = counter(History::new());
+        gui.root_widget_context().insert_component(Catcher::new(|_, _| {}));
+        add(&mut gui, counter, 1);
+        gui.dispatch(&counter.update_undoable::<_, TestRenderer>(|_, _| panic!("the change failed")));
+        assert_eq!(gui.history().unwrap().len(), 1);
+    }
+}
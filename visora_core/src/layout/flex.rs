@@ -0,0 +1,239 @@
+//! rows and columns that share their space between the children, like the flex layout of flutter and css
+
+use super::{Axis, BoxConstraints, Layout, LayoutChildren, Size};
+
+/// where the children are put along the axis of a [`Flex`] when they don't fill it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MainAxisAlignment {
+    #[default]
+    Start,
+    End,
+    Center,
+    /// the free space goes between the children
+    SpaceBetween,
+    /// every child gets the same free space on both sides
+    SpaceAround,
+    /// the gaps before, between and after the children are the same
+    SpaceEvenly,
+}
+/// where the children are put across the axis of a [`Flex`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CrossAxisAlignment {
+    #[default]
+    Start,
+    End,
+    Center,
+    /// the children are as thick as the flex may get
+    Stretch,
+}
+/// how long a [`Flex`] is along its axis
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MainAxisSize {
+    /// as long as the children
+    Min,
+    /// as long as the constraints allow
+    #[default]
+    Max,
+}
+/// how a child of a [`Flex`] shares the free space with its siblings, it is stored on the child
+///
+/// the child is as long as its share, its own layout decides whether its content fills it, like a
+/// [`super::Proxy`] that loosens the constraints
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FlexChild {
+    /// the child gets `flex` parts of the free space, out of the flex of all children together
+    pub flex: u32,
+}
+
+/// places the children along `axis`, the children with a [`FlexChild`] share what the others left
+///
+/// the children without a flex get unbounded constraints along the axis. in an unbounded flex every child is
+/// laid out like that
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Flex {
+    pub axis: Axis,
+    pub main_alignment: MainAxisAlignment,
+    pub cross_alignment: CrossAxisAlignment,
+    pub main_size: MainAxisSize,
+    /// the gap between two children
+    pub spacing: f32,
+}
+impl Flex {
+    pub fn new(axis: Axis) -> Self {
+        Self {
+            axis,
+            main_alignment: MainAxisAlignment::default(),
+            cross_alignment: CrossAxisAlignment::default(),
+            main_size: MainAxisSize::default(),
+            spacing: 0.0,
+        }
+    }
+    /// the constraints of a child that is `min` to `max` long along the axis
+    fn child_constraints(&self, constraints: &BoxConstraints, min: f32, max: f32) -> BoxConstraints {
+        let max_cross = self.axis.cross(constraints.biggest());
+        let min_cross = match self.cross_alignment {
+            CrossAxisAlignment::Stretch if max_cross.is_finite() => max_cross,
+            _ => 0.0,
+        };
+        match self.axis {
+            Axis::Horizontal => BoxConstraints::new(min, max, min_cross, max_cross),
+            Axis::Vertical => BoxConstraints::new(min_cross, max_cross, min, max),
+        }
+    }
+}
+impl Layout for Flex {
+    fn layout(&self, constraints: BoxConstraints, children: &mut LayoutChildren) -> Size {
+        let count = children.len();
+        let max_main = self.axis.main(constraints.biggest());
+        let flexes: Vec<Option<FlexChild>> = (0..count)
+            .map(|i| children.get::<FlexChild>(i).copied().filter(|_| max_main.is_finite()))
+            .collect();
+        let total_flex: u32 = flexes.iter().flatten().map(|x| x.flex).sum();
+
+        let mut sizes = vec![Size::default(); count];
+        let mut allocated = self.spacing * count.saturating_sub(1) as f32;
+        for (i, flex) in flexes.iter().enumerate() {
+            if flex.is_none_or(|x| x.flex == 0) {
+                sizes[i] = children.layout(i, self.child_constraints(&constraints, 0.0, f32::INFINITY));
+                allocated += self.axis.main(sizes[i]);
+            }
+        }
+        if total_flex > 0 {
+            let unit = (max_main - allocated).max(0.0) / total_flex as f32;
+            for (i, flex) in flexes.iter().enumerate() {
+                let Some(flex) = flex.filter(|x| x.flex > 0) else { continue };
+                let share = unit * flex.flex as f32;
+                sizes[i] = children.layout(i, self.child_constraints(&constraints, share, share));
+                allocated += self.axis.main(sizes[i]);
+            }
+        }
+
+        let main = match self.main_size {
+            MainAxisSize::Max if max_main.is_finite() => max_main,
+            _ => allocated,
+        };
+        let max_cross = self.axis.cross(constraints.biggest());
+        let cross = match self.cross_alignment {
+            CrossAxisAlignment::Stretch if max_cross.is_finite() => max_cross,
+            _ => sizes.iter().fold(0.0, |a, x| self.axis.cross(*x).max(a)),
+        };
+        let size = constraints.constrain(self.axis.size(main, cross));
+        let (main, cross) = (self.axis.main(size), self.axis.cross(size));
+
+        let free = (main - allocated).max(0.0);
+        let (leading, between) = match self.main_alignment {
+            MainAxisAlignment::Start => (0.0, 0.0),
+            MainAxisAlignment::End => (free, 0.0),
+            MainAxisAlignment::Center => (free / 2.0, 0.0),
+            MainAxisAlignment::SpaceBetween if count > 1 => (0.0, free / (count - 1) as f32),
+            MainAxisAlignment::SpaceBetween => (0.0, 0.0),
+            MainAxisAlignment::SpaceAround if count > 0 => (free / count as f32 / 2.0, free / count as f32),
+            MainAxisAlignment::SpaceEvenly => (free / (count + 1) as f32, free / (count + 1) as f32),
+            MainAxisAlignment::SpaceAround => (0.0, 0.0),
+        };
+        let mut position = leading;
+        for (i, child) in sizes.into_iter().enumerate() {
+            let offset = match self.cross_alignment {
+                CrossAxisAlignment::Start | CrossAxisAlignment::Stretch => 0.0,
+                CrossAxisAlignment::End => cross - self.axis.cross(child),
+                CrossAxisAlignment::Center => (cross - self.axis.cross(child)) / 2.0,
+            };
+            match self.axis {
+                Axis::Horizontal => children.place(i, position, offset),
+                Axis::Vertical => children.place(i, offset, position),
+            }
+            position += self.axis.main(child) + self.spacing + between;
+        }
+        size
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        layout::{tests::{child, rect}, Axis, LayoutRect, Padded, Proxy, Size},
+        test_utils::TestRenderer,
+        treecs::EntityKey,
+        Gui,
+    };
+
+    use super::{CrossAxisAlignment, Flex, FlexChild, MainAxisAlignment, MainAxisSize};
+
+    fn gui() -> Gui<TestRenderer> {
+        let mut renderer = TestRenderer::new();
+        renderer.viewport = Some(Size::new(200.0, 100.0));
+        Gui::new(renderer)
+    }
+    fn fixed(gui: &mut Gui<TestRenderer>, parent: EntityKey, width: f32, height: f32) -> EntityKey {
+        child(gui, parent, Some(Box::new(Padded { width: Some(width), height: Some(height), ..Default::default() })))
+    }
+    fn flexible(gui: &mut Gui<TestRenderer>, parent: EntityKey, flex: u32, loosen: bool) -> EntityKey {
+        let key = child(gui, parent, Some(Box::new(Proxy { loosen })));
+        gui.tree.register(key, FlexChild { flex });
+        key
+    }
+
+    #[test]
+    fn flexible_children_share_the_free_space() {
+        let mut gui = gui();
+        let root = gui.tree().root();
+        let row = child(&mut gui, root, Some(Box::new(Flex { spacing: 10.0, ..Flex::new(Axis::Horizontal) })));
+        let a = fixed(&mut gui, row, 30.0, 20.0);
+        let one = flexible(&mut gui, row, 1, false);
+        let two = flexible(&mut gui, row, 2, false);
+        let loose = flexible(&mut gui, row, 1, true);
+        let inner = fixed(&mut gui, loose, 5.0, 5.0);
+        gui.render();
+
+        // 200 - 30 - 3 gaps of 10 leaves 140 for 4 flex parts
+        assert_eq!(rect(&gui, row), LayoutRect::new(0.0, 0.0, 200.0, 20.0));
+        assert_eq!(rect(&gui, a), LayoutRect::new(0.0, 0.0, 30.0, 20.0));
+        assert_eq!(rect(&gui, one), LayoutRect::new(40.0, 0.0, 35.0, 0.0));
+        assert_eq!(rect(&gui, two), LayoutRect::new(85.0, 0.0, 70.0, 0.0));
+        // the content of a loosened child can be smaller than the share
+        assert_eq!(rect(&gui, loose), LayoutRect::new(165.0, 0.0, 35.0, 5.0));
+        assert_eq!(rect(&gui, inner), LayoutRect::new(165.0, 0.0, 5.0, 5.0));
+    }
+    #[test]
+    fn children_are_aligned() {
+        let mut gui = gui();
+        let root = gui.tree().root();
+        let column = child(&mut gui, root, Some(Box::new(Flex {
+            main_alignment: MainAxisAlignment::SpaceBetween,
+            cross_alignment: CrossAxisAlignment::Center,
+            ..Flex::new(Axis::Vertical)
+        })));
+        let a = fixed(&mut gui, column, 40.0, 20.0);
+        let b = fixed(&mut gui, column, 20.0, 20.0);
+        let c = fixed(&mut gui, column, 10.0, 20.0);
+        gui.render();
+
+        assert_eq!(rect(&gui, column), LayoutRect::new(0.0, 0.0, 40.0, 100.0));
+        assert_eq!(rect(&gui, a), LayoutRect::new(0.0, 0.0, 40.0, 20.0));
+        assert_eq!(rect(&gui, b), LayoutRect::new(10.0, 40.0, 20.0, 20.0));
+        assert_eq!(rect(&gui, c), LayoutRect::new(15.0, 80.0, 10.0, 20.0));
+    }
+    #[test]
+    fn min_rows_wrap_their_children_and_stretch() {
+        let mut gui = gui();
+        let root = gui.tree().root();
+        let row = child(&mut gui, root, Some(Box::new(Flex {
+            main_alignment: MainAxisAlignment::End,
+            cross_alignment: CrossAxisAlignment::Stretch,
+            main_size: MainAxisSize::Min,
+            ..Flex::new(Axis::Horizontal)
+        })));
+        let a = fixed(&mut gui, row, 40.0, 20.0);
+        let spacer = flexible(&mut gui, row, 1, false);
+        gui.render();
+
+        // the spacer takes all the space, so the row is as long as the constraints allow
+        assert_eq!(rect(&gui, row), LayoutRect::new(0.0, 0.0, 200.0, 100.0));
+        assert_eq!(rect(&gui, a), LayoutRect::new(0.0, 0.0, 40.0, 100.0));
+        assert_eq!(rect(&gui, spacer), LayoutRect::new(40.0, 0.0, 160.0, 100.0));
+
+        gui.tree.remove(spacer);
+        gui.render();
+        assert_eq!(rect(&gui, row), LayoutRect::new(0.0, 0.0, 40.0, 100.0));
+    }
+}
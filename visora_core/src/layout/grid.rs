@@ -0,0 +1,335 @@
+//! rows and columns of tracks that the children are put in, like the grid of css
+
+use std::collections::HashSet;
+
+use super::{BoxConstraints, Layout, LayoutChildren, Size};
+
+/// how long a row or a column of a [`Grid`] is
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Track {
+    Fixed(f32),
+    /// a share of the space the other tracks left, like `fr` in css. the track is sized like an auto track when
+    /// the grid is unbounded along it
+    Fraction(f32),
+    /// as long as the children in it
+    Auto,
+}
+
+/// where a child of a [`Grid`] is put in its area along one axis
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CellAlignment {
+    Start,
+    End,
+    Center,
+    /// the child is as big as its area
+    #[default]
+    Stretch,
+}
+impl CellAlignment {
+    /// the offset of a child that is `child` long in an area that is `area` long
+    fn offset(&self, area: f32, child: f32) -> f32 {
+        match self {
+            Self::Start | Self::Stretch => 0.0,
+            Self::End => area - child,
+            Self::Center => (area - child) / 2.0,
+        }
+    }
+    /// the constraints along the axis of a child in an area that is `area` long
+    fn range(&self, area: f32) -> (f32, f32) {
+        match self {
+            Self::Stretch => (area, area),
+            _ => (0.0, area),
+        }
+    }
+}
+
+/// the cells a child of a [`Grid`] covers, from the cell at `row` and `column` on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GridArea {
+    pub row: usize,
+    pub column: usize,
+    pub row_span: usize,
+    pub column_span: usize,
+}
+impl GridArea {
+    pub fn new(row: usize, column: usize) -> Self {
+        Self { row, column, row_span: 1, column_span: 1 }
+    }
+    fn cells(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (self.row..self.row + self.row_span).flat_map(move |row| (self.column..self.column + self.column_span).map(move |column| (row, column)))
+    }
+}
+
+/// where a child asks to be put, see [`auto_place`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Placement {
+    At(GridArea),
+    /// the next free cells that fit the spans
+    Auto { row_span: usize, column_span: usize },
+}
+
+/// the areas of the children of a grid of `columns` columns, in the order of `placements`
+///
+/// the children that are put at an area keep it, the others flow into the free cells row by row, like the auto
+/// placement of css. a child never goes back before the one that was placed before it
+pub fn auto_place(columns: usize, placements: &[Placement]) -> Vec<GridArea> {
+    let columns = placements.iter()
+        .map(|x| match x {
+            Placement::At(area) => area.column + area.column_span.max(1),
+            Placement::Auto { column_span, .. } => *column_span,
+        })
+        .fold(columns.max(1), usize::max);
+    let mut taken: HashSet<(usize, usize)> = placements.iter()
+        .filter_map(|x| match x {
+            Placement::At(area) => Some(area.cells().collect::<Vec<_>>()),
+            Placement::Auto { .. } => None,
+        })
+        .flatten()
+        .collect();
+    let (mut row, mut column) = (0, 0);
+    placements.iter()
+        .map(|x| match *x {
+            Placement::At(area) => GridArea { row_span: area.row_span.max(1), column_span: area.column_span.max(1), ..area },
+            Placement::Auto { row_span, column_span } => {
+                let (row_span, column_span) = (row_span.max(1), column_span.max(1));
+                loop {
+                    if column + column_span > columns {
+                        (row, column) = (row + 1, 0);
+                        continue;
+                    }
+                    let area = GridArea { row, column, row_span, column_span };
+                    if area.cells().any(|x| taken.contains(&x)) {
+                        column += 1;
+                        continue;
+                    }
+                    taken.extend(area.cells());
+                    column += column_span;
+                    return area;
+                }
+            }
+        })
+        .collect()
+}
+
+/// component on a child of a [`Grid`], the area it is put in and how it is aligned there when it isn't aligned
+/// like the grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GridChild {
+    pub area: GridArea,
+    pub horizontal: Option<CellAlignment>,
+    pub vertical: Option<CellAlignment>,
+}
+
+/// puts the children with a [`GridChild`] in the cells of its `columns` and `rows`
+///
+/// the tracks past the ones given are auto tracks. the auto tracks are sized first, by the children in one track and
+/// then by the children that span more, which share what they miss between the auto tracks they span. children
+/// in a fraction track don't size auto tracks, the fraction tracks share what is left. a grid with fraction tracks
+/// is as big as the constraints allow along them, otherwise it is as big as its tracks
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Grid {
+    pub columns: Vec<Track>,
+    pub rows: Vec<Track>,
+    pub column_gap: f32,
+    pub row_gap: f32,
+    pub horizontal_alignment: CellAlignment,
+    pub vertical_alignment: CellAlignment,
+}
+impl Grid {
+    pub fn new(columns: Vec<Track>) -> Self {
+        Self { columns, ..Self::default() }
+    }
+}
+
+/// the lengths of `count` tracks of which the first are `template`, in `available` with `gap` between two tracks
+///
+/// `items` are the start, the span and the length of the children along the axis
+pub fn track_sizes(template: &[Track], count: usize, available: f32, gap: f32, items: &[(usize, usize, f32)]) -> Vec<f32> {
+    let tracks: Vec<Track> = (0..count).map(|i| template.get(i).copied().unwrap_or(Track::Auto)).collect();
+    let flexible = |track: &Track| matches!(track, Track::Fraction(_)) && available.is_finite();
+    let mut sizes: Vec<f32> = tracks.iter()
+        .map(|x| match x {
+            Track::Fixed(x) => *x,
+            _ => 0.0,
+        })
+        .collect();
+    let mut items = items.to_vec();
+    items.sort_by_key(|(_, span, _)| *span);
+    for (start, span, length) in items {
+        let spanned = start..start + span;
+        if tracks[spanned.clone()].iter().any(flexible) {
+            continue;
+        }
+        let intrinsic: Vec<usize> = spanned.clone().filter(|i| !matches!(tracks[*i], Track::Fixed(_))).collect();
+        let current = sizes[spanned].iter().sum::<f32>() + gap * span.saturating_sub(1) as f32;
+        if intrinsic.is_empty() || length <= current {
+            continue;
+        }
+        let share = (length - current) / intrinsic.len() as f32;
+        for i in intrinsic {
+            sizes[i] += share;
+        }
+    }
+    let fractions: f32 = tracks.iter().filter(|x| flexible(x)).map(|x| match x {
+        Track::Fraction(x) => *x,
+        _ => 0.0,
+    }).sum();
+    if fractions > 0.0 {
+        let used = tracks.iter().zip(&sizes).filter(|(x, _)| !flexible(x)).map(|(_, x)| x).sum::<f32>()
+            + gap * count.saturating_sub(1) as f32;
+        // like css, fractions that add up to less than one leave space free
+        let unit = (available - used).max(0.0) / fractions.max(1.0);
+        for (track, size) in tracks.iter().zip(&mut sizes) {
+            if let Track::Fraction(x) = track {
+                *size = x * unit;
+            }
+        }
+    }
+    sizes
+}
+
+/// where every track starts and how long the span from `start` is
+struct Tracks {
+    starts: Vec<f32>,
+    sizes: Vec<f32>,
+    gap: f32,
+}
+impl Tracks {
+    fn new(sizes: Vec<f32>, gap: f32) -> Self {
+        let mut position = 0.0;
+        let starts = sizes.iter().map(|x| {
+            let start = position;
+            position += x + gap;
+            start
+        }).collect();
+        Self { starts, sizes, gap }
+    }
+    fn span(&self, start: usize, span: usize) -> f32 {
+        self.sizes[start..start + span].iter().sum::<f32>() + self.gap * span.saturating_sub(1) as f32
+    }
+    fn length(&self) -> f32 {
+        self.span(0, self.sizes.len())
+    }
+}
+
+impl Layout for Grid {
+    fn layout(&self, constraints: BoxConstraints, children: &mut LayoutChildren) -> Size {
+        let items: Vec<(usize, GridChild)> = (0..children.len())
+            .filter_map(|i| children.get::<GridChild>(i).map(|x| (i, *x)))
+            .collect();
+        let column_count = items.iter().map(|(_, x)| x.area.column + x.area.column_span).fold(self.columns.len(), usize::max);
+        let row_count = items.iter().map(|(_, x)| x.area.row + x.area.row_span).fold(self.rows.len(), usize::max);
+        let horizontal = |x: &GridChild| x.horizontal.unwrap_or(self.horizontal_alignment);
+        let vertical = |x: &GridChild| x.vertical.unwrap_or(self.vertical_alignment);
+
+        // the columns are sized by the widths of the children, the rows by their heights in their columns
+        let widths: Vec<(usize, usize, f32)> = items.iter()
+            .map(|(i, x)| (x.area.column, x.area.column_span, children.layout(*i, BoxConstraints::unbounded()).width))
+            .collect();
+        let columns = Tracks::new(track_sizes(&self.columns, column_count, constraints.max_width, self.column_gap, &widths), self.column_gap);
+        let heights: Vec<(usize, usize, f32)> = items.iter()
+            .map(|(i, x)| {
+                let (min, max) = horizontal(x).range(columns.span(x.area.column, x.area.column_span));
+                (x.area.row, x.area.row_span, children.layout(*i, BoxConstraints::new(min, max, 0.0, f32::INFINITY)).height)
+            })
+            .collect();
+        let rows = Tracks::new(track_sizes(&self.rows, row_count, constraints.max_height, self.row_gap, &heights), self.row_gap);
+
+        let fills = |tracks: &[Track], max: f32| max.is_finite() && tracks.iter().any(|x| matches!(x, Track::Fraction(_)));
+        let size = constraints.constrain(Size::new(
+            if fills(&self.columns, constraints.max_width) { constraints.max_width } else { columns.length() },
+            if fills(&self.rows, constraints.max_height) { constraints.max_height } else { rows.length() },
+        ));
+        for (i, x) in &items {
+            let area = Size::new(columns.span(x.area.column, x.area.column_span), rows.span(x.area.row, x.area.row_span));
+            let (horizontal, vertical) = (horizontal(x), vertical(x));
+            let (min_width, max_width) = horizontal.range(area.width);
+            let (min_height, max_height) = vertical.range(area.height);
+            let child = children.layout(*i, BoxConstraints::new(min_width, max_width, min_height, max_height));
+            children.place(
+                *i,
+                columns.starts[x.area.column] + horizontal.offset(area.width, child.width),
+                rows.starts[x.area.row] + vertical.offset(area.height, child.height),
+            );
+        }
+        size
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        layout::{tests::{child, rect}, LayoutRect, Padded, Size},
+        test_utils::TestRenderer,
+        Gui,
+    };
+
+    use super::{auto_place, track_sizes, CellAlignment, Grid, GridArea, GridChild, Placement, Track};
+
+    #[test]
+    fn children_flow_around_the_placed_ones() {
+        let auto = Placement::Auto { row_span: 1, column_span: 1 };
+        let areas = auto_place(3, &[
+            Placement::At(GridArea::new(0, 1)),
+            auto,
+            Placement::Auto { row_span: 2, column_span: 2 },
+            auto,
+            auto,
+            Placement::Auto { row_span: 1, column_span: 3 },
+        ]);
+        assert_eq!(areas, vec![
+            GridArea::new(0, 1),
+            GridArea::new(0, 0),
+            GridArea { row: 1, column: 0, row_span: 2, column_span: 2 },
+            GridArea::new(1, 2),
+            GridArea::new(2, 2),
+            GridArea { row: 3, column: 0, row_span: 1, column_span: 3 },
+        ]);
+    }
+
+    #[test]
+    fn tracks_share_what_is_left() {
+        let template = [Track::Fixed(50.0), Track::Auto, Track::Fraction(1.0), Track::Fraction(3.0)];
+        // the second child spans the fixed and the auto track, the last one is in a fraction track
+        let items = [(1, 1, 30.0), (0, 2, 100.0), (2, 1, 500.0)];
+        assert_eq!(track_sizes(&template, 5, 300.0, 10.0, &items), vec![50.0, 40.0, 42.5, 127.5, 0.0]);
+        // unbounded, the fractions are sized like auto tracks
+        assert_eq!(track_sizes(&template, 4, f32::INFINITY, 10.0, &items), vec![50.0, 40.0, 500.0, 0.0]);
+    }
+
+    #[test]
+    fn children_are_aligned_in_their_area() {
+        let mut renderer = TestRenderer::new();
+        renderer.viewport = Some(Size::new(200.0, 100.0));
+        let mut gui = Gui::new(renderer);
+        let root = gui.tree().root();
+        let layout = Grid {
+            columns: vec![Track::Fixed(40.0), Track::Fraction(1.0)],
+            rows: vec![Track::Auto, Track::Fraction(1.0)],
+            column_gap: 10.0,
+            row_gap: 10.0,
+            ..Grid::default()
+        };
+        let grid = child(&mut gui, root, Some(Box::new(layout)));
+        let items: Vec<_> = [
+            (GridArea::new(0, 0), None, None),
+            (GridArea::new(0, 1), Some(CellAlignment::End), Some(CellAlignment::Center)),
+            (GridArea { row: 1, column: 0, row_span: 1, column_span: 2 }, Some(CellAlignment::Center), None),
+        ]
+        .into_iter()
+        .map(|(area, horizontal, vertical)| {
+            let padded = Padded { width: Some(20.0), height: Some(20.0), ..Default::default() };
+            let item = child(&mut gui, grid, Some(Box::new(padded)));
+            gui.tree.register(item, GridChild { area, horizontal, vertical });
+            item
+        })
+        .collect();
+        gui.render();
+
+        // the root loosens the constraints, the grid fills them because of its fraction tracks
+        assert_eq!(rect(&gui, grid), LayoutRect::new(0.0, 0.0, 200.0, 100.0));
+        assert_eq!(rect(&gui, items[0]), LayoutRect::new(0.0, 0.0, 40.0, 20.0));
+        assert_eq!(rect(&gui, items[1]), LayoutRect::new(180.0, 0.0, 20.0, 20.0));
+        assert_eq!(rect(&gui, items[2]), LayoutRect::new(90.0, 30.0, 20.0, 70.0));
+    }
+}
@@ -0,0 +1,122 @@
+//! long lists of items that are all as long, so the items in view are known without laying out the others
+
+use std::ops::Range;
+
+use super::{Axis, BoxConstraints, Layout, LayoutChildren, Size};
+
+/// component on a child of a [`Lazy`], the index of the item it shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LazyItem {
+    pub index: usize,
+}
+
+/// as long as `count` items of `extent` along `axis`, the children with a [`LazyItem`] are put where their item is
+///
+/// only the items a list shows have to be children. they are as long as `extent` and as thick as the list, which
+/// is as thick as the constraints allow, or as its thickest child when they are unbounded
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Lazy {
+    pub axis: Axis,
+    pub extent: f32,
+    pub count: usize,
+}
+impl Lazy {
+    /// how long all the items are together
+    pub fn length(&self) -> f32 {
+        self.count as f32 * self.extent
+    }
+    /// the items that are at most `cache` away from the `viewport` long window at `offset`, the offset is clamped
+    /// to the list like a scroll view does
+    pub fn items_in_view(&self, offset: f32, viewport: f32, cache: f32) -> Range<usize> {
+        if self.extent <= 0.0 {
+            return 0..0;
+        }
+        let offset = offset.clamp(0.0, (self.length() - viewport).max(0.0));
+        let first = ((offset - cache) / self.extent).floor().max(0.0) as usize;
+        let last = ((offset + viewport + cache) / self.extent).ceil() as usize;
+        first.min(self.count)..last.min(self.count)
+    }
+    /// the most items [`Lazy::items_in_view`] returns for a `viewport` long window, wherever it is
+    pub fn capacity(&self, viewport: f32, cache: f32) -> usize {
+        if self.extent <= 0.0 {
+            return 0;
+        }
+        (((viewport + 2.0 * cache) / self.extent).ceil() as usize + 1).min(self.count)
+    }
+}
+impl Layout for Lazy {
+    fn layout(&self, constraints: BoxConstraints, children: &mut LayoutChildren) -> Size {
+        let (min_cross, max_cross) = match self.axis {
+            Axis::Horizontal => (constraints.min_height, constraints.max_height),
+            Axis::Vertical => (constraints.min_width, constraints.max_width),
+        };
+        // a bounded list is as thick as it may get, like the list views of flutter
+        let min_cross = if max_cross.is_finite() { max_cross } else { min_cross };
+        let mut cross = min_cross;
+        for i in 0..children.len() {
+            let Some(item) = children.get::<LazyItem>(i).copied() else { continue };
+            let item_constraints = match self.axis {
+                Axis::Horizontal => BoxConstraints::new(self.extent, self.extent, min_cross, max_cross),
+                Axis::Vertical => BoxConstraints::new(min_cross, max_cross, self.extent, self.extent),
+            };
+            let size = children.layout(i, item_constraints);
+            cross = cross.max(self.axis.cross(size));
+            let main = item.index as f32 * self.extent;
+            match self.axis {
+                Axis::Horizontal => children.place(i, main, 0.0),
+                Axis::Vertical => children.place(i, 0.0, main),
+            }
+        }
+        constraints.constrain(self.axis.size(self.length(), cross))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        layout::{scroll::Scroll, tests::{child, rect}, Axis, LayoutRect, Padded, Size},
+        test_utils::TestRenderer,
+        Gui,
+    };
+
+    use super::{Lazy, LazyItem};
+
+    #[test]
+    fn items_in_view_stay_within_the_capacity() {
+        let lazy = Lazy { axis: Axis::Vertical, extent: 25.0, count: 100_000 };
+        assert_eq!(lazy.items_in_view(0.0, 100.0, 50.0), 0..6);
+        assert_eq!(lazy.items_in_view(1010.0, 100.0, 50.0), 38..47);
+        // past the end shows the end
+        assert_eq!(lazy.items_in_view(f32::INFINITY, 100.0, 50.0), 99_994..100_000);
+        let capacity = lazy.capacity(100.0, 50.0);
+        for offset in (0..2000).map(|x| x as f32 * 1.7) {
+            assert!(lazy.items_in_view(offset, 100.0, 50.0).len() <= capacity);
+        }
+        assert_eq!(Lazy { count: 3, ..lazy }.capacity(100.0, 50.0), 3);
+    }
+
+    #[test]
+    fn items_are_put_at_their_index() {
+        let mut renderer = TestRenderer::new();
+        renderer.viewport = Some(Size::new(100.0, 50.0));
+        let mut gui = Gui::new(renderer);
+        let root = gui.tree().root();
+        let view = child(&mut gui, root, Some(Box::new(Scroll::new(Axis::Vertical))));
+        let lazy = Lazy { axis: Axis::Vertical, extent: 20.0, count: 1000 };
+        let list = child(&mut gui, view, Some(Box::new(lazy)));
+        // the children are in any order, like the slots a list recycles
+        let items: Vec<_> = [7, 5, 6]
+            .into_iter()
+            .map(|index| {
+                let item = child(&mut gui, list, Some(Box::new(Padded { width: Some(10.0), ..Default::default() })));
+                gui.tree.register(item, LazyItem { index });
+                item
+            })
+            .collect();
+        gui.render();
+
+        assert_eq!(rect(&gui, list), LayoutRect::new(0.0, 0.0, 100.0, 20_000.0));
+        assert_eq!(rect(&gui, items[0]), LayoutRect::new(0.0, 140.0, 100.0, 20.0));
+        assert_eq!(rect(&gui, items[1]), LayoutRect::new(0.0, 100.0, 100.0, 20.0));
+    }
+}
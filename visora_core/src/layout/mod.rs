@@ -0,0 +1,605 @@
+//! computes where every entity is drawn, so a backend only has to paint
+//!
+//! every entity can hold a [`Layout`], set with [`crate::WidgetContext::set_layout`]. the gui lays out a root
+//! before it renders, when the renderer has a [`crate::renderer::Renderer::viewport`], and stores a [`LayoutRect`]
+//! on every visible entity. entities without a layout stack their children vertically, like a [`Linear`] column
+
+use std::cell::RefCell;
+
+use crate::{
+    event::Point,
+    treecs::{EntityKey, Treecs},
+    Component,
+};
+
+pub mod flex;
+pub mod scroll;
+pub mod lazy;
+pub mod grid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Size {
+    pub width: f32,
+    pub height: f32,
+}
+impl Size {
+    pub fn new(width: f32, height: f32) -> Self {
+        Self { width, height }
+    }
+}
+
+/// the smallest and the biggest size a parent allows a child to take
+///
+/// the constraints go down the tree, the sizes come back up and the parents place their children. a max of
+/// [`f32::INFINITY`] is unbounded, like the height of the content of a scrolling list
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoxConstraints {
+    pub min_width: f32,
+    pub max_width: f32,
+    pub min_height: f32,
+    pub max_height: f32,
+}
+impl BoxConstraints {
+    pub fn new(min_width: f32, max_width: f32, min_height: f32, max_height: f32) -> Self {
+        Self { min_width, max_width, min_height, max_height }
+    }
+    /// only allows `size`
+    pub fn tight(size: Size) -> Self {
+        Self::new(size.width, size.width, size.height, size.height)
+    }
+    /// allows everything from nothing up to `size`
+    pub fn loose(size: Size) -> Self {
+        Self::new(0.0, size.width, 0.0, size.height)
+    }
+    /// allows every size
+    pub fn unbounded() -> Self {
+        Self::new(0.0, f32::INFINITY, 0.0, f32::INFINITY)
+    }
+    /// the same max without a min
+    pub fn loosen(&self) -> Self {
+        Self::new(0.0, self.max_width, 0.0, self.max_height)
+    }
+    /// only allows `width` and `height` when they are set, as far as these constraints allow them
+    pub fn tighten(&self, width: Option<f32>, height: Option<f32>) -> Self {
+        let (min_width, max_width) = match width {
+            Some(x) => {
+                let x = x.clamp(self.min_width, self.max_width);
+                (x, x)
+            }
+            None => (self.min_width, self.max_width),
+        };
+        let (min_height, max_height) = match height {
+            Some(x) => {
+                let x = x.clamp(self.min_height, self.max_height);
+                (x, x)
+            }
+            None => (self.min_height, self.max_height),
+        };
+        Self::new(min_width, max_width, min_height, max_height)
+    }
+    /// the constraints with `horizontal` and `vertical` taken away, they don't get negative
+    pub fn deflate(&self, horizontal: f32, vertical: f32) -> Self {
+        let min_width = (self.min_width - horizontal).max(0.0);
+        let min_height = (self.min_height - vertical).max(0.0);
+        Self::new(
+            min_width,
+            (self.max_width - horizontal).max(min_width),
+            min_height,
+            (self.max_height - vertical).max(min_height),
+        )
+    }
+    /// the allowed size closest to `size`
+    pub fn constrain(&self, size: Size) -> Size {
+        Size::new(
+            size.width.clamp(self.min_width, self.max_width),
+            size.height.clamp(self.min_height, self.max_height),
+        )
+    }
+    pub fn biggest(&self) -> Size {
+        Size::new(self.max_width, self.max_height)
+    }
+    pub fn smallest(&self) -> Size {
+        Size::new(self.min_width, self.min_height)
+    }
+    pub fn has_bounded_width(&self) -> bool {
+        self.max_width.is_finite()
+    }
+    pub fn has_bounded_height(&self) -> bool {
+        self.max_height.is_finite()
+    }
+    pub fn is_tight(&self) -> bool {
+        self.min_width == self.max_width && self.min_height == self.max_height
+    }
+}
+
+/// where an entity is drawn, in the coordinates of its root
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct LayoutRect {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+impl LayoutRect {
+    pub fn new(x: f32, y: f32, width: f32, height: f32) -> Self {
+        Self { x, y, width, height }
+    }
+    pub fn size(&self) -> Size {
+        Size::new(self.width, self.height)
+    }
+    pub fn right(&self) -> f32 {
+        self.x + self.width
+    }
+    pub fn bottom(&self) -> f32 {
+        self.y + self.height
+    }
+    pub fn contains(&self, position: Point) -> bool {
+        position.x >= self.x && position.x < self.right() && position.y >= self.y && position.y < self.bottom()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextStyle {
+    pub size: f32,
+    pub bold: bool,
+}
+impl Default for TextStyle {
+    fn default() -> Self {
+        Self { size: 25.0, bold: false }
+    }
+}
+
+/// measures text for the layout, every backend measures with the fonts it paints with
+pub trait TextMeasurer {
+    /// the size of `text` when its lines wrap at `max_width`
+    fn measure(&self, text: &str, style: &TextStyle, max_width: f32) -> Size;
+}
+/// measures every character half as wide as the font size and every line as high as the font size
+///
+/// for tests and for backends without fonts
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MonospaceMeasurer;
+impl TextMeasurer for MonospaceMeasurer {
+    fn measure(&self, text: &str, style: &TextStyle, max_width: f32) -> Size {
+        let advance = style.size / 2.0;
+        // at least one character fits on a line, otherwise nothing would be drawn
+        let per_line = ((max_width / advance).floor() as usize).max(1);
+        let mut width = 0;
+        let mut lines = 0;
+        for line in text.split('\n') {
+            let chars = line.chars().count();
+            width = width.max(chars.min(per_line));
+            lines += chars.div_ceil(per_line).max(1);
+        }
+        Size::new(width as f32 * advance, lines as f32 * style.size)
+    }
+}
+
+/// how an entity sizes itself and places its children
+///
+/// like the layout of flutter, the [`BoxConstraints`] go down, the size comes back up and the parent places the children
+pub trait Layout: Send + Sync + 'static {
+    fn layout(&self, constraints: BoxConstraints, children: &mut LayoutChildren) -> Size;
+}
+
+/// the [`Layout`] of an entity
+pub(crate) struct LayoutBox(pub(crate) Box<dyn Layout>);
+
+/// the children of the entity being laid out
+pub struct LayoutChildren<'a> {
+    tree: &'a Treecs,
+    key: EntityKey,
+    measurer: &'a dyn TextMeasurer,
+    needs_build: &'a RefCell<Vec<EntityKey>>,
+    children: Vec<EntityKey>,
+    laid_out: Vec<bool>,
+}
+impl LayoutChildren<'_> {
+    pub fn len(&self) -> usize {
+        self.children.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.children.is_empty()
+    }
+    /// lays out the child at `index` in `constraints` and returns its size, it is placed at the top left corner
+    /// until [`LayoutChildren::place`] moves it
+    pub fn layout(&mut self, index: usize, constraints: BoxConstraints) -> Size {
+        self.laid_out[index] = true;
+        layout_entity(self.tree, self.measurer, self.needs_build, self.children[index], constraints)
+    }
+    /// moves the child at `index` to `x`, `y` from the top left corner of this entity
+    pub fn place(&mut self, index: usize, x: f32, y: f32) {
+        if let Some(rect) = self.tree.get_component_mut::<LayoutRect>(self.children[index]) {
+            rect.x = x;
+            rect.y = y;
+        }
+    }
+    /// a component of the child at `index`, like the [`flex::FlexChild`] a child of a row shares the space with
+    pub fn get<T: Component>(&self, index: usize) -> Option<&T> {
+        self.tree.get_component(self.children[index])
+    }
+    pub fn measure_text(&self, text: &str, style: &TextStyle, max_width: f32) -> Size {
+        self.measurer.measure(text, style, max_width)
+    }
+    /// stores a component on the entity being laid out, like the [`scroll::ScrollMetrics`] its input is clamped with
+    pub fn insert_component<T: Component>(&self, component: T) {
+        self.tree.store().add_component(self.key, component);
+    }
+    /// builds the entity being laid out again and lays the tree out once more, for content that is built for the
+    /// size it gets, like the items of a lazy list
+    pub fn mark_needs_build(&self) {
+        self.needs_build.borrow_mut().push(self.key);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Axis {
+    Horizontal,
+    Vertical,
+}
+impl Axis {
+    /// the length of `size` along this axis
+    pub fn main(&self, size: Size) -> f32 {
+        match self {
+            Axis::Horizontal => size.width,
+            Axis::Vertical => size.height,
+        }
+    }
+    /// the length of `size` across this axis
+    pub fn cross(&self, size: Size) -> f32 {
+        match self {
+            Axis::Horizontal => size.height,
+            Axis::Vertical => size.width,
+        }
+    }
+    /// the size that is `main` long along this axis and `cross` long across it
+    pub fn size(&self, main: f32, cross: f32) -> Size {
+        match self {
+            Axis::Horizontal => Size::new(main, cross),
+            Axis::Vertical => Size::new(cross, main),
+        }
+    }
+}
+
+/// places the children one after the other along `axis`, it is as long as the children together
+///
+/// every child can take what the children before it left, so a bounded list doesn't grow out of its parent
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Linear {
+    pub axis: Axis,
+}
+impl Linear {
+    pub fn new(axis: Axis) -> Self {
+        Self { axis }
+    }
+}
+impl Layout for Linear {
+    fn layout(&self, constraints: BoxConstraints, children: &mut LayoutChildren) -> Size {
+        let (mut along, mut across) = (0.0f32, 0.0f32);
+        for i in 0..children.len() {
+            let size = match self.axis {
+                Axis::Vertical => {
+                    let max_height = (constraints.max_height - along).max(0.0);
+                    let size = children.layout(i, BoxConstraints::new(0.0, constraints.max_width, 0.0, max_height));
+                    children.place(i, 0.0, along);
+                    (size.height, size.width)
+                }
+                Axis::Horizontal => {
+                    let max_width = (constraints.max_width - along).max(0.0);
+                    let size = children.layout(i, BoxConstraints::new(0.0, max_width, 0.0, constraints.max_height));
+                    children.place(i, along, 0.0);
+                    (size.width, size.height)
+                }
+            };
+            along += size.0;
+            across = across.max(size.1);
+        }
+        constraints.constrain(match self.axis {
+            Axis::Vertical => Size::new(across, along),
+            Axis::Horizontal => Size::new(along, across),
+        })
+    }
+}
+
+/// insets its children, which are stacked vertically, a fixed width or height replaces the size of the children
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Padded {
+    pub left: f32,
+    pub top: f32,
+    pub right: f32,
+    pub bottom: f32,
+    pub width: Option<f32>,
+    pub height: Option<f32>,
+}
+impl Layout for Padded {
+    fn layout(&self, constraints: BoxConstraints, children: &mut LayoutChildren) -> Size {
+        let outer = constraints.tighten(self.width, self.height);
+        let inner = outer.deflate(self.left + self.right, self.top + self.bottom).loosen();
+        let content = Linear::new(Axis::Vertical).layout(inner, children);
+        for i in 0..children.len() {
+            if let Some(rect) = children.tree.get_component_mut::<LayoutRect>(children.children[i]) {
+                rect.x += self.left;
+                rect.y += self.top;
+            }
+        }
+        outer.constrain(Size::new(
+            content.width + self.left + self.right,
+            content.height + self.top + self.bottom,
+        ))
+    }
+}
+
+/// takes all the space it gets and centers its children in it
+///
+/// in an unbounded direction it is as big as its biggest child
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Centered;
+impl Layout for Centered {
+    fn layout(&self, constraints: BoxConstraints, children: &mut LayoutChildren) -> Size {
+        let sizes: Vec<Size> = (0..children.len()).map(|i| children.layout(i, constraints.loosen())).collect();
+        let width = match constraints.has_bounded_width() {
+            true => constraints.max_width,
+            false => sizes.iter().fold(0.0, |a, x| x.width.max(a)),
+        };
+        let height = match constraints.has_bounded_height() {
+            true => constraints.max_height,
+            false => sizes.iter().fold(0.0, |a, x| x.height.max(a)),
+        };
+        let size = constraints.constrain(Size::new(width, height));
+        for (i, child) in sizes.into_iter().enumerate() {
+            children.place(i, (size.width - child.width) / 2.0, (size.height - child.height) / 2.0);
+        }
+        size
+    }
+}
+
+/// the size of a text measured by the backend
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextLayout {
+    pub text: String,
+    pub style: TextStyle,
+}
+impl TextLayout {
+    pub fn new(text: &str, style: TextStyle) -> Self {
+        Self { text: text.to_owned(), style }
+    }
+}
+impl Layout for TextLayout {
+    fn layout(&self, constraints: BoxConstraints, children: &mut LayoutChildren) -> Size {
+        constraints.constrain(children.measure_text(&self.text, &self.style, constraints.max_width))
+    }
+}
+
+/// grows to the space it gets, up to a max width and height
+///
+/// an image in an unbounded direction without a max takes the min of the constraints
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Growable {
+    pub max_width: Option<f32>,
+    pub max_height: Option<f32>,
+}
+impl Layout for Growable {
+    fn layout(&self, constraints: BoxConstraints, _children: &mut LayoutChildren) -> Size {
+        let grow = |max: Option<f32>, allowed: f32, min: f32| match max.unwrap_or(f32::INFINITY).min(allowed) {
+            x if x.is_finite() => x,
+            _ => min,
+        };
+        constraints.constrain(Size::new(
+            grow(self.max_width, constraints.max_width, constraints.min_width),
+            grow(self.max_height, constraints.max_height, constraints.min_height),
+        ))
+    }
+}
+
+/// lays its children out in its own constraints, on top of each other, and is as big as the biggest
+///
+/// without children it is as small as the constraints allow, like a spacer. a loosened proxy only passes the max
+/// on, so its children can be smaller than it
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Proxy {
+    pub loosen: bool,
+}
+impl Layout for Proxy {
+    fn layout(&self, constraints: BoxConstraints, children: &mut LayoutChildren) -> Size {
+        let inner = match self.loosen {
+            true => constraints.loosen(),
+            false => constraints,
+        };
+        let size = (0..children.len()).fold(constraints.smallest(), |size, i| {
+            let child = children.layout(i, inner);
+            Size::new(size.width.max(child.width), size.height.max(child.height))
+        });
+        constraints.constrain(size)
+    }
+}
+
+/// lays out `root` in `viewport` and stores the [`LayoutRect`] of every visible entity below it
+///
+/// the gui does this before every render when the renderer has a viewport, backends without one can lay out
+/// a tree themselves, like a test that compares the layout with the page of ssr. returns the entities that
+/// asked to be built again, see [`LayoutChildren::mark_needs_build`]
+pub fn layout_root(tree: &Treecs, root: EntityKey, viewport: Size, measurer: &dyn TextMeasurer) -> Vec<EntityKey> {
+    let needs_build = RefCell::new(Vec::new());
+    layout_entity(tree, measurer, &needs_build, root, BoxConstraints::tight(viewport));
+    // the layouts placed the children relative to their parent
+    let mut stack = vec![(root, Point::default())];
+    while let Some((key, origin)) = stack.pop() {
+        let Some(rect) = tree.get_component_mut::<LayoutRect>(key) else { continue };
+        rect.x += origin.x;
+        rect.y += origin.y;
+        let origin = Point::new(rect.x, rect.y);
+        stack.extend(visible_children(tree, key).into_iter().map(|x| (x, origin)));
+    }
+    needs_build.into_inner()
+}
+
+fn visible_children(tree: &Treecs, key: EntityKey) -> Vec<EntityKey> {
+    tree.linkdata(key)
+        .map(|x| x.children().iter().copied().filter(|x| !tree.linkdata(*x).is_some_and(|x| x.is_hidden())).collect())
+        .unwrap_or_default()
+}
+
+fn layout_entity(
+    tree: &Treecs,
+    measurer: &dyn TextMeasurer,
+    needs_build: &RefCell<Vec<EntityKey>>,
+    key: EntityKey,
+    constraints: BoxConstraints,
+) -> Size {
+    let children = visible_children(tree, key);
+    let mut laid_out = LayoutChildren {
+        tree,
+        key,
+        measurer,
+        needs_build,
+        laid_out: vec![false; children.len()],
+        children,
+    };
+    let size = match tree.get_component::<LayoutBox>(key) {
+        Some(layout) => layout.0.layout(constraints, &mut laid_out),
+        None => Linear::new(Axis::Vertical).layout(constraints, &mut laid_out),
+    };
+    // children a layout skipped get no space
+    for i in 0..laid_out.len() {
+        if !laid_out.laid_out[i] {
+            laid_out.layout(i, BoxConstraints::tight(Size::default()));
+        }
+    }
+    tree.store().add_component(key, LayoutRect::new(0.0, 0.0, size.width, size.height));
+    size
+}
+
+/// the deepest visible entity below `root` whose [`LayoutRect`] contains `position`
+pub(crate) fn hit_test(tree: &Treecs, root: EntityKey, position: Point) -> Option<EntityKey> {
+    let mut hit = None;
+    let mut stack = vec![root];
+    while let Some(key) = stack.pop() {
+        if !tree.get_component::<LayoutRect>(key).is_some_and(|x| x.contains(position)) {
+            continue;
+        }
+        hit = Some(key);
+        // later children are drawn over the earlier ones
+        stack.clear();
+        stack.extend(visible_children(tree, key));
+    }
+    hit
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        event::Point,
+        layout::{Axis, BoxConstraints, Centered, Growable, Layout, LayoutChildren, Linear, MonospaceMeasurer, Padded, TextLayout, TextMeasurer, TextStyle},
+        test_utils::TestRenderer,
+        treecs::EntityKey,
+        Gui,
+    };
+
+    use super::{LayoutRect, Size};
+
+    pub(super) fn child(gui: &mut Gui<TestRenderer>, parent: EntityKey, layout: Option<Box<dyn super::Layout>>) -> EntityKey {
+        let key = gui.tree.add(parent).unwrap();
+        if let Some(layout) = layout {
+            gui.tree.register(key, super::LayoutBox(layout));
+        }
+        key
+    }
+    pub(super) fn rect(gui: &Gui<TestRenderer>, key: EntityKey) -> LayoutRect {
+        *gui.tree().get_component::<LayoutRect>(key).unwrap()
+    }
+
+    #[test]
+    fn monospace_text_wraps() {
+        let style = TextStyle { size: 10.0, bold: false };
+        assert_eq!(MonospaceMeasurer.measure("abcd", &style, 100.0), Size::new(20.0, 10.0));
+        assert_eq!(MonospaceMeasurer.measure("abcd", &style, 10.0), Size::new(10.0, 20.0));
+        assert_eq!(MonospaceMeasurer.measure("ab\nc", &style, 100.0), Size::new(10.0, 20.0));
+    }
+    #[test]
+    fn layouts_place_their_children() {
+        let mut renderer = TestRenderer::new();
+        renderer.viewport = Some(Size::new(200.0, 100.0));
+        let mut gui = Gui::new(renderer);
+        let root = gui.tree().root();
+        let style = TextStyle { size: 10.0, bold: false };
+        let padded = child(&mut gui, root, Some(Box::new(Padded { left: 5.0, top: 10.0, right: 5.0, bottom: 10.0, ..Default::default() })));
+        let row = child(&mut gui, padded, Some(Box::new(Linear::new(Axis::Horizontal))));
+        let a = child(&mut gui, row, Some(Box::new(TextLayout::new("abc", style))));
+        let b = child(&mut gui, row, Some(Box::new(TextLayout::new("de", style))));
+        let centered = child(&mut gui, root, Some(Box::new(Centered)));
+        let c = child(&mut gui, centered, Some(Box::new(TextLayout::new("ab", style))));
+        gui.render();
+
+        assert_eq!(rect(&gui, root), LayoutRect::new(0.0, 0.0, 200.0, 100.0));
+        assert_eq!(rect(&gui, padded), LayoutRect::new(0.0, 0.0, 35.0, 30.0));
+        assert_eq!(rect(&gui, row), LayoutRect::new(5.0, 10.0, 25.0, 10.0));
+        assert_eq!(rect(&gui, a), LayoutRect::new(5.0, 10.0, 15.0, 10.0));
+        assert_eq!(rect(&gui, b), LayoutRect::new(20.0, 10.0, 10.0, 10.0));
+        // the centered entity gets what is left below the padded one
+        assert_eq!(rect(&gui, centered), LayoutRect::new(0.0, 30.0, 200.0, 70.0));
+        assert_eq!(rect(&gui, c), LayoutRect::new(95.0, 60.0, 10.0, 10.0));
+
+        // the renderer has no hit test, the gui falls back to the layout
+        assert_eq!(super::hit_test(gui.tree(), root, Point::new(22.0, 12.0)), Some(b));
+        assert_eq!(super::hit_test(gui.tree(), root, Point::new(100.0, 65.0)), Some(c));
+        assert_eq!(super::hit_test(gui.tree(), root, Point::new(100.0, 10.0)), Some(root));
+    }
+    #[test]
+    fn constraints_clamp_sizes() {
+        let constraints = BoxConstraints::new(10.0, 100.0, 0.0, 50.0);
+        assert_eq!(constraints.constrain(Size::new(5.0, 80.0)), Size::new(10.0, 50.0));
+        assert_eq!(constraints.tighten(Some(200.0), None), BoxConstraints::new(100.0, 100.0, 0.0, 50.0));
+        assert_eq!(constraints.deflate(20.0, 60.0), BoxConstraints::new(0.0, 80.0, 0.0, 0.0));
+        assert_eq!(constraints.loosen(), BoxConstraints::loose(Size::new(100.0, 50.0)));
+        assert!(BoxConstraints::tight(Size::new(1.0, 2.0)).is_tight());
+        assert!(!BoxConstraints::unbounded().has_bounded_height());
+    }
+    #[test]
+    fn fixed_sizes_and_growables() {
+        let mut renderer = TestRenderer::new();
+        renderer.viewport = Some(Size::new(200.0, 100.0));
+        let mut gui = Gui::new(renderer);
+        let root = gui.tree().root();
+        let fixed = child(&mut gui, root, Some(Box::new(Padded { left: 5.0, top: 5.0, right: 5.0, bottom: 5.0, width: Some(50.0), height: Some(40.0) })));
+        let filled = child(&mut gui, fixed, Some(Box::new(Growable::default())));
+        let row = child(&mut gui, root, Some(Box::new(Linear::new(Axis::Horizontal))));
+        let limited = child(&mut gui, row, Some(Box::new(Growable { max_width: Some(30.0), max_height: Some(1000.0) })));
+        let too_wide = child(&mut gui, row, Some(Box::new(Padded { width: Some(500.0), ..Default::default() })));
+        gui.render();
+
+        assert_eq!(rect(&gui, fixed), LayoutRect::new(0.0, 0.0, 50.0, 40.0));
+        assert_eq!(rect(&gui, filled), LayoutRect::new(5.0, 5.0, 40.0, 30.0));
+        // the row gets what the fixed entity left
+        assert_eq!(rect(&gui, row), LayoutRect::new(0.0, 40.0, 200.0, 60.0));
+        assert_eq!(rect(&gui, limited), LayoutRect::new(0.0, 40.0, 30.0, 60.0));
+        assert_eq!(rect(&gui, too_wide), LayoutRect::new(30.0, 40.0, 170.0, 0.0));
+    }
+
+    /// lays out its children without bounds, like the content of a scrolling list
+    struct Unbounded;
+    impl Layout for Unbounded {
+        fn layout(&self, constraints: BoxConstraints, children: &mut LayoutChildren) -> Size {
+            for i in 0..children.len() {
+                children.layout(i, BoxConstraints::unbounded());
+            }
+            constraints.biggest()
+        }
+    }
+    #[test]
+    fn unbounded_children_shrink_to_their_content() {
+        let mut renderer = TestRenderer::new();
+        renderer.viewport = Some(Size::new(200.0, 100.0));
+        let mut gui = Gui::new(renderer);
+        let root = gui.tree().root();
+        let unbounded = child(&mut gui, root, Some(Box::new(Unbounded)));
+        let centered = child(&mut gui, unbounded, Some(Box::new(Centered)));
+        let text = child(&mut gui, centered, Some(Box::new(TextLayout::new("abcd", TextStyle { size: 10.0, bold: false }))));
+        let growable = child(&mut gui, unbounded, Some(Box::new(Growable { max_width: Some(30.0), max_height: None })));
+        gui.render();
+
+        assert_eq!(rect(&gui, centered), LayoutRect::new(0.0, 0.0, 20.0, 10.0));
+        // an unbounded text doesn't wrap
+        assert_eq!(rect(&gui, text), LayoutRect::new(0.0, 0.0, 20.0, 10.0));
+        assert_eq!(rect(&gui, growable), LayoutRect::new(0.0, 0.0, 30.0, 0.0));
+    }
+}
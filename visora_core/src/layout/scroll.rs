@@ -0,0 +1,213 @@
+//! a window onto content that is bigger than the space it gets, moved by a scroll offset
+
+use crate::event::Point;
+
+use super::{Axis, BoxConstraints, Layout, LayoutChildren, Size};
+
+/// how far the content of a [`Scroll`] can move, the layout stores it on the entity of the scroll
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ScrollMetrics {
+    /// the offset the content was placed at, clamped to what the content can scroll
+    pub offset: Point,
+    pub viewport: Size,
+    pub content: Size,
+}
+impl ScrollMetrics {
+    /// the offset that shows the end of the content
+    pub fn max_offset(&self) -> Point {
+        Point::new(
+            (self.content.width - self.viewport.width).max(0.0),
+            (self.content.height - self.viewport.height).max(0.0),
+        )
+    }
+    /// `offset` moved into the range the content can scroll
+    pub fn clamp(&self, offset: Point) -> Point {
+        let max = self.max_offset();
+        Point::new(offset.x.clamp(0.0, max.x), offset.y.clamp(0.0, max.y))
+    }
+    /// whether the content is longer than the viewport along `axis`
+    pub fn overflows(&self, axis: Axis) -> bool {
+        axis.main(self.content) > axis.main(self.viewport)
+    }
+}
+
+/// component on a child of a [`Scroll`] that is the thumb of its scrollbar along `axis`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScrollbarThumb {
+    pub axis: Axis,
+}
+
+/// shows its content moved by `offset`, the content is laid out unbounded along the scrolled axes
+///
+/// it is as big as its content as far as the constraints allow, so it needs bounded constraints to scroll.
+/// the first child without a [`ScrollbarThumb`] is the content, the thumbs are put along the right and the bottom
+/// edge, as long as the part of the content that is visible. a thumb is only laid out when the content overflows
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Scroll {
+    pub horizontal: bool,
+    pub vertical: bool,
+    pub offset: Point,
+    /// how thick the thumbs are
+    pub thumb_thickness: f32,
+    /// the viewport the content was built for, the scroll asks to be built again when it gets another one
+    pub built_for: Option<Size>,
+}
+impl Scroll {
+    pub fn new(axis: Axis) -> Self {
+        Self {
+            horizontal: axis == Axis::Horizontal,
+            vertical: axis == Axis::Vertical,
+            offset: Point::default(),
+            thumb_thickness: 6.0,
+            built_for: None,
+        }
+    }
+    /// scrolls along both axes
+    pub fn both() -> Self {
+        Self {
+            horizontal: true,
+            vertical: true,
+            ..Self::new(Axis::Vertical)
+        }
+    }
+}
+impl Layout for Scroll {
+    fn layout(&self, constraints: BoxConstraints, children: &mut LayoutChildren) -> Size {
+        let (mut min_width, mut max_width) = (constraints.min_width, constraints.max_width);
+        let (mut min_height, mut max_height) = (constraints.min_height, constraints.max_height);
+        if self.horizontal {
+            (min_width, max_width) = (0.0, f32::INFINITY);
+        }
+        if self.vertical {
+            (min_height, max_height) = (0.0, f32::INFINITY);
+        }
+        let content = (0..children.len()).find(|i| children.get::<ScrollbarThumb>(*i).is_none());
+        let content_size = match content {
+            Some(i) => children.layout(i, BoxConstraints::new(min_width, max_width, min_height, max_height)),
+            None => Size::default(),
+        };
+        let viewport = constraints.constrain(content_size);
+        if self.built_for.is_some_and(|x| x != viewport) {
+            children.mark_needs_build();
+        }
+        let mut metrics = ScrollMetrics { offset: Point::default(), viewport, content: content_size };
+        // the content is clipped along the axes that don't scroll
+        metrics.offset = metrics.clamp(Point::new(
+            if self.horizontal { self.offset.x } else { 0.0 },
+            if self.vertical { self.offset.y } else { 0.0 },
+        ));
+        if let Some(i) = content {
+            children.place(i, -metrics.offset.x, -metrics.offset.y);
+        }
+        for i in 0..children.len() {
+            let Some(thumb) = children.get::<ScrollbarThumb>(i).copied() else { continue };
+            let scrolls = match thumb.axis {
+                Axis::Horizontal => self.horizontal,
+                Axis::Vertical => self.vertical,
+            };
+            if !scrolls || !metrics.overflows(thumb.axis) {
+                continue;
+            }
+            let visible = thumb.axis.main(viewport);
+            let length = (visible * visible / thumb.axis.main(content_size)).max(self.thumb_thickness);
+            let start = match thumb.axis {
+                Axis::Horizontal => metrics.offset.x / metrics.max_offset().x,
+                Axis::Vertical => metrics.offset.y / metrics.max_offset().y,
+            } * (visible - length);
+            children.layout(i, BoxConstraints::tight(thumb.axis.size(length, self.thumb_thickness)));
+            match thumb.axis {
+                Axis::Horizontal => children.place(i, start, viewport.height - self.thumb_thickness),
+                Axis::Vertical => children.place(i, viewport.width - self.thumb_thickness, start),
+            }
+        }
+        children.insert_component(metrics);
+        viewport
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        event::Point,
+        layout::{layout_root, tests::{child, rect}, Axis, LayoutRect, Linear, MonospaceMeasurer, Padded, Size},
+        test_utils::TestRenderer,
+        Gui,
+    };
+
+    use super::{Scroll, ScrollMetrics, ScrollbarThumb};
+
+    fn gui() -> Gui<TestRenderer> {
+        let mut renderer = TestRenderer::new();
+        renderer.viewport = Some(Size::new(100.0, 50.0));
+        Gui::new(renderer)
+    }
+
+    #[test]
+    fn content_is_moved_by_the_clamped_offset() {
+        let mut gui = gui();
+        let root = gui.tree().root();
+        let scroll = Scroll { offset: Point::new(0.0, 30.0), ..Scroll::new(Axis::Vertical) };
+        let view = child(&mut gui, root, Some(Box::new(scroll)));
+        let content = child(&mut gui, view, Some(Box::new(Linear::new(Axis::Vertical))));
+        let items: Vec<_> = (0..4)
+            .map(|_| child(&mut gui, content, Some(Box::new(Padded { width: Some(40.0), height: Some(40.0), ..Default::default() }))))
+            .collect();
+        let thumb = child(&mut gui, view, None);
+        gui.tree.register(thumb, ScrollbarThumb { axis: Axis::Vertical });
+        let across = child(&mut gui, view, None);
+        gui.tree.register(across, ScrollbarThumb { axis: Axis::Horizontal });
+        gui.render();
+
+        // the root lets the scroll shrink to the width of its content, the height is bounded by the window
+        assert_eq!(rect(&gui, view), LayoutRect::new(0.0, 0.0, 40.0, 50.0));
+        assert_eq!(rect(&gui, content), LayoutRect::new(0.0, -30.0, 40.0, 160.0));
+        assert_eq!(rect(&gui, items[1]), LayoutRect::new(0.0, 10.0, 40.0, 40.0));
+        // 50 of 160 are visible, the thumb moved 30 of the 110 it can scroll
+        let length = 50.0 * 50.0 / 160.0;
+        assert_eq!(rect(&gui, thumb), LayoutRect::new(34.0, 30.0 / 110.0 * (50.0 - length), 6.0, length));
+        assert_eq!(rect(&gui, across).size(), Size::default(), "the content doesn't scroll horizontally");
+        let metrics = *gui.tree().get_component::<ScrollMetrics>(view).unwrap();
+        assert_eq!(metrics.max_offset(), Point::new(0.0, 110.0));
+
+        // an offset past the end shows the end
+        let scroll = Scroll { offset: Point::new(20.0, 500.0), ..Scroll::new(Axis::Vertical) };
+        gui.tree.register(view, crate::layout::LayoutBox(Box::new(scroll)));
+        gui.render();
+        assert_eq!(rect(&gui, content), LayoutRect::new(0.0, -110.0, 40.0, 160.0));
+        assert_eq!(rect(&gui, thumb), LayoutRect::new(34.0, 50.0 - length, 6.0, length));
+        assert_eq!(gui.tree().get_component::<ScrollMetrics>(view).unwrap().offset, Point::new(0.0, 110.0));
+    }
+
+    #[test]
+    fn short_content_doesnt_scroll() {
+        let mut gui = gui();
+        let root = gui.tree().root();
+        let scroll = Scroll { offset: Point::new(10.0, 10.0), ..Scroll::both() };
+        let view = child(&mut gui, root, Some(Box::new(scroll)));
+        let content = child(&mut gui, view, Some(Box::new(Padded { width: Some(120.0), height: Some(20.0), ..Default::default() })));
+        let thumb = child(&mut gui, view, None);
+        gui.tree.register(thumb, ScrollbarThumb { axis: Axis::Vertical });
+        gui.render();
+
+        assert_eq!(rect(&gui, content), LayoutRect::new(-10.0, 0.0, 120.0, 20.0));
+        assert_eq!(rect(&gui, thumb).size(), Size::default());
+        let metrics = gui.tree().get_component::<ScrollMetrics>(view).unwrap();
+        assert!(metrics.overflows(Axis::Horizontal) && !metrics.overflows(Axis::Vertical));
+        assert_eq!(metrics.clamp(Point::new(-5.0, 30.0)), Point::new(0.0, 0.0));
+    }
+
+    #[test]
+    fn content_built_for_another_viewport_asks_for_a_build() {
+        let mut gui = gui();
+        let root = gui.tree().root();
+        let scroll = Scroll { built_for: Some(Size::default()), ..Scroll::new(Axis::Vertical) };
+        let view = child(&mut gui, root, Some(Box::new(scroll)));
+        child(&mut gui, view, Some(Box::new(Padded { width: Some(40.0), height: Some(80.0), ..Default::default() })));
+        let viewport = Size::new(100.0, 50.0);
+        assert_eq!(layout_root(gui.tree(), root, viewport, &MonospaceMeasurer), vec![view]);
+
+        let scroll = Scroll { built_for: Some(Size::new(40.0, 50.0)), ..Scroll::new(Axis::Vertical) };
+        gui.tree.register(view, crate::layout::LayoutBox(Box::new(scroll)));
+        assert!(layout_root(gui.tree(), root, viewport, &MonospaceMeasurer).is_empty());
+    }
+}
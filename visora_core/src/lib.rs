@@ -3,81 +3,857 @@ pub mod widget;
 pub mod color;
 pub mod renderer;
 pub mod state;
-use std::marker::PhantomData;
+pub mod task;
+pub mod runtime;
+pub mod provider;
+pub mod event;
+pub mod focus;
+pub mod animation;
+pub mod timer;
+pub mod error;
+pub mod signal;
+pub mod history;
+pub mod record;
+pub mod devtools;
+pub mod profile;
+pub mod layout;
+#[cfg(test)]
+pub mod test_utils;
+use std::{future::Future, marker::PhantomData, panic::{self, AssertUnwindSafe}, sync::{mpsc::Receiver, Arc}, time::Duration};
 
+use animation::{clock::Clock, Tickers};
+use error::{find_catcher, Catcher, WidgetError};
+use event::{Event, EventHandler, Key, KeyState, Propagation};
+use focus::{scope_of, traversal_order, Focusable};
+use devtools::{Devtools, Dispatched};
+use history::History;
+use layout::{Layout, LayoutBox};
+use profile::{FrameStats, Phase};
+use record::{Recorder, Session, Step};
+use animation::clock::ManualClock;
+use provider::{find_provided, Provided};
 use renderer::Renderer;
+use runtime::Runtime;
+use signal::{Effect, Memo, Signal};
+use state::StateTransaction;
+use task::{Executor, Tasks};
+use timer::{TimerHandle, Timers};
 pub use treecs::component::Component;
-use treecs::{component::{ComponentEntry, ComponentEntryMut, ComponentStore}, entity::Entity, iterators::{breadth::BreadthIter, QueryIter}, query::{Query, QueryAble}, EntityKey, Treecs};
+use treecs::{component::{ComponentEntry, ComponentEntryMut}, entity::Entity, iterators::{breadth::{BreadthInfo, BreadthIter}, parent::ParentIter, QueryIter}, query::{Query, QueryAble}, EntityKey, Treecs};
+use widget::{Lifecycle, Mounted, Render, RenderAble, Unmount, Widget, WidgetType};
 
 pub struct Gui<R> {
     tree: Treecs,
-    renderer: R
+    renderer: R,
+    runtime: Runtime,
+    transactions: Receiver<StateTransaction>,
+    /// the entity the pointer was over during the last pointer event
+    hovered: Option<EntityKey>,
+    /// the roots added next to the main one, see [`Gui::add_root`]
+    roots: Vec<Root<R>>,
+    /// records the undoable transactions, see [`Gui::with_history`]
+    history: Option<History>,
+    /// writes the input and timer steps, see [`Gui::with_recorder`]
+    recorder: Option<Recorder>,
+    /// the state of the devtools inspector, see [`Gui::with_devtools`]
+    devtools: Option<Devtools>,
+    /// the time the phases of the last frame took
+    last_frame: FrameStats,
+}
+/// a root added with [`Gui::add_root`] and the target it renders to
+struct Root<R> {
+    key: EntityKey,
+    renderer: R,
+    hovered: Option<EntityKey>,
 }
 impl<R: Renderer> Gui<R> {
     pub fn new(renderer: R) -> Self {
+        let (runtime, transactions) = Runtime::new();
         Self {
             tree: Treecs::new(),
-            renderer
+            renderer,
+            runtime,
+            transactions,
+            hovered: None,
+            roots: Vec::new(),
+            history: None,
+            recorder: None,
+            devtools: None,
+            last_frame: FrameStats::default(),
+        }
+    }
+    /// sets the executor that runs the tasks spawned with [`BuildContext::spawn`]
+    pub fn with_executor(mut self, executor: impl Executor + 'static) -> Self {
+        self.runtime.executor = Some(Arc::new(executor));
+        self
+    }
+    /// sets the clock that drives the animations, see [`Gui::tick`]
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.runtime.clock = Arc::new(clock);
+        self
+    }
+    /// records the transactions created with `update_undoable`, so they can be undone with [`Gui::undo`]
+    pub fn with_history(mut self, history: History) -> Self {
+        self.history = Some(history);
+        self
+    }
+    /// records every input event, timer firing and animation frame with the time of the clock,
+    /// so the session can be replayed with [`Gui::replay`]
+    pub fn with_recorder(mut self, recorder: Recorder) -> Self {
+        self.recorder = Some(recorder);
+        self
+    }
+    /// keeps what the devtools inspector shows, the dispatched transactions and the selected entity
+    pub fn with_devtools(mut self, devtools: Devtools) -> Self {
+        self.devtools = Some(devtools);
+        self
+    }
+    pub fn devtools(&self) -> Option<&Devtools> {
+        self.devtools.as_ref()
+    }
+    pub fn devtools_mut(&mut self) -> Option<&mut Devtools> {
+        self.devtools.as_mut()
+    }
+    /// the tree, the debuggable components and the last transactions as json, without devtools there is nothing to inspect
+    pub fn inspect(&self) -> Option<String> {
+        self.devtools.as_ref().map(|devtools| devtools::inspect(self, devtools))
+    }
+    /// records a step that only the app can replay, like opening a tab, the replay passes `mark` back to the app
+    pub fn record_mark(&mut self, mark: &str) {
+        self.record(Step::Mark(mark.to_owned()));
+    }
+    fn record(&mut self, step: Step) {
+        if let Some(recorder) = &mut self.recorder {
+            recorder.record(self.runtime.now(), &step);
+        }
+    }
+    /// repeats the steps of a recorded session, `clock` has to be the clock of this gui and is moved to the time of
+    /// every step
+    ///
+    /// the gui has to start with the same widgets as the recorded one, marks are passed to `on_mark`.
+    /// tasks are not recorded, their transactions only happen when the executor of the replay finishes them
+    pub fn replay<F>(&mut self, session: &Session, clock: &ManualClock, mut on_mark: F)
+    where F: FnMut(&mut Self, &str)
+    {
+        for (at, step) in &session.steps {
+            clock.advance(at.saturating_sub(self.runtime.now()));
+            match step {
+                Step::Event { root, hit, event } => {
+                    self.deliver_root_event(*root, event.clone(), *hit);
+                }
+                Step::Dispatch { target, event } => {
+                    self.dispatch_event(*target, event);
+                }
+                Step::Timers => {
+                    self.fire_timers();
+                }
+                Step::Tick => {
+                    self.tick();
+                }
+                Step::Mark(mark) => {
+                    self.record_mark(mark);
+                    on_mark(self, mark);
+                }
+            }
         }
     }
     pub fn root_widget_context(&mut self) -> WidgetContext<'_, R> {
         let key = self.tree.root();
-        WidgetContext {
-            tree: &mut self.tree,
+        WidgetContext::new(&mut self.tree, &self.runtime, key)
+    }
+    /// adds a root that renders with its own renderer, like another window or another browser tab
+    ///
+    /// the roots share the runtime, so transactions, timers and the focus work across them
+    pub fn add_root(&mut self, renderer: R) -> EntityKey {
+        let key = self.tree.add_root();
+        self.roots.push(Root {
             key,
-            _ph: PhantomData,
+            renderer,
+            hovered: None,
+        });
+        key
+    }
+    /// disposes the widgets of a root added with [`Gui::add_root`] and returns its renderer
+    pub fn remove_root(&mut self, root: EntityKey) -> Option<R> {
+        let index = self.roots.iter().position(|x| x.key == root)?;
+        remove_entity::<R>(&mut self.tree, &self.runtime, root);
+        Some(self.roots.remove(index).renderer)
+    }
+    /// the main root followed by the roots added with [`Gui::add_root`]
+    pub fn roots(&self) -> impl Iterator<Item = EntityKey> + '_ {
+        self.tree.roots()
+    }
+    /// the context to mount the widget of `root` with, see [`Gui::root_widget_context`] for the main root
+    pub fn root_context(&mut self, root: EntityKey) -> Option<WidgetContext<'_, R>> {
+        if !self.tree.roots().any(|x| x == root) {
+            return None;
         }
+        Some(WidgetContext::new(&mut self.tree, &self.runtime, root))
+    }
+    /// the renderer `root` is drawn with
+    pub fn root_renderer(&mut self, root: EntityKey) -> Option<&mut R> {
+        self.root_mut(root).map(|(renderer, _)| renderer)
+    }
+    fn root_mut(&mut self, root: EntityKey) -> Option<(&mut R, &mut Option<EntityKey>)> {
+        if root == self.tree.root() {
+            return Some((&mut self.renderer, &mut self.hovered));
+        }
+        self.roots.iter_mut().find(|x| x.key == root).map(|x| (&mut x.renderer, &mut x.hovered))
     }
     pub fn root_build_context(&mut self) -> BuildContext<'_/*, R*/> {
         let key = self.tree.root();
         BuildContext {
-            store: self.tree.store(),
+            tree: &self.tree,
+            runtime: &self.runtime,
             key,
             //_ph: PhantomData,
         }
     }
+    pub fn build_context(&mut self, entity: EntityKey) -> Option<BuildContext<'_>> {
+        if !self.tree.contains(entity) {
+            return None;
+        }
+        Some(BuildContext {
+            tree: &self.tree,
+            runtime: &self.runtime,
+            key: entity
+        })
+    }
+    pub fn runtime(&self) -> &Runtime {
+        &self.runtime
+    }
     pub fn tree(&self) -> &Treecs{
         &self.tree
     }
+    /// the renderer of the main root
     pub fn renderer(&mut self) -> &mut R {
         &mut self.renderer
     }
+    /// draws every root with its own renderer, this ends a frame
     pub fn render(&mut self){
-        let query = Query::new(&self.tree);
-        self.renderer.render(query);
+        let roots: Vec<EntityKey> = self.tree.roots().collect();
+        for root in roots {
+            self.draw(root);
+        }
+        self.last_frame = profile::end_frame();
+    }
+    /// draws one root, this ends a frame
+    pub fn render_root(&mut self, root: EntityKey) {
+        self.draw(root);
+        self.last_frame = profile::end_frame();
+    }
+    fn draw(&mut self, root: EntityKey) {
+        let _span = tracing::info_span!("render", root = devtools::entity_id(root)).entered();
+        let Some(renderer) = renderer_of(&mut self.roots, &mut self.renderer, &self.tree, root) else { return };
+        if let Some(devtools) = &self.devtools {
+            let selected = devtools.selected().filter(|x| self.tree.root_of(*x) == Some(root));
+            renderer.highlight(selected);
+        }
+        // content that is built for the size it gets is built again and laid out once more in the same frame,
+        // what still asks after that is built in the next frame
+        let needs_build = self.layout(root);
+        if !needs_build.is_empty() {
+            // the widget that built the entity builds it again
+            for x in needs_build {
+                self.runtime.mark_dirty(lifecycle_of::<R>(&self.tree, x));
+            }
+            self.rebuild_dirty();
+            for x in self.layout(root) {
+                self.runtime.mark_dirty(lifecycle_of::<R>(&self.tree, x));
+            }
+        }
+        if let Some(renderer) = renderer_of(&mut self.roots, &mut self.renderer, &self.tree, root) {
+            renderer.render(Query::new_at(&self.tree, root));
+        }
+    }
+    /// lays out `root` when its renderer has a viewport, returns the entities that asked to be built again
+    fn layout(&mut self, root: EntityKey) -> Vec<EntityKey> {
+        let Some(renderer) = renderer_of(&mut self.roots, &mut self.renderer, &self.tree, root) else { return Vec::new() };
+        let Some(viewport) = renderer.viewport() else { return Vec::new() };
+        let _phase = profile::enter(Phase::Layout);
+        layout::layout_root(&self.tree, root, viewport, renderer.text_measurer())
+    }
+    /// runs `f` as the present phase of the last frame, backends hand the drawn frame to the window or the browser in it
+    pub fn present<T>(&mut self, f: impl FnOnce(&mut Self) -> T) -> T {
+        let presented = {
+            let _phase = profile::enter(Phase::Present);
+            f(self)
+        };
+        self.last_frame.present += profile::take(Phase::Present);
+        presented
+    }
+    /// how long the phases of the last frame took, a frame ends with every render
+    ///
+    /// the builds and mounts since the previous render count for the frame, the layout and paint are timed by
+    /// the renderer
+    pub fn last_frame_stats(&self) -> FrameStats {
+        self.last_frame
+    }
+    /// applies a state transaction to the entity that created it and rebuilds that entity, unless the transaction
+    /// was created [`StateTransaction::without_rebuild`]
+    pub fn dispatch(&mut self, transaction: &StateTransaction) {
+        let key = transaction.target();
+        if !self.tree.contains(key) {
+            return;
+        }
+        if let Some(devtools) = &mut self.devtools {
+            devtools.dispatched(Dispatched {
+                target: key,
+                widget: self.tree.get_component::<WidgetType>(key).map(|x| x.name()),
+                at: self.runtime.now(),
+            });
+        }
+        let before = transaction.snapshot()
+            .filter(|_| self.history.is_some())
+            .and_then(|snapshot| Some((snapshot, snapshot(&self.build_context(key)?)?)));
+        let applied = self.catch_panics(key, |tree, runtime| transaction.apply(&mut BuildContext {
+            tree,
+            runtime,
+            key
+        }));
+        // a transaction that panicked changed nothing to undo
+        if let (true, Some(history), Some((snapshot, before))) = (applied, &mut self.history, before) {
+            history.record(key, snapshot, before, self.runtime.now());
+        }
+        if transaction.rebuilds() {
+            self.rebuild(key);
+        }
+        self.rebuild_dirty();
+    }
+    pub fn history(&self) -> Option<&History> {
+        self.history.as_ref()
+    }
+    pub fn history_mut(&mut self) -> Option<&mut History> {
+        self.history.as_mut()
+    }
+    /// restores the states from before the last group of undoable transactions and rebuilds their entities,
+    /// returns whether there was something to undo
+    pub fn undo(&mut self) -> bool {
+        let Some(targets) = self.history.as_mut().and_then(|x| x.undo(&self.tree, &self.runtime)) else {
+            return false;
+        };
+        self.rebuild_all(targets);
+        true
+    }
+    /// applies the last undone group again, returns whether there was something to redo
+    pub fn redo(&mut self) -> bool {
+        let Some(targets) = self.history.as_mut().and_then(|x| x.redo(&self.tree, &self.runtime)) else {
+            return false;
+        };
+        self.rebuild_all(targets);
+        true
+    }
+    /// the undoable transactions until [`Gui::end_undo_group`] are undone together, groups can be nested
+    pub fn begin_undo_group(&mut self) {
+        if let Some(history) = &mut self.history {
+            history.begin_group();
+        }
+    }
+    pub fn end_undo_group(&mut self) {
+        if let Some(history) = &mut self.history {
+            history.end_group();
+        }
+    }
+    fn rebuild_all(&mut self, targets: Vec<EntityKey>) {
+        for target in targets {
+            self.runtime.mark_dirty(target);
+        }
+        self.rebuild_dirty();
+    }
+    /// dispatches the transactions queued in the runtime, like the ones finished tasks resolved into
+    ///
+    /// returns the amount of dispatched transactions
+    pub fn dispatch_pending(&mut self) -> usize {
+        let mut count = 0;
+        while let Ok(transaction) = self.transactions.try_recv() {
+            self.dispatch(&transaction);
+            count += 1;
+        }
+        if let Some(entity) = self.runtime.take_focus_request() {
+            self.set_focus(Some(entity));
+        }
+        count
+    }
+    /// delivers `event` to `target` and bubbles it up to the root, until a handler stops it
+    ///
+    /// the transactions queued by the handlers are dispatched afterwards.
+    /// returns whether a handler stopped the propagation
+    pub fn dispatch_event(&mut self, target: EntityKey, event: &Event) -> bool {
+        if !self.tree.contains(target) {
+            return false;
+        }
+        self.record(Step::Dispatch { target, event: event.clone() });
+        self.deliver(target, event)
+    }
+    fn deliver(&mut self, target: EntityKey, event: &Event) -> bool {
+        let query: Query<(EntityKey, &EventHandler), ParentIter> = Query::new_parent(&self.tree, target);
+        let mut stopped = false;
+        for (key, handler) in query {
+            let mut context = BuildContext {
+                tree: &self.tree,
+                runtime: &self.runtime,
+                key
+            };
+            if handler.handle(event, &mut context) == Propagation::Stop {
+                stopped = true;
+                break;
+            }
+        }
+        self.dispatch_pending();
+        stopped
+    }
+    /// delivers input translated by a backend to the main root, see [`Gui::handle_root_event`]
+    pub fn handle_event(&mut self, event: Event) -> bool {
+        self.handle_root_event(self.tree.root(), event)
+    }
+    /// delivers input translated by a backend for the target of `root`, pointer events go to the entity found by [`Renderer::hit_test`]
+    /// or, when it finds nothing, to the deepest entity whose [`layout::LayoutRect`] contains them
+    ///
+    /// when the pointer moves onto another entity the old one gets [`Event::PointerLeave`] and the new one [`Event::PointerEnter`].
+    /// keyboard input goes to the focused entity when it is in `root`, a Tab that no handler stopped moves the focus.
+    /// events that hit nothing and keyboard input without focus are delivered to `root`
+    pub fn handle_root_event(&mut self, root: EntityKey, event: Event) -> bool {
+        let hit = match event.position() {
+            Some(position) => match self.root_mut(root) {
+                Some((renderer, _)) => match renderer.hit_test(position) {
+                    Some(hit) => Some(hit),
+                    None => layout::hit_test(&self.tree, root, position),
+                },
+                None => return false,
+            },
+            None => None,
+        };
+        self.deliver_root_event(root, event, hit)
+    }
+    /// [`Gui::handle_root_event`] with the entity the renderer hit, a replay passes the recorded hit
+    fn deliver_root_event(&mut self, root: EntityKey, event: Event, hit: Option<EntityKey>) -> bool {
+        if !self.tree.contains(root) {
+            return false;
+        }
+        self.record(Step::Event { root, hit, event: event.clone() });
+        let Some(position) = event.position() else {
+            // a focused entity on a hidden page doesn't receive the keys anymore
+            let target = self.focused()
+                .filter(|x| is_visible(&self.tree, *x) && self.tree.root_of(*x) == Some(root))
+                .unwrap_or(root);
+            let stopped = self.deliver(target, &event);
+            if let Event::Key { key: Key::Tab, state: KeyState::Pressed, modifiers } = &event {
+                if !stopped {
+                    self.focus_next_in(root, modifiers.shift);
+                    return true;
+                }
+            }
+            return stopped;
+        };
+        let Some((_, hovered)) = self.root_mut(root) else {
+            return false;
+        };
+        let old = *hovered;
+        let hit = hit.filter(|x| self.tree.contains(*x));
+        let target = hit.unwrap_or(root);
+        if hit != old {
+            if let Some(old) = old {
+                self.deliver(old, &Event::PointerLeave { position });
+            }
+            if let Some(new) = hit {
+                self.deliver(new, &Event::PointerEnter { position });
+            }
+            if let Some((_, hovered)) = self.root_mut(root) {
+                *hovered = hit;
+            }
+        }
+        self.deliver(target, &event)
+    }
+    /// advances the active tickers to the time of the clock and rebuilds the entities that own them
+    ///
+    /// backends call this before every frame, returns the amount of rebuilt entities
+    pub fn tick(&mut self) -> usize {
+        let now = self.runtime.now();
+        let query: Query<(EntityKey, &Tickers), BreadthIter> = Query::new(&self.tree);
+        for (info, (key, tickers)) in query {
+            if info == BreadthInfo::Other && tickers.tick(now) {
+                self.runtime.mark_dirty(key);
+            }
+        }
+        let rebuilt = self.rebuild_dirty();
+        // frames that change nothing don't need to be replayed
+        if rebuilt > 0 {
+            self.record(Step::Tick);
+        }
+        rebuilt
+    }
+    /// whether a ticker is active, backends keep requesting frames while it is
+    pub fn is_animating(&self) -> bool {
+        let mut query: Query<&Tickers, BreadthIter> = Query::new(&self.tree);
+        query.any(|(_, tickers)| tickers.is_active())
+    }
+    /// dispatches the transactions of the timers that are due, returns how many fired
+    pub fn fire_timers(&mut self) -> usize {
+        let now = self.runtime.now();
+        let mut fired = 0;
+        let query: Query<(EntityKey, &Timers), BreadthIter> = Query::new(&self.tree);
+        let keys: Vec<EntityKey> = query
+            .filter(|(info, _)| *info == BreadthInfo::Other)
+            .map(|(_, (key, _))| key)
+            .collect();
+        for key in keys {
+            if let Some(timers) = self.tree.get_component_mut::<Timers>(key) {
+                fired += timers.fire(now, &self.runtime);
+            }
+        }
+        if fired > 0 {
+            self.record(Step::Timers);
+            self.dispatch_pending();
+        }
+        fired
+    }
+    /// the time until the next timer is due, backends sleep until then instead of polling
+    pub fn until_next_timer(&self) -> Option<Duration> {
+        let query: Query<&Timers, BreadthIter> = Query::new(&self.tree);
+        let deadline = query.filter_map(|(_, timers)| timers.next_deadline()).min()?;
+        Some(deadline.saturating_sub(self.runtime.now()))
+    }
+    pub fn focused(&self) -> Option<EntityKey> {
+        self.runtime.focused()
     }
+    /// moves the focus to `entity`, the [`Focusable`] listeners of the old and the new entity are notified
+    pub fn set_focus(&mut self, entity: Option<EntityKey>) {
+        let entity = entity.filter(|x| self.tree.contains(*x));
+        let old = self.runtime.focused();
+        if old == entity {
+            return;
+        }
+        self.runtime.set_focused(entity);
+        for (key, focused) in [(old, false), (entity, true)] {
+            let Some(key) = key else { continue };
+            if let Some(focusable) = self.tree.get_component::<Focusable>(key) {
+                focusable.notify(focused, &mut BuildContext {
+                    tree: &self.tree,
+                    runtime: &self.runtime,
+                    key
+                });
+            }
+        }
+        self.dispatch_pending();
+    }
+    /// moves the focus to the next focusable entity in the scope of the focused one, or the previous when `backwards`
+    pub fn focus_next(&mut self, backwards: bool) {
+        let root = self.focused().and_then(|x| self.tree.root_of(x)).unwrap_or(self.tree.root());
+        self.focus_next_in(root, backwards);
+    }
+    /// like [`Gui::focus_next`] inside of `root`, the focus enters `root` when it is in another one
+    pub fn focus_next_in(&mut self, root: EntityKey, backwards: bool) {
+        let current = self.focused().filter(|x| self.tree.root_of(*x) == Some(root));
+        let scope = current.map_or(root, |x| scope_of(&self.tree, x));
+        let order = traversal_order(&self.tree, scope);
+        if order.is_empty() {
+            return;
+        }
+        let position = current.and_then(|x| order.iter().position(|y| *y == x));
+        let next = match (position, backwards) {
+            (None, false) => 0,
+            (None, true) => order.len() - 1,
+            (Some(x), false) => (x + 1) % order.len(),
+            (Some(x), true) => (x + order.len() - 1) % order.len(),
+        };
+        self.set_focus(Some(order[next]));
+    }
+    /// builds the stateful widget on `entity` again, entities in its subtree are reused where the widget type didn't change
+    pub fn rebuild(&mut self, entity: EntityKey) {
+        let Some(lifecycle) = self.tree.get_component::<Lifecycle<R>>(entity).copied() else {
+            return;
+        };
+        self.catch_panics(entity, |tree, runtime| {
+            (lifecycle.rebuild)(WidgetContext::new(tree, runtime, entity));
+        });
+    }
+    /// runs `f`, a panic in the subtree of `entity` is passed to the closest [`Catcher`] above the entity that panicked
+    /// and the entity of that catcher is rebuilt, without a catcher the panic continues. returns whether `f` finished
+    fn catch_panics<F>(&mut self, entity: EntityKey, f: F) -> bool
+    where F: FnOnce(&mut Treecs, &Runtime)
+    {
+        let depth = self.runtime.mounting_depth();
+        let Err(payload) = panic::catch_unwind(AssertUnwindSafe(|| f(&mut self.tree, &self.runtime))) else {
+            return true;
+        };
+        let failed = self.runtime.unwind_mounting(depth).unwrap_or(entity);
+        let Some(catcher) = find_catcher(&self.tree, failed) else {
+            panic::resume_unwind(payload);
+        };
+        let error = WidgetError::new(&self.tree, failed, &*payload);
+        // while the catcher rebuilds, a panic of its fallback goes to the next catcher instead of this one
+        let handler = self.tree.remove_component::<Catcher>(catcher).unwrap();
+        handler.catch(error, &mut BuildContext {
+            tree: &self.tree,
+            runtime: &self.runtime,
+            key: catcher
+        });
+        self.rebuild(catcher);
+        if self.tree.contains(catcher) {
+            self.tree.register(catcher, handler);
+        }
+        false
+    }
+    /// rebuilds the entities marked dirty in the runtime, like the ones watching a provided value that changed
+    ///
+    /// parents are rebuilt before their children, so a child rebuilt by its parent isn't built twice.
+    /// returns the amount of rebuilt entities
+    pub fn rebuild_dirty(&mut self) -> usize {
+        let mut count = 0;
+        loop {
+            // a dirty entity without a widget, like a render item that reads a signal, is rebuilt by its widget
+            let dirty: Vec<_> = self.runtime.take_dirty()
+                .into_iter()
+                .filter(|x| self.tree.contains(*x))
+                .map(|x| (x, lifecycle_of::<R>(&self.tree, x)))
+                .collect();
+            let Some(entity) = dirty.iter()
+                .map(|(_, x)| *x)
+                .min_by_key(|x| ParentIter::new(&self.tree, *x).count())
+            else {
+                return count;
+            };
+            // rebuilding the shallowest entity can clean or remove the deeper ones, they are checked again afterwards
+            for (x, _) in dirty.into_iter().filter(|(_, lifecycle)| *lifecycle != entity) {
+                self.runtime.mark_dirty(x);
+            }
+            self.rebuild(entity);
+            count += 1;
+        }
+    }
+    /// disposes the widgets in the subtree of `entity` and removes it from the tree
+    pub fn remove(&mut self, entity: EntityKey) {
+        remove_entity::<R>(&mut self.tree, &self.runtime, entity);
+    }
+}
+
+/// the renderer that draws `root`
+fn renderer_of<'a, R>(roots: &'a mut [Root<R>], main: &'a mut R, tree: &Treecs, root: EntityKey) -> Option<&'a mut R> {
+    match roots.iter_mut().find(|x| x.key == root) {
+        Some(x) => Some(&mut x.renderer),
+        None if root == tree.root() => Some(main),
+        None => None,
+    }
+}
+
+/// whether no entity from `entity` up to the root is hidden
+fn is_visible(tree: &Treecs, entity: EntityKey) -> bool {
+    ParentIter::new(tree, entity).all(|x| !tree.linkdata(x).is_some_and(|x| x.is_hidden()))
+}
+
+/// the closest entity from `entity` up that holds a stateful widget, it mounted `entity` and rebuilds it
+fn lifecycle_of<R: 'static>(tree: &Treecs, entity: EntityKey) -> EntityKey {
+    ParentIter::new(tree, entity)
+        .find(|x| tree.get_component::<Lifecycle<R>>(*x).is_some())
+        .unwrap_or(entity)
+}
+
+/// removes `entity` and its subtree, every widget is disposed before its parent and before it is dropped
+fn remove_entity<R: 'static>(tree: &mut Treecs, runtime: &Runtime, entity: EntityKey) {
+    if !tree.contains(entity) {
+        return;
+    }
+    let mut order = Vec::new();
+    let mut stack = vec![entity];
+    while let Some(key) = stack.pop() {
+        order.push(key);
+        stack.extend(tree.linkdata(key).unwrap().children().iter());
+    }
+    // reversed pre-order visits every child before its parent
+    for key in order.into_iter().rev() {
+        if runtime.focused() == Some(key) {
+            runtime.set_focused(None);
+        }
+        if let Some(lifecycle) = tree.get_component::<Lifecycle<R>>(key).copied() {
+            (lifecycle.dispose)(&mut BuildContext {
+                tree,
+                runtime,
+                key
+            });
+        }
+        if let Some(unmount) = tree.get_component::<Unmount<R>>(key).copied() {
+            (unmount.unmount)(&mut WidgetContext::new(tree, runtime, key));
+        }
+    }
+    tree.remove(entity);
 }
 
 pub struct BuildContext<'gui/*, R*/> {
-    store: &'gui ComponentStore,
+    tree: &'gui Treecs,
+    runtime: &'gui Runtime,
     key: EntityKey,
     //_ph: PhantomData<R>,
 }
 impl<'gui> BuildContext<'gui> {
     pub fn get_component<Q: Component>(&self) -> Option<&Q> {
-        self.store.get_component(self.key)
+        self.tree.get_component(self.key)
     }
     pub fn get_component_mut<Q: Component>(&self) -> Option<&mut Q>{
-        self.store.get_component_mut(self.key)
+        self.tree.get_component_mut(self.key)
     }
     pub fn insert_component<Q: Component>(&self, comp: Q) {
-        self.store.add_component(self.key, comp);
+        self.tree.store().add_component(self.key, comp);
+    }
+    pub fn key(&self) -> EntityKey {
+        self.key
+    }
+    pub fn runtime(&self) -> &'gui Runtime {
+        self.runtime
+    }
+    /// the `T` provided by the closest ancestor, this entity is rebuilt when that value changes
+    pub fn watch<T: Component>(&self) -> Option<&'gui T> {
+        let provided = find_provided::<T>(self.tree, self.key)?;
+        provided.depend(self.key);
+        Some(provided.value())
+    }
+    /// the `T` provided by the closest ancestor, without rebuilding this entity when it changes
+    pub fn read<T: Component>(&self) -> Option<&'gui T> {
+        find_provided::<T>(self.tree, self.key).map(Provided::value)
+    }
+    /// runs `future` on the executor of the gui, the transaction it resolves into is dispatched by [`Gui::dispatch_pending`]
+    ///
+    /// the task is aborted when this entity is removed from the tree
+    pub fn spawn<F>(&self, future: F)
+    where F: Future<Output = StateTransaction> + Send + 'static
+    {
+        let executor = self.runtime.executor
+            .as_ref()
+            .expect("spawning a task requires an executor, see Gui::with_executor");
+        let sender = self.runtime.transaction_sender();
+        let handle = executor.spawn(Box::pin(async move {
+            let _ = sender.send(future.await);
+        }));
+        match self.get_component_mut::<Tasks>() {
+            Some(tasks) => tasks.push(handle),
+            None => {
+                let mut tasks = Tasks::default();
+                tasks.push(handle);
+                self.insert_component(tasks);
+            }
+        }
+    }
+    /// dispatches `transaction` once after `delay`, the timer is cancelled when this entity is removed from the tree
+    pub fn set_timeout(&self, delay: Duration, transaction: StateTransaction) -> TimerHandle {
+        self.add_timer(delay, None, transaction)
+    }
+    /// dispatches `transaction` every `period` until the handle is cancelled or this entity is removed from the tree
+    pub fn set_interval(&self, period: Duration, transaction: StateTransaction) -> TimerHandle {
+        assert!(!period.is_zero(), "the period of an interval can't be zero");
+        self.add_timer(period, Some(period), transaction)
+    }
+    /// a signal holding `value`, stored on this entity
+    ///
+    /// the first build creates it, the following builds return the same signal and ignore `value`.
+    /// signals, memos and effects are told apart by the order they are created in, so create them on every build
+    /// and not conditionally
+    pub fn use_signal<T: Clone + Send + Sync + 'static>(&self, value: T) -> Signal<T> {
+        Signal::create(self, value)
+    }
+    /// a value computed by `compute` from the signals and memos it reads, see [`BuildContext::use_signal`]
+    pub fn use_memo<T, F>(&self, compute: F) -> Memo<T>
+    where T: Clone + PartialEq + Send + Sync + 'static,
+          F: Fn(&BuildContext) -> T + Send + Sync + 'static
+    {
+        Memo::create(self, compute)
+    }
+    /// runs `run` after this build is mounted and again whenever a signal or memo it read changes,
+    /// see [`BuildContext::use_signal`]
+    pub fn use_effect<F>(&self, run: F) -> Effect
+    where F: Fn(&mut BuildContext) + Send + Sync + 'static
+    {
+        Effect::create(self, run)
+    }
+    fn add_timer(&self, delay: Duration, period: Option<Duration>, transaction: StateTransaction) -> TimerHandle {
+        let deadline = self.runtime.now() + delay;
+        match self.get_component_mut::<Timers>() {
+            Some(timers) => timers.push(deadline, period, transaction),
+            None => {
+                let mut timers = Timers::default();
+                let handle = timers.push(deadline, period, transaction);
+                self.insert_component(timers);
+                handle
+            }
+        }
     }
 
 }
 pub struct WidgetContext<'gui, R> {
     tree: &'gui mut Treecs,
+    runtime: &'gui Runtime,
     key: EntityKey,
+    /// position of the next child mounted with [`WidgetContext::mount_child`]
+    cursor: usize,
     _ph: PhantomData<R>,
 }
 
+impl<'gui, R> WidgetContext<'gui, R> {
+    fn new(tree: &'gui mut Treecs, runtime: &'gui Runtime, key: EntityKey) -> Self {
+        Self {
+            tree,
+            runtime,
+            key,
+            cursor: 0,
+            _ph: PhantomData
+        }
+    }
+}
+
 impl<'gui, R: Renderer> WidgetContext<'gui, R> {
     pub fn insert_component<Q: Component>(&mut self, comp: Q){
         self.tree.register(self.key, comp);
     }
+    /// hides the subtree of this entity from the renderer, the hit testing and the focus traversal
+    ///
+    /// the entities keep their state, their tickers and their timers, like the pages below the top of a navigator
+    pub fn set_hidden(&mut self, hidden: bool) {
+        self.tree.linkdata_mut(self.key).unwrap().set_hidden(hidden);
+    }
+    /// sets how this entity sizes itself and places its children, see [`layout`]
+    pub fn set_layout(&mut self, layout: impl Layout) {
+        self.insert_component(LayoutBox(Box::new(layout)));
+    }
     pub fn mount_renderer(&mut self, renderer: R::RenderItem){
         self.tree.register(self.key, renderer);        
     }
+    /// mounts the render item of `widget` with [`Render::mount`]
+    ///
+    /// when a rebuild reuses an entity that already shows a `W` the item is patched with [`Render::update`] instead,
+    /// and [`Render::unmount`] is called when the entity is removed
+    pub fn mount_render<W>(&mut self, widget: &W)
+    where R: Render<W>,
+          W: Clone + Component
+    {
+        match self.tree.remove_component::<Mounted<W>>(self.key) {
+            Some(Mounted(old)) => R::update(&old, widget, self),
+            None => {
+                R::mount(widget, self);
+                self.insert_component(Unmount::<R>::new::<W>());
+            }
+        }
+        self.insert_component(Mounted(widget.clone()));
+    }
+    /// makes `value` available to the descendants of this entity through [`BuildContext::watch`]
+    ///
+    /// when this entity already provides a different `T` the descendants that watch it are marked dirty,
+    /// they are rebuilt by [`Gui::rebuild_dirty`]
+    pub fn provide<T: Component + PartialEq>(&mut self, value: T) {
+        match self.get_component_mut::<Provided<T>>() {
+            Some(provided) if *provided.value() == value => {}
+            Some(provided) => {
+                for dependent in provided.take_dependents() {
+                    self.runtime.mark_dirty(dependent);
+                }
+                provided.set(value);
+            }
+            None => self.insert_component(Provided::new(value)),
+        }
+    }
+    /// sets the handler for the events delivered to or bubbling through this entity, see [`Gui::dispatch_event`]
+    pub fn on_event<F>(&mut self, handler: F)
+    where F: Fn(&Event, &mut BuildContext) -> Propagation + Send + Sync + 'static
+    {
+        self.insert_component(EventHandler::new(handler));
+    }
     pub fn get_renderer(&mut self) -> Option<&mut R::RenderItem>{
         self.get_component_mut::<R::RenderItem>()
     }
@@ -87,27 +863,116 @@ impl<'gui, R: Renderer> WidgetContext<'gui, R> {
     pub fn get_component_mut<Q: Component>(&self) -> Option<&mut Q>{
         (&*self.tree).get_component_mut(self.key)
     }
+    pub fn key(&self) -> EntityKey {
+        self.key
+    }
     pub fn new_child(self) -> Self {
         let child = self.tree.add(self.key).unwrap();
-        Self {
-            tree: self.tree,
-            key: child,
-            _ph: PhantomData
-        }
+        Self::new(self.tree, self.runtime, child)
     }
     pub fn to_parent(self) -> Option<Self> {
         let linkdata = self.tree.linkdata(self.key).unwrap();
         let parent = *linkdata.parent().as_ref()?;
-        Some(Self {
-            tree: self.tree,
-            key: parent,
+        Some(Self::new(self.tree, self.runtime, parent))
+    }
+    /// mounts `widget` as the next child of this entity
+    ///
+    /// a child that already exists at this position is reused when it was mounted by the same widget type,
+    /// otherwise it is disposed and replaced by a new entity
+    pub fn mount_child<W: RenderAble<R> + ?Sized + 'static>(self, widget: &W) -> Self {
+        let Self { tree, runtime, key, cursor, .. } = self;
+        let widget_type = widget.widget_type();
+        let existing = tree.linkdata(key).unwrap().children().get_child(cursor).copied();
+        let child = match existing {
+            Some(child) if tree.get_component::<WidgetType>(child) == Some(&widget_type) => child,
+            Some(child) => {
+                remove_entity::<R>(tree, runtime, child);
+                tree.insert(key, cursor).unwrap()
+            }
+            None => tree.add(key).unwrap()
+        };
+        tree.register(child, widget_type);
+        // the children are mounted in the phase of their parent
+        let _phase = (!profile::is_in(Phase::Mount))
+            .then(|| profile::enter_span(Phase::Mount, tracing::info_span!("mount", widget = widget_type.name())));
+        runtime.push_mounting(child);
+        let context = widget.mount(Self::new(tree, runtime, child)).remove_unmounted_children();
+        runtime.pop_mounting();
+        Self {
+            tree: context.tree,
+            runtime,
+            key,
+            cursor: cursor + 1,
             _ph: PhantomData
-        })
+        }
+    }
+    /// mounts `widget` like [`WidgetContext::mount_child`] but catches a panic while building or mounting it
+    ///
+    /// the partially mounted child is removed, so the next child is mounted in its place, like a fallback
+    pub fn try_mount_child<W: RenderAble<R> + ?Sized + 'static>(self, widget: &W) -> (Self, Option<WidgetError>) {
+        let Self { tree, runtime, key, cursor, .. } = self;
+        let depth = runtime.mounting_depth();
+        let mounted = panic::catch_unwind(AssertUnwindSafe(|| {
+            let context = WidgetContext::<R>::new(&mut *tree, runtime, key);
+            context.with_cursor(cursor).mount_child(widget).cursor
+        }));
+        let (cursor, error) = match mounted {
+            Ok(cursor) => (cursor, None),
+            Err(payload) => {
+                let failed = runtime.unwind_mounting(depth).unwrap_or(key);
+                let error = WidgetError::new(tree, failed, &*payload);
+                if let Some(child) = tree.linkdata(key).unwrap().children().get_child(cursor).copied() {
+                    remove_entity::<R>(tree, runtime, child);
+                }
+                (cursor, Some(error))
+            }
+        };
+        (Self::new(tree, runtime, key).with_cursor(cursor), error)
+    }
+    fn with_cursor(mut self, cursor: usize) -> Self {
+        self.cursor = cursor;
+        self
+    }
+    /// removes the children that were not mounted again since this context was created
+    pub fn remove_unmounted_children(self) -> Self {
+        let stale: Vec<_> = self.tree.linkdata(self.key).unwrap()
+            .children()
+            .iter()
+            .skip(self.cursor)
+            .copied()
+            .collect();
+        for child in stale {
+            remove_entity::<R>(self.tree, self.runtime, child);
+        }
+        self
+    }
+    /// mounts a stateful widget on this entity
+    ///
+    /// the widget and its state are stored as components, when the entity already holds a widget of this type
+    /// the state is kept and [`Widget::did_update_widget`] is called instead of [`Widget::init_state`]
+    pub fn mount_widget<W>(self, widget: &W) -> Self
+    where W: Widget<R> + Clone + Component
+    {
+        let old = self.tree.remove_component::<W>(self.key);
+        self.tree.register(self.key, widget.clone());
+        match &old {
+            Some(old) => widget.did_update_widget(old, &mut self.get_buildcontext()),
+            None => {
+                self.tree.register(self.key, widget.create_state());
+                self.tree.register(self.key, Lifecycle::<R>::new::<W>());
+            }
+        }
+        let context = (Lifecycle::<R>::new::<W>().rebuild)(self);
+        if old.is_none() {
+            widget.init_state(&mut context.get_buildcontext());
+        }
+        context
     }
     pub fn get_buildcontext(&self) -> BuildContext<'_> {
         BuildContext {
             key: self.key,
-            store: self.tree.store()
+            tree: &*self.tree,
+            runtime: self.runtime
         }
     }
 }
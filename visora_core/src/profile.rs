@@ -0,0 +1,187 @@
+use std::{cell::RefCell, time::{Duration, Instant}};
+
+use tracing::span::EnteredSpan;
+
+/// a part of a frame, the time spent in it is summed up in [`FrameStats`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Phase {
+    /// the `build` of stateful widgets
+    Build,
+    /// mounting the built widgets and their render items
+    Mount,
+    /// computing the size and position of the render items
+    Layout,
+    /// drawing the render items, or writing the html for ssr
+    Paint,
+    /// handing the drawn frame to the window or the browser, see [`crate::Gui::present`]
+    Present,
+}
+impl Phase {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Phase::Build => "build",
+            Phase::Mount => "mount",
+            Phase::Layout => "layout",
+            Phase::Paint => "paint",
+            Phase::Present => "present",
+        }
+    }
+}
+
+/// how long the phases of a frame took, see [`crate::Gui::last_frame_stats`]
+///
+/// a phase that runs inside another one, like the build of a child while its parent mounts, is only counted for
+/// the inner phase, so the phases add up to the time of the frame
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FrameStats {
+    pub build: Duration,
+    pub mount: Duration,
+    pub layout: Duration,
+    pub paint: Duration,
+    pub present: Duration,
+    /// how many stateful widgets were built
+    pub builds: usize,
+}
+impl FrameStats {
+    pub fn get(&self, phase: Phase) -> Duration {
+        match phase {
+            Phase::Build => self.build,
+            Phase::Mount => self.mount,
+            Phase::Layout => self.layout,
+            Phase::Paint => self.paint,
+            Phase::Present => self.present,
+        }
+    }
+    fn get_mut(&mut self, phase: Phase) -> &mut Duration {
+        match phase {
+            Phase::Build => &mut self.build,
+            Phase::Mount => &mut self.mount,
+            Phase::Layout => &mut self.layout,
+            Phase::Paint => &mut self.paint,
+            Phase::Present => &mut self.present,
+        }
+    }
+    /// the time of all phases together
+    pub fn total(&self) -> Duration {
+        self.build + self.mount + self.layout + self.paint + self.present
+    }
+}
+
+/// the frame being timed on this thread
+#[derive(Default)]
+struct Frame {
+    stats: FrameStats,
+    /// the phases that were entered and not left yet, with the time the innermost was started or resumed at
+    running: Vec<(Phase, Instant)>,
+}
+
+thread_local! {
+    static FRAME: RefCell<Frame> = RefCell::default();
+}
+
+/// times a phase and traces it as a span until it is dropped, see [`enter`]
+#[must_use = "the phase ends when the guard is dropped"]
+pub struct PhaseGuard {
+    _span: EnteredSpan,
+}
+impl Drop for PhaseGuard {
+    fn drop(&mut self) {
+        FRAME.with_borrow_mut(|frame| {
+            let now = Instant::now();
+            if let Some((phase, start)) = frame.running.pop() {
+                *frame.stats.get_mut(phase) += now - start;
+            }
+            // the outer phase continues from here
+            if let Some((_, start)) = frame.running.last_mut() {
+                *start = now;
+            }
+        });
+    }
+}
+
+/// starts timing `phase` on this thread, it ends when the guard is dropped
+///
+/// backends enter the layout, paint and present phases, the gui enters the build and mount phases itself.
+/// the time is added to the frame that ends with the next render of a gui on this thread
+pub fn enter(phase: Phase) -> PhaseGuard {
+    enter_span(phase, tracing::info_span!("phase", name = phase.name()))
+}
+/// like [`enter`], with a span that describes the phase better
+pub fn enter_span(phase: Phase, span: tracing::Span) -> PhaseGuard {
+    FRAME.with_borrow_mut(|frame| {
+        let now = Instant::now();
+        // the outer phase is paused while the inner one runs
+        if let Some((outer, start)) = frame.running.last() {
+            let elapsed = now - *start;
+            *frame.stats.get_mut(*outer) += elapsed;
+        }
+        frame.running.push((phase, now));
+    });
+    PhaseGuard { _span: span.entered() }
+}
+/// whether `phase` is the innermost phase running on this thread
+pub(crate) fn is_in(phase: Phase) -> bool {
+    FRAME.with_borrow(|frame| frame.running.last().is_some_and(|(x, _)| *x == phase))
+}
+pub(crate) fn count_build() {
+    FRAME.with_borrow_mut(|frame| frame.stats.builds += 1);
+}
+/// ends the frame, the phases that are still running count for the next one
+pub(crate) fn end_frame() -> FrameStats {
+    FRAME.with_borrow_mut(|frame| {
+        let now = Instant::now();
+        if let Some((outer, start)) = frame.running.last_mut() {
+            *frame.stats.get_mut(*outer) += now - *start;
+            *start = now;
+        }
+        std::mem::take(&mut frame.stats)
+    })
+}
+/// takes the time spent in `phase` since the end of the last frame
+pub(crate) fn take(phase: Phase) -> Duration {
+    FRAME.with_borrow_mut(|frame| std::mem::take(frame.stats.get_mut(phase)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{thread::sleep, time::{Duration, Instant}};
+
+    use crate::{test_utils::{Label, TestRenderer}, Gui};
+
+    use super::{enter, end_frame, Phase};
+
+    #[test]
+    fn nested_phases_are_counted_once() {
+        end_frame();
+        let started = Instant::now();
+        {
+            let _mount = enter(Phase::Mount);
+            sleep(Duration::from_millis(5));
+            let _build = enter(Phase::Build);
+            sleep(Duration::from_millis(20));
+        }
+        let elapsed = started.elapsed();
+        let stats = end_frame();
+        assert!(stats.mount >= Duration::from_millis(5));
+        assert!(stats.build >= Duration::from_millis(20));
+        // the build inside the mount isn't counted for the mount as well
+        assert_eq!(stats.total(), stats.mount + stats.build);
+        assert!(stats.total() <= elapsed);
+    }
+    #[test]
+    fn gui_keeps_the_last_frame() {
+        let mut gui = Gui::new(TestRenderer::new());
+        gui.root_widget_context().mount_child(&Label::new("a"));
+        gui.render();
+        let first = gui.last_frame_stats();
+        assert!(first.mount > Duration::ZERO);
+        let presented = gui.present(|_| {
+            sleep(Duration::from_millis(2));
+            7
+        });
+        assert_eq!(presented, 7);
+        assert!(gui.last_frame_stats().present >= Duration::from_millis(2));
+        gui.render();
+        assert_eq!(gui.last_frame_stats().mount, Duration::ZERO);
+    }
+}
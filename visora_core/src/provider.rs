@@ -0,0 +1,121 @@
+use std::{collections::HashSet, sync::Mutex};
+
+use crate::{treecs::{iterators::parent::ParentIter, query::Query, EntityKey, Treecs}, Component};
+
+/// component with a value made available to the subtree of an entity, see [`crate::WidgetContext::provide`]
+pub struct Provided<T> {
+    value: T,
+    /// entities that watched this value since it last changed, they are rebuilt when it changes and watch it again
+    dependents: Mutex<HashSet<EntityKey>>,
+}
+impl<T> Provided<T> {
+    pub(crate) fn new(value: T) -> Self {
+        Self {
+            value,
+            dependents: Mutex::new(HashSet::new()),
+        }
+    }
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+    pub(crate) fn set(&mut self, value: T) {
+        self.value = value;
+    }
+    pub(crate) fn depend(&self, entity: EntityKey) {
+        self.dependents.lock().unwrap().insert(entity);
+    }
+    /// the dependents are taken, so removed entities and the ones that stopped watching are dropped
+    pub(crate) fn take_dependents(&self) -> HashSet<EntityKey> {
+        std::mem::take(&mut *self.dependents.lock().unwrap())
+    }
+}
+
+/// the closest ancestor of `entity` that provides a `T`, `entity` itself is not included
+pub(crate) fn find_provided<T: Component>(tree: &Treecs, entity: EntityKey) -> Option<&Provided<T>> {
+    let parent = (*tree.linkdata(entity)?.parent())?;
+    let mut query: Query<&Provided<T>, ParentIter> = Query::new_parent(tree, parent);
+    query.next()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        test_utils::{Label, Log, TestRenderer, Watcher},
+        Gui,
+    };
+
+    use super::Provided;
+
+    #[test]
+    fn watch_finds_closest_ancestor() {
+        let mut gui = Gui::new(TestRenderer::new());
+        let log = Log::default();
+        let mut context = gui.root_widget_context();
+        context.provide(1u32);
+        let mut context = context.new_child();
+        context.provide(2u32);
+        context.mount_child(&Watcher::new("watcher", log.clone()));
+        gui.render();
+
+        assert_eq!(gui.renderer().rendered(), vec!["2"]);
+        assert_eq!(log.lock().unwrap().as_slice(), ["build watcher"]);
+    }
+    #[test]
+    fn changed_value_rebuilds_dependents() {
+        let mut gui = Gui::new(TestRenderer::new());
+        let log = Log::default();
+        let mut context = gui.root_widget_context();
+        context.provide(1u32);
+        context
+            .mount_child(&Watcher::new("watcher", log.clone()))
+            .mount_child(&Label::new("static"));
+
+        gui.root_widget_context().provide(1u32);
+        assert_eq!(gui.rebuild_dirty(), 0);
+
+        gui.root_widget_context().provide(5u32);
+        assert_eq!(gui.rebuild_dirty(), 1);
+        gui.render();
+        assert_eq!(gui.renderer().rendered(), vec!["5", "static"]);
+        assert_eq!(log.lock().unwrap().as_slice(), ["build watcher", "build watcher"]);
+    }
+    #[test]
+    fn read_does_not_depend() {
+        let mut gui = Gui::new(TestRenderer::new());
+        let log = Log::default();
+        let mut context = gui.root_widget_context();
+        context.provide(1u32);
+        context.mount_child(&Watcher::new("reader", log.clone()).reading());
+
+        gui.root_widget_context().provide(2u32);
+        assert_eq!(gui.rebuild_dirty(), 0);
+        assert_eq!(log.lock().unwrap().as_slice(), ["build reader"]);
+    }
+    #[test]
+    fn missing_value_is_none() {
+        let mut gui = Gui::new(TestRenderer::new());
+        gui.root_widget_context().mount_child(&Watcher::new("watcher", Log::default()));
+        gui.render();
+        assert_eq!(gui.renderer().rendered(), vec!["none"]);
+    }
+    #[test]
+    fn dependents_are_dropped_when_the_value_changes() {
+        let mut gui = Gui::new(TestRenderer::new());
+        let log = Log::default();
+        let mut context = gui.root_widget_context();
+        context.provide(1u32);
+        context.mount_child(&Watcher::new("watcher", log.clone()));
+        let watcher = *gui.tree().linkdata(gui.tree().root()).unwrap().children().get_left().unwrap();
+        let dependents = |gui: &Gui<TestRenderer>| gui.tree().get_component::<Provided<u32>>(gui.tree().root()).unwrap().dependents.lock().unwrap().len();
+        assert_eq!(dependents(&gui), 1);
+
+        gui.root_widget_context().provide(2u32);
+        assert_eq!(gui.rebuild_dirty(), 1);
+        assert_eq!(dependents(&gui), 1, "the rebuilt watcher watches again");
+
+        gui.remove(watcher);
+        gui.root_widget_context().provide(3u32);
+        assert_eq!(gui.rebuild_dirty(), 0);
+        assert_eq!(dependents(&gui), 0, "the removed watcher is dropped");
+    }
+}
@@ -0,0 +1,370 @@
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+    time::Duration,
+};
+
+use crate::{
+    devtools::{entity_from_id, entity_id},
+    event::{Event, Key, KeyState, Modifiers, Point, PointerButton},
+    layout::Size,
+    treecs::EntityKey,
+};
+
+/// something a [`crate::Gui`] was told to do, in the order it happened
+///
+/// entity keys are handed out in the same order when the same steps are replayed on the same widgets,
+/// so the recorded keys point to the same entities
+#[derive(Debug, Clone, PartialEq)]
+pub enum Step {
+    /// input for a root, see [`crate::Gui::handle_root_event`], with the entity the renderer hit at the time
+    Event { root: EntityKey, hit: Option<EntityKey>, event: Event },
+    /// input for an entity that the backend found itself, see [`crate::Gui::dispatch_event`]
+    Dispatch { target: EntityKey, event: Event },
+    /// timers fired, see [`crate::Gui::fire_timers`]
+    Timers,
+    /// an animation frame rebuilt entities, see [`crate::Gui::tick`]
+    Tick,
+    /// something only the app knows how to repeat, like opening a tab, see [`crate::Gui::record_mark`]
+    Mark(String),
+}
+
+/// writes the steps of a gui to a file, one line each, see [`crate::Gui::with_recorder`]
+///
+/// lines have the form `micros|kind|args..`, with the time of the gui clock in microseconds:
+/// - `micros|event|root|hit or -|event..`, `micros|dispatch|target|event..`
+/// - `micros|timers`, `micros|tick`, `micros|mark|text`
+///
+/// events are `pointerdown|x|y|button`, `pointerup|x|y|button`, `pointermove|x|y`, `pointerenter|x|y`,
+/// `pointerleave|x|y`, `scroll|x|y|dx|dy`, `key|down or up|shift|ctrl|alt|meta|key` and `text|data`
+pub struct Recorder {
+    out: Box<dyn Write + Send + Sync>,
+}
+impl Recorder {
+    pub fn new(out: impl Write + Send + Sync + 'static) -> Self {
+        Self { out: Box::new(out) }
+    }
+    /// records to a new file at `path`, an existing file is truncated
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(Self::new(BufWriter::new(File::create(path)?)))
+    }
+    /// writes the step and flushes it, so a crash keeps everything up to it
+    pub(crate) fn record(&mut self, at: Duration, step: &Step) {
+        let line = format!("{}|{}\n", at.as_micros(), encode(step));
+        if let Err(error) = self.out.write_all(line.as_bytes()).and_then(|_| self.out.flush()) {
+            tracing::warn!(%error, "could not record a step");
+        }
+    }
+}
+
+/// the steps read from a recording, replayed with [`crate::Gui::replay`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Session {
+    pub steps: Vec<(Duration, Step)>,
+}
+impl Session {
+    /// parses a recording, returns the number of the first line that isn't a step
+    pub fn parse(recording: &str) -> Result<Self, usize> {
+        let steps = recording
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.is_empty())
+            .map(|(number, line)| decode(line).ok_or(number + 1))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { steps })
+    }
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::parse(&std::fs::read_to_string(path)?).map_err(|line| {
+            io::Error::new(io::ErrorKind::InvalidData, format!("line {line} of the recording is not a step"))
+        })
+    }
+}
+
+fn key(key: EntityKey) -> String {
+    entity_id(key).to_string()
+}
+fn parse_key(text: &str) -> Option<EntityKey> {
+    Some(entity_from_id(text.parse().ok()?))
+}
+/// keeps a recorded text on one line
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('\n', "\\n").replace('\r', "\\r")
+}
+fn unescape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        out.push(match (c, c == '\\') {
+            (_, true) => match chars.next() {
+                Some('n') => '\n',
+                Some('r') => '\r',
+                Some(x) => x,
+                None => '\\',
+            },
+            (c, false) => c,
+        });
+    }
+    out
+}
+
+fn encode(step: &Step) -> String {
+    match step {
+        Step::Event { root, hit, event } => {
+            format!("event|{}|{}|{}", key(*root), hit.map(key).unwrap_or("-".to_owned()), encode_event(event))
+        }
+        Step::Dispatch { target, event } => format!("dispatch|{}|{}", key(*target), encode_event(event)),
+        Step::Timers => "timers".to_owned(),
+        Step::Tick => "tick".to_owned(),
+        Step::Mark(text) => format!("mark|{}", escape(text)),
+    }
+}
+fn decode(line: &str) -> Option<(Duration, Step)> {
+    let mut parts = line.splitn(3, '|');
+    let at = Duration::from_micros(parts.next()?.parse().ok()?);
+    let kind = parts.next()?;
+    let args = parts.next().unwrap_or_default();
+    let step = match kind {
+        "event" => {
+            let mut args = args.splitn(3, '|');
+            let root = parse_key(args.next()?)?;
+            let hit = match args.next()? {
+                "-" => None,
+                x => Some(parse_key(x)?),
+            };
+            Step::Event { root, hit, event: decode_event(args.next()?)? }
+        }
+        "dispatch" => {
+            let (target, event) = args.split_once('|')?;
+            Step::Dispatch { target: parse_key(target)?, event: decode_event(event)? }
+        }
+        "timers" => Step::Timers,
+        "tick" => Step::Tick,
+        "mark" => Step::Mark(unescape(args)),
+        _ => return None,
+    };
+    Some((at, step))
+}
+
+fn encode_event(event: &Event) -> String {
+    let point = |x: &Point| format!("{}|{}", x.x, x.y);
+    match event {
+        Event::PointerDown { position, button } => format!("pointerdown|{}|{}", point(position), encode_button(*button)),
+        Event::PointerUp { position, button } => format!("pointerup|{}|{}", point(position), encode_button(*button)),
+        Event::PointerMove { position } => format!("pointermove|{}", point(position)),
+        Event::PointerEnter { position } => format!("pointerenter|{}", point(position)),
+        Event::PointerLeave { position } => format!("pointerleave|{}", point(position)),
+        Event::Scroll { position, delta } => format!("scroll|{}|{}", point(position), point(delta)),
+        Event::Scrolled { offset, viewport } => format!("scrolled|{}|{}|{}", point(offset), viewport.width, viewport.height),
+        Event::Key { key, state, modifiers } => {
+            let flag = |x: bool| if x { "1" } else { "0" };
+            format!(
+                "key|{}|{}|{}|{}|{}|{}",
+                match state {
+                    KeyState::Pressed => "down",
+                    KeyState::Released => "up",
+                },
+                flag(modifiers.shift),
+                flag(modifiers.ctrl),
+                flag(modifiers.alt),
+                flag(modifiers.meta),
+                encode_key(key)
+            )
+        }
+        Event::TextInput(text) => format!("text|{}", escape(text)),
+    }
+}
+fn decode_event(text: &str) -> Option<Event> {
+    let (kind, args) = text.split_once('|')?;
+    if kind == "text" {
+        return Some(Event::TextInput(unescape(args)));
+    }
+    // the key is last, a character key can be a `|`
+    let args: Vec<&str> = match kind {
+        "key" => args.splitn(6, '|').collect(),
+        _ => args.split('|').collect(),
+    };
+    let number = |idx: usize| args.get(idx)?.parse::<f32>().ok();
+    let point = |idx: usize| Some(Point::new(number(idx)?, number(idx + 1)?));
+    let flag = |idx: usize| args.get(idx) == Some(&"1");
+    Some(match kind {
+        "pointerdown" => Event::PointerDown { position: point(0)?, button: decode_button(args.get(2)?)? },
+        "pointerup" => Event::PointerUp { position: point(0)?, button: decode_button(args.get(2)?)? },
+        "pointermove" => Event::PointerMove { position: point(0)? },
+        "pointerenter" => Event::PointerEnter { position: point(0)? },
+        "pointerleave" => Event::PointerLeave { position: point(0)? },
+        "scroll" => Event::Scroll { position: point(0)?, delta: point(2)? },
+        "scrolled" => Event::Scrolled { offset: point(0)?, viewport: Size::new(number(2)?, number(3)?) },
+        "key" => Event::Key {
+            state: match *args.first()? {
+                "down" => KeyState::Pressed,
+                "up" => KeyState::Released,
+                _ => return None,
+            },
+            modifiers: Modifiers { shift: flag(1), ctrl: flag(2), alt: flag(3), meta: flag(4) },
+            key: decode_key(args.get(5)?),
+        },
+        _ => return None,
+    })
+}
+
+fn encode_button(button: PointerButton) -> String {
+    match button {
+        PointerButton::Primary => "primary".to_owned(),
+        PointerButton::Secondary => "secondary".to_owned(),
+        PointerButton::Middle => "middle".to_owned(),
+        PointerButton::Other(x) => x.to_string(),
+    }
+}
+fn decode_button(text: &str) -> Option<PointerButton> {
+    Some(match text {
+        "primary" => PointerButton::Primary,
+        "secondary" => PointerButton::Secondary,
+        "middle" => PointerButton::Middle,
+        x => PointerButton::Other(x.parse().ok()?),
+    })
+}
+
+fn encode_key(key: &Key) -> String {
+    match key {
+        Key::Character(x) => format!("char:{}", escape(x)),
+        x => format!("{x:?}"),
+    }
+}
+fn decode_key(text: &str) -> Key {
+    if let Some(x) = text.strip_prefix("char:") {
+        return Key::Character(unescape(x));
+    }
+    match text {
+        "Enter" => Key::Enter,
+        "Space" => Key::Space,
+        "Tab" => Key::Tab,
+        "Escape" => Key::Escape,
+        "Backspace" => Key::Backspace,
+        "Delete" => Key::Delete,
+        "ArrowUp" => Key::ArrowUp,
+        "ArrowDown" => Key::ArrowDown,
+        "ArrowLeft" => Key::ArrowLeft,
+        "ArrowRight" => Key::ArrowRight,
+        "Home" => Key::Home,
+        "End" => Key::End,
+        _ => Key::Unidentified,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::Write,
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    use crate::{
+        animation::clock::ManualClock,
+        event::{Event, EventHandler, Key, KeyState, Modifiers, Point, PointerButton, Propagation},
+        layout::Size,
+        state::StateTransaction,
+        test_utils::{Dynamic, TestRenderer},
+        treecs::EntityKey,
+        Gui,
+    };
+
+    use super::{decode, encode, entity_from_id, Recorder, Session, Step};
+
+    /// a recording that can be read while the gui still owns the recorder
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// an app whose root counts the events it gets and a timer that adds ten, each mark adds a label
+    fn app(gui: &mut Gui<TestRenderer>) {
+        let mut context = gui.root_widget_context();
+        context.insert_component(0u32);
+        context.insert_component(EventHandler::new(|event, context| {
+            let key = context.key();
+            let text = matches!(event, Event::TextInput(_));
+            context.runtime().queue(StateTransaction::new(key, move |context| {
+                *context.get_component_mut::<u32>().unwrap() += if text { 100 } else { 1 };
+            }));
+            Propagation::Stop
+        }));
+        let root = gui.tree().root();
+        let context = gui.build_context(root).unwrap();
+        context.set_timeout(Duration::from_millis(100), StateTransaction::new(root, |context| {
+            *context.get_component_mut::<u32>().unwrap() += 10;
+        }));
+    }
+    fn open(gui: &mut Gui<TestRenderer>, mark: &str) {
+        let count = gui.tree().linkdata(gui.tree().root()).unwrap().children().iter().count();
+        let mut context = gui.root_widget_context();
+        context.cursor = count;
+        context.mount_child(&Dynamic { text: mark.to_owned() });
+    }
+    fn snapshot(gui: &mut Gui<TestRenderer>) -> (Option<u32>, Vec<String>) {
+        gui.render();
+        let root = gui.tree().root();
+        (gui.tree().get_component::<u32>(root).copied(), gui.renderer().rendered().iter().map(|x| x.to_string()).collect())
+    }
+
+    #[test]
+    fn replays_produce_the_same_tree() {
+        let buffer = Buffer::default();
+        let clock = ManualClock::new();
+        let mut gui = Gui::new(TestRenderer::new()).with_clock(clock.clone()).with_recorder(Recorder::new(buffer.clone()));
+        app(&mut gui);
+        gui.record_mark("first | tab\nline");
+        open(&mut gui, "first | tab\nline");
+        let label = *gui.tree().linkdata(gui.tree().root()).unwrap().children().get_left().unwrap();
+        gui.renderer().areas = vec![(label, 0.0..10.0)];
+
+        gui.handle_event(Event::PointerDown { position: Point::new(5.0, 1.5), button: PointerButton::Other(7) });
+        clock.advance(Duration::from_millis(150));
+        gui.fire_timers();
+        gui.dispatch_event(label, &Event::TextInput("a|b".to_owned()));
+        gui.record_mark("second");
+        open(&mut gui, "second");
+        let recorded = snapshot(&mut gui);
+        assert_eq!(recorded, (Some(112), vec!["first | tab\nline".to_owned(), "second".to_owned()]));
+
+        let session = Session::parse(&String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap()).unwrap();
+        assert_eq!(session.steps.len(), 5, "the pointer enter sent by the gui is not a step");
+        assert_eq!(session.steps[2], (Duration::from_millis(150), Step::Timers));
+
+        // the replay has no hit areas, the recorded hit is used
+        let clock = ManualClock::new();
+        let mut replay = Gui::new(TestRenderer::new()).with_clock(clock.clone());
+        app(&mut replay);
+        replay.replay(&session, &clock, open);
+        assert_eq!(snapshot(&mut replay), recorded);
+    }
+    #[test]
+    fn steps_survive_a_round_trip() {
+        let key: EntityKey = entity_from_id(42 | (3 << 32));
+        let steps = [
+            Step::Event {
+                root: key,
+                hit: None,
+                event: Event::Key { key: Key::Character("|".to_owned()), state: KeyState::Released, modifiers: Modifiers { ctrl: true, ..Default::default() } },
+            },
+            Step::Dispatch { target: key, event: Event::Scroll { position: Point::new(1.0, -2.5), delta: Point::new(0.0, 3.0) } },
+            Step::Dispatch { target: key, event: Event::Scrolled { offset: Point::new(0.0, 120.5), viewport: Size::new(300.0, 200.0) } },
+            Step::Dispatch { target: key, event: Event::Key { key: Key::ArrowLeft, state: KeyState::Pressed, modifiers: Modifiers::default() } },
+            Step::Mark("back\\slash".to_owned()),
+            Step::Tick,
+        ];
+        for step in steps {
+            let line = format!("7|{}", encode(&step));
+            assert_eq!(decode(&line), Some((Duration::from_micros(7), step)), "{line}");
+        }
+        assert_eq!(Session::parse("1|tick\n2|nope"), Err(2));
+    }
+}
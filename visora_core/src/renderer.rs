@@ -1,9 +1,32 @@
-use crate::{treecs::{iterators::{QueryIter, WorldIter}, query::{Query, QueryAble}}, Component};
+use crate::{event::Point, layout::{MonospaceMeasurer, Size, TextMeasurer}, treecs::{iterators::{QueryIter, WorldIter}, query::{Query, QueryAble}, EntityKey}, Component};
 
 
-pub trait Renderer{
+pub trait Renderer: 'static {
     type RenderItem: Component;
-    type QueryType<'gui>: for<'a> QueryIter<'gui, &'a Self::RenderItem> + for<'a> WorldIter<'gui, &'a Self::RenderItem>;
-    fn render<'gui>(&mut self, q: Query<'gui, &Self::RenderItem, Self::QueryType<'gui>>)
+    type QueryType<'gui>: for<'a> QueryIter<'gui, (EntityKey, &'a Self::RenderItem)> + for<'a> WorldIter<'gui, (EntityKey, &'a Self::RenderItem)>;
+    /// draws the tree, every item comes with the entity it is mounted on
+    fn render<'gui>(&mut self, q: Query<'gui, (EntityKey, &Self::RenderItem), Self::QueryType<'gui>>)
     ;
-}
\ No newline at end of file
+    /// the deepest entity drawn at `position` during the last render
+    ///
+    /// backends without their own hit testing, like the browser for ssr, keep the default
+    fn hit_test(&self, _position: Point) -> Option<EntityKey> {
+        None
+    }
+    /// the top left and bottom right corner of `entity` during the last render, shown by the devtools
+    fn bounds(&self, _entity: EntityKey) -> Option<(Point, Point)> {
+        None
+    }
+    /// outlines `entity` in the next renders, it was selected in the devtools
+    fn highlight(&mut self, _entity: Option<EntityKey>) {}
+    /// the size the root is laid out in before every render, see [`crate::layout`]
+    ///
+    /// backends that leave the layout to someone else, like the browser for ssr, keep the default and get no layout
+    fn viewport(&self) -> Option<Size> {
+        None
+    }
+    /// measures the text of the layout with the fonts of the backend
+    fn text_measurer(&self) -> &dyn TextMeasurer {
+        &MonospaceMeasurer
+    }
+}
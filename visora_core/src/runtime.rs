@@ -0,0 +1,96 @@
+use std::{collections::HashSet, sync::{mpsc::{self, Receiver, Sender}, Arc, Mutex}, time::Duration};
+
+use crate::{animation::clock::{Clock, SystemClock}, signal::Observer, state::StateTransaction, task::Executor, treecs::EntityKey};
+
+/// services of the gui that are shared with every build and widget context
+pub struct Runtime {
+    pub(crate) executor: Option<Arc<dyn Executor>>,
+    pub(crate) clock: Arc<dyn Clock>,
+    transactions: Sender<StateTransaction>,
+    /// entities that need to be rebuilt by [`crate::Gui::rebuild_dirty`]
+    dirty: Mutex<HashSet<EntityKey>>,
+    focused: Mutex<Option<EntityKey>>,
+    /// applied by [`crate::Gui::dispatch_pending`], so listeners run outside of the handler that requested it
+    focus_request: Mutex<Option<EntityKey>>,
+    /// the entities being mounted, innermost last, after a panic the last one is the entity that panicked
+    mounting: Mutex<Vec<EntityKey>>,
+    /// the memos and effects that are running, innermost last, the signals they read are recorded on them
+    observers: Mutex<Vec<Observer>>,
+}
+impl Runtime {
+    pub(crate) fn new() -> (Self, Receiver<StateTransaction>) {
+        let (transactions, receiver) = mpsc::channel();
+        let runtime = Self {
+            executor: None,
+            clock: Arc::new(SystemClock::new()),
+            transactions,
+            dirty: Mutex::new(HashSet::new()),
+            focused: Mutex::new(None),
+            focus_request: Mutex::new(None),
+            mounting: Mutex::new(Vec::new()),
+            observers: Mutex::new(Vec::new()),
+        };
+        (runtime, receiver)
+    }
+    /// a sender that queues transactions to be dispatched by [`crate::Gui::dispatch_pending`], it can be moved to other threads
+    pub fn transaction_sender(&self) -> Sender<StateTransaction> {
+        self.transactions.clone()
+    }
+    /// queues a transaction to be dispatched by [`crate::Gui::dispatch_pending`]
+    pub fn queue(&self, transaction: StateTransaction) {
+        // the receiver lives as long as the gui that owns this runtime
+        let _ = self.transactions.send(transaction);
+    }
+    /// schedules `entity` to be rebuilt by [`crate::Gui::rebuild_dirty`]
+    pub fn mark_dirty(&self, entity: EntityKey) {
+        self.dirty.lock().unwrap().insert(entity);
+    }
+    pub(crate) fn clear_dirty(&self, entity: EntityKey) {
+        self.dirty.lock().unwrap().remove(&entity);
+    }
+    pub(crate) fn take_dirty(&self) -> Vec<EntityKey> {
+        self.dirty.lock().unwrap().drain().collect()
+    }
+    /// the entity that receives the keyboard input
+    pub fn focused(&self) -> Option<EntityKey> {
+        *self.focused.lock().unwrap()
+    }
+    pub(crate) fn set_focused(&self, entity: Option<EntityKey>) {
+        *self.focused.lock().unwrap() = entity;
+    }
+    pub(crate) fn request_focus(&self, entity: EntityKey) {
+        *self.focus_request.lock().unwrap() = Some(entity);
+    }
+    pub(crate) fn take_focus_request(&self) -> Option<EntityKey> {
+        self.focus_request.lock().unwrap().take()
+    }
+    pub(crate) fn push_mounting(&self, entity: EntityKey) {
+        self.mounting.lock().unwrap().push(entity);
+    }
+    pub(crate) fn pop_mounting(&self) {
+        self.mounting.lock().unwrap().pop();
+    }
+    pub(crate) fn mounting_depth(&self) -> usize {
+        self.mounting.lock().unwrap().len()
+    }
+    /// forgets the entities a panic left on the mounting stack above `depth`, returns the one that panicked
+    pub(crate) fn unwind_mounting(&self, depth: usize) -> Option<EntityKey> {
+        let mut mounting = self.mounting.lock().unwrap();
+        let failed = mounting.get(depth..).and_then(|x| x.last().copied());
+        mounting.truncate(depth);
+        failed
+    }
+    pub(crate) fn push_observer(&self, observer: Observer) {
+        self.observers.lock().unwrap().push(observer);
+    }
+    pub(crate) fn pop_observer(&self) {
+        self.observers.lock().unwrap().pop();
+    }
+    pub(crate) fn observer(&self) -> Option<Observer> {
+        self.observers.lock().unwrap().last().copied()
+    }
+    /// the time of the frame clock, see [`crate::Gui::with_clock`]
+    pub fn now(&self) -> Duration {
+        self.clock.now()
+    }
+}
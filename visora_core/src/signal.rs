@@ -0,0 +1,438 @@
+use std::{
+    any::Any,
+    collections::HashSet,
+    marker::PhantomData,
+    sync::{atomic::{AtomicBool, AtomicUsize, Ordering}, Arc, Mutex},
+};
+
+use crate::{runtime::Runtime, state::StateTransaction, treecs::{EntityKey, Treecs}, BuildContext};
+
+type Value = Box<dyn Any + Send + Sync>;
+type Compute = dyn Fn(&BuildContext) -> Value + Send + Sync;
+type Run = dyn Fn(&mut BuildContext) + Send + Sync;
+
+/// what is notified when the value of a signal or a memo changes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum Observer {
+    /// an entity that read the value while building or mounting, it is marked dirty
+    Entity(EntityKey),
+    /// a memo or an effect, by the entity that owns it and its index in the [`Signals`] of that entity
+    Slot(EntityKey, usize),
+}
+
+enum Kind {
+    Signal,
+    Memo {
+        compute: Arc<Compute>,
+        changed: fn(&Value, &Value) -> bool,
+    },
+    Effect {
+        run: Arc<Run>,
+        stopped: AtomicBool,
+    },
+}
+
+struct Slot {
+    value: Mutex<Value>,
+    /// the observers that read the value since it last changed, they read it again when they are notified
+    observers: Mutex<HashSet<Observer>>,
+    kind: Kind,
+}
+impl Slot {
+    fn new(value: Value, kind: Kind) -> Self {
+        Self {
+            value: Mutex::new(value),
+            observers: Mutex::new(HashSet::new()),
+            kind,
+        }
+    }
+    fn track(&self, context: &BuildContext) {
+        let observer = context.runtime.observer().unwrap_or(Observer::Entity(context.key));
+        self.observers.lock().unwrap().insert(observer);
+    }
+    fn read<T: Clone + 'static>(&self) -> T {
+        self.value.lock().unwrap().downcast_ref::<T>().expect("the value has the type of its handle").clone()
+    }
+    fn take_observers(&self) -> HashSet<Observer> {
+        std::mem::take(&mut *self.observers.lock().unwrap())
+    }
+}
+
+/// component with the signals, memos and effects created while building an entity, they are dropped with it
+///
+/// they are told apart by the order they are created in, so every build has to create them in the same order
+#[derive(Default)]
+pub struct Signals {
+    slots: Mutex<Vec<Arc<Slot>>>,
+    /// the amount of slots the current build asked for
+    cursor: AtomicUsize,
+}
+impl Signals {
+    /// called before every build of the entity
+    pub(crate) fn start_build(&self) {
+        self.cursor.store(0, Ordering::Relaxed);
+    }
+    /// the next slot of the build, `create` is only called by the first build
+    fn next(&self, create: impl FnOnce() -> Slot) -> (usize, bool) {
+        let index = self.cursor.fetch_add(1, Ordering::Relaxed);
+        let mut slots = self.slots.lock().unwrap();
+        if index < slots.len() {
+            return (index, false);
+        }
+        slots.push(Arc::new(create()));
+        (index, true)
+    }
+    fn get(&self, index: usize) -> Option<Arc<Slot>> {
+        self.slots.lock().unwrap().get(index).cloned()
+    }
+}
+
+fn slot(tree: &Treecs, owner: EntityKey, index: usize) -> Option<Arc<Slot>> {
+    tree.get_component::<Signals>(owner)?.get(index)
+}
+fn next_slot(context: &BuildContext, create: impl FnOnce() -> Slot) -> (usize, bool) {
+    if context.get_component::<Signals>().is_none() {
+        context.insert_component(Signals::default());
+    }
+    context.get_component::<Signals>().unwrap().next(create)
+}
+
+/// pops the observer when a memo or an effect returns, also when it panics
+struct Observing<'a>(&'a Runtime);
+impl<'a> Observing<'a> {
+    fn new(runtime: &'a Runtime, observer: Observer) -> Self {
+        runtime.push_observer(observer);
+        Self(runtime)
+    }
+}
+impl Drop for Observing<'_> {
+    fn drop(&mut self) {
+        self.0.pop_observer();
+    }
+}
+
+/// notifies `observers` of a change, entities are marked dirty, memos are computed again and effects are queued
+fn notify(tree: &Treecs, runtime: &Runtime, observers: HashSet<Observer>) {
+    for observer in observers {
+        let (owner, index) = match observer {
+            Observer::Entity(key) => {
+                runtime.mark_dirty(key);
+                continue;
+            }
+            Observer::Slot(owner, index) => (owner, index),
+        };
+        let Some(slot) = slot(tree, owner, index) else { continue };
+        match &slot.kind {
+            Kind::Signal => {}
+            Kind::Memo { compute, changed } => {
+                let value = {
+                    let _observing = Observing::new(runtime, observer);
+                    compute(&BuildContext { tree, runtime, key: owner })
+                };
+                let mut old = slot.value.lock().unwrap();
+                // the observers of a memo are only notified when its value changed
+                if changed(&old, &value) {
+                    *old = value;
+                    drop(old);
+                    notify(tree, runtime, slot.take_observers());
+                }
+            }
+            Kind::Effect { .. } => runtime.queue(run_effect(owner, index)),
+        }
+    }
+}
+
+/// a transaction that runs the effect, it is dropped with the entity that owns it
+fn run_effect(owner: EntityKey, index: usize) -> StateTransaction {
+    StateTransaction::new(owner, move |context| {
+        let Some(slot) = slot(context.tree, owner, index) else { return };
+        let Kind::Effect { run, stopped } = &slot.kind else { return };
+        if !stopped.load(Ordering::Relaxed) {
+            let _observing = Observing::new(context.runtime, Observer::Slot(owner, index));
+            run(context);
+        }
+    })
+    .without_rebuild()
+}
+
+/// a value stored on the entity that created it, see [`BuildContext::use_signal`]
+///
+/// the entities that read it with [`Signal::get`] while building or mounting are rebuilt when it is written,
+/// their parents are not
+pub struct Signal<T> {
+    owner: EntityKey,
+    index: usize,
+    _ph: PhantomData<fn() -> T>,
+}
+impl<T> Clone for Signal<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<T> Copy for Signal<T> {}
+impl<T> PartialEq for Signal<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.owner == other.owner && self.index == other.index
+    }
+}
+impl<T: Clone + Send + Sync + 'static> Signal<T> {
+    pub(crate) fn create(context: &BuildContext, value: T) -> Self {
+        let (index, _) = next_slot(context, || Slot::new(Box::new(value), Kind::Signal));
+        Self { owner: context.key, index, _ph: PhantomData }
+    }
+    fn slot(&self, tree: &Treecs) -> Arc<Slot> {
+        slot(tree, self.owner, self.index).expect("a signal is used after its entity was removed")
+    }
+    /// the value, the entity of `context` or the memo or effect that is running depends on it
+    pub fn get(&self, context: &BuildContext) -> T {
+        let slot = self.slot(context.tree);
+        slot.track(context);
+        slot.read()
+    }
+    /// the value without depending on it, for event handlers and transactions
+    pub fn get_untracked(&self, context: &BuildContext) -> T {
+        self.slot(context.tree).read()
+    }
+    pub fn set(&self, context: &BuildContext, value: T) {
+        self.update(context, move |x| *x = value);
+    }
+    /// changes the value in place and notifies everything that read it
+    pub fn update(&self, context: &BuildContext, f: impl FnOnce(&mut T)) {
+        let slot = self.slot(context.tree);
+        f(slot.value.lock().unwrap().downcast_mut::<T>().expect("the value has the type of its handle"));
+        notify(context.tree, context.runtime, slot.take_observers());
+    }
+    /// a transaction that updates the value with `f`, for event handlers like [`Signal::update`]
+    ///
+    /// only the readers are rebuilt, not the entity that owns the signal
+    pub fn transaction(&self, f: impl Fn(&mut T) + Send + Sync + 'static) -> StateTransaction {
+        let signal = *self;
+        StateTransaction::new(self.owner, move |context| signal.update(context, &f)).without_rebuild()
+    }
+}
+
+/// a value computed from signals and other memos, see [`BuildContext::use_memo`]
+///
+/// it is computed again as soon as one of them changes, and only notifies its readers when the result differs
+pub struct Memo<T> {
+    owner: EntityKey,
+    index: usize,
+    _ph: PhantomData<fn() -> T>,
+}
+impl<T> Clone for Memo<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<T> Copy for Memo<T> {}
+impl<T: Clone + PartialEq + Send + Sync + 'static> Memo<T> {
+    pub(crate) fn create<F>(context: &BuildContext, compute: F) -> Self
+    where F: Fn(&BuildContext) -> T + Send + Sync + 'static
+    {
+        let owner = context.key;
+        let (index, created) = next_slot(context, || Slot::new(Box::new(()), Kind::Memo {
+            compute: Arc::new(move |context| Box::new(compute(context))),
+            changed: |old, new| old.downcast_ref::<T>() != new.downcast_ref::<T>(),
+        }));
+        if created {
+            let slot = slot(context.tree, owner, index).unwrap();
+            let Kind::Memo { compute, .. } = &slot.kind else { unreachable!() };
+            let value = {
+                let _observing = Observing::new(context.runtime, Observer::Slot(owner, index));
+                compute(context)
+            };
+            *slot.value.lock().unwrap() = value;
+        }
+        Self { owner, index, _ph: PhantomData }
+    }
+    /// the computed value, the entity of `context` or the memo or effect that is running depends on it
+    pub fn get(&self, context: &BuildContext) -> T {
+        let slot = slot(context.tree, self.owner, self.index).expect("a memo is used after its entity was removed");
+        slot.track(context);
+        slot.read()
+    }
+}
+
+/// a closure that runs again after the signals and memos it read changed, see [`BuildContext::use_effect`]
+#[derive(Clone, Copy, PartialEq)]
+pub struct Effect {
+    owner: EntityKey,
+    index: usize,
+}
+impl Effect {
+    pub(crate) fn create<F>(context: &BuildContext, run: F) -> Self
+    where F: Fn(&mut BuildContext) + Send + Sync + 'static
+    {
+        let owner = context.key;
+        let (index, created) = next_slot(context, || Slot::new(Box::new(()), Kind::Effect {
+            run: Arc::new(run),
+            stopped: AtomicBool::new(false),
+        }));
+        // the first run waits until the build is mounted, like the runs after a change
+        if created {
+            context.runtime.queue(run_effect(owner, index));
+        }
+        Self { owner, index }
+    }
+    /// the effect doesn't run anymore, it also stops when its entity is removed
+    pub fn stop(&self, context: &BuildContext) {
+        if let Some(Kind::Effect { stopped, .. }) = slot(context.tree, self.owner, self.index).as_deref().map(|x| &x.kind) {
+            stopped.store(true, Ordering::Relaxed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use crate::{
+        state::State,
+        test_utils::{Dynamic, Log, TestRenderer},
+        widget::{Render, RenderAble, Widget},
+        BuildContext, Gui, WidgetContext,
+    };
+
+    use super::Signal;
+
+    /// a counter whose label reads the signal, so only the label is rebuilt
+    #[derive(Clone)]
+    struct Counter {
+        signal: Arc<Mutex<Option<Signal<u32>>>>,
+        log: Log,
+    }
+    impl RenderAble<TestRenderer> for Counter {
+        fn mount<'gui>(&self, context: WidgetContext<'gui, TestRenderer>) -> WidgetContext<'gui, TestRenderer> {
+            context.mount_widget(self)
+        }
+    }
+    impl Widget<TestRenderer> for Counter {
+        type State = ();
+        fn create_state(&self) -> Self::State {}
+        fn build<'gui>(&self, _state: State<Self>, context: &mut BuildContext<'gui>) -> impl RenderAble<TestRenderer> + 'static {
+            self.log.lock().unwrap().push("build counter".to_string());
+            let count = context.use_signal(0u32);
+            *self.signal.lock().unwrap() = Some(count);
+            CountLabel { count }
+        }
+    }
+    #[derive(Clone)]
+    struct CountLabel {
+        count: Signal<u32>,
+    }
+    impl RenderAble<TestRenderer> for CountLabel {
+        fn mount<'gui>(&self, context: WidgetContext<'gui, TestRenderer>) -> WidgetContext<'gui, TestRenderer> {
+            context.mount_widget(self)
+        }
+    }
+    impl Widget<TestRenderer> for CountLabel {
+        type State = ();
+        fn create_state(&self) -> Self::State {}
+        fn build<'gui>(&self, _state: State<Self>, context: &mut BuildContext<'gui>) -> impl RenderAble<TestRenderer> + 'static {
+            Dynamic { text: format!("count {}", self.count.get(context)) }
+        }
+    }
+
+    #[test]
+    fn writes_rebuild_only_the_readers() {
+        let signal = Arc::new(Mutex::new(None));
+        let log = Log::default();
+        let mut gui = Gui::new(TestRenderer::new());
+        gui.root_widget_context().mount_child(&Counter { signal: signal.clone(), log: log.clone() });
+        gui.render();
+        let count = signal.lock().unwrap().unwrap();
+        assert_eq!(gui.renderer().rendered(), ["count 0"]);
+        assert_eq!(*log.lock().unwrap(), ["build counter"]);
+        let counter = *gui.tree().linkdata(gui.tree().root()).unwrap().children().get_left().unwrap();
+
+        count.update(&gui.build_context(counter).unwrap(), |x| *x += 1);
+        assert_eq!(gui.rebuild_dirty(), 1, "only the label is rebuilt");
+        gui.render();
+        assert_eq!(gui.renderer().rendered(), ["count 1"]);
+        assert_eq!(log.lock().unwrap().len(), 1, "the counter isn't rebuilt");
+        assert_eq!(count.get_untracked(&gui.build_context(counter).unwrap()), 1);
+        // the counter keeps its signal when it is rebuilt
+        gui.rebuild(counter);
+        assert_eq!(count.get_untracked(&gui.build_context(counter).unwrap()), 1);
+    }
+    /// a render item that reads the signal of its widget when it is mounted
+    #[derive(Clone)]
+    struct SignalItem {
+        count: Signal<u32>,
+    }
+    impl Render<SignalItem> for TestRenderer {
+        fn mount<'gui>(widget: &SignalItem, context: &mut WidgetContext<'gui, Self>) {
+            let text = format!("item {}", widget.count.get(&context.get_buildcontext()));
+            context.mount_renderer(text);
+        }
+    }
+    impl RenderAble<TestRenderer> for SignalItem {
+        fn mount<'gui>(&self, mut context: WidgetContext<'gui, TestRenderer>) -> WidgetContext<'gui, TestRenderer> {
+            context.mount_render(self);
+            context
+        }
+    }
+    #[derive(Clone)]
+    struct Owner {
+        signal: Arc<Mutex<Option<Signal<u32>>>>,
+    }
+    impl RenderAble<TestRenderer> for Owner {
+        fn mount<'gui>(&self, context: WidgetContext<'gui, TestRenderer>) -> WidgetContext<'gui, TestRenderer> {
+            context.mount_widget(self)
+        }
+    }
+    impl Widget<TestRenderer> for Owner {
+        type State = ();
+        fn create_state(&self) -> Self::State {}
+        fn build<'gui>(&self, _state: State<Self>, context: &mut BuildContext<'gui>) -> impl RenderAble<TestRenderer> + 'static {
+            let count = context.use_signal(0u32);
+            *self.signal.lock().unwrap() = Some(count);
+            SignalItem { count }
+        }
+    }
+
+    #[test]
+    fn render_items_that_read_a_signal_rebuild_their_widget() {
+        let signal = Arc::new(Mutex::new(None));
+        let mut gui = Gui::new(TestRenderer::new());
+        gui.root_widget_context().mount_child(&Owner { signal: signal.clone() });
+        gui.render();
+        let count = signal.lock().unwrap().unwrap();
+        let owner = *gui.tree().linkdata(gui.tree().root()).unwrap().children().get_left().unwrap();
+
+        count.set(&gui.build_context(owner).unwrap(), 1);
+        assert_eq!(gui.rebuild_dirty(), 1);
+        gui.render();
+        assert_eq!(gui.renderer().rendered(), ["item 1"]);
+    }
+    #[test]
+    fn memos_and_effects_follow_their_signals() {
+        let mut gui = Gui::new(TestRenderer::new());
+        let root = gui.tree().root();
+        let context = gui.build_context(root).unwrap();
+        let count = context.use_signal(1u32);
+        let even = context.use_memo(move |context| count.get(context).is_multiple_of(2));
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let effect_seen = seen.clone();
+        let effect = context.use_effect(move |context| effect_seen.lock().unwrap().push(even.get(context)));
+        gui.dispatch_pending();
+        assert_eq!(*seen.lock().unwrap(), [false]);
+
+        let context = gui.build_context(root).unwrap();
+        count.set(&context, 3);
+        gui.dispatch_pending();
+        assert_eq!(*seen.lock().unwrap(), [false], "the memo didn't change, the effect doesn't run");
+
+        let context = gui.build_context(root).unwrap();
+        count.set(&context, 4);
+        gui.dispatch_pending();
+        assert_eq!(*seen.lock().unwrap(), [false, true]);
+
+        let context = gui.build_context(root).unwrap();
+        effect.stop(&context);
+        count.set(&context, 5);
+        gui.dispatch_pending();
+        assert_eq!(*seen.lock().unwrap(), [false, true]);
+        assert!(!even.get(&gui.build_context(root).unwrap()));
+    }
+}
@@ -1,6 +1,6 @@
-use std::marker::PhantomData;
+use std::{marker::PhantomData, sync::Arc};
 
-use crate::{BuildContext, Component, widget::{Render, Widget}};
+use crate::{BuildContext, Component, history::{snapshot, Snapshot}, treecs::EntityKey, widget::{Render, Widget}};
 
 
 
@@ -20,23 +20,102 @@ where
         }
     }
     pub fn update<F, R>(&self, f: F) -> StateTransaction
-    where W: Widget<R>, F: Fn(&W, &mut <W as Widget<R>>::State) + 'static  {
-        let a = move |ctx: &mut BuildContext| {
-            let state = ctx.get_component_mut::<<W as Widget<R>>::State>().expect("every element always has a state");
-            let data = ctx.get_component::<W>().expect("every element always has a widget");
-            (f)(data, state)
-        };
-        StateTransaction{
-            func: Box::new(a)
-        }
+    where W: Widget<R>, F: Fn(&W, &mut <W as Widget<R>>::State) + Send + Sync + 'static  {
+        self.handle().update(f)
+    }
+    /// like [`State::update`], the change is recorded by the history of the gui so it can be undone
+    pub fn update_undoable<F, R>(&self, f: F) -> StateTransaction
+    where W: Widget<R>, <W as Widget<R>>::State: Clone, F: Fn(&W, &mut <W as Widget<R>>::State) + Send + Sync + 'static  {
+        self.handle().update_undoable(f)
     }
     pub fn read<R>(&self) -> &<W as Widget<R>>::State
     where W: Widget<R>
     {
         self.build_ctx.get_component::<<W as Widget<R>>::State>().unwrap()
     }
+    /// a handle that can outlive the build, for example to create transactions from inside a spawned task
+    pub fn handle(&self) -> StateHandle<W> {
+        StateHandle::new(self.build_ctx.key)
+    }
 }
 
+/// refers to the state of the widget `W` mounted on an entity
+pub struct StateHandle<W: ?Sized> {
+    key: EntityKey,
+    _ph: PhantomData<fn() -> W>
+}
+impl<W: ?Sized> Clone for StateHandle<W> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<W: ?Sized> Copy for StateHandle<W> {}
+impl<W: Component> StateHandle<W> {
+    pub fn new(key: EntityKey) -> Self {
+        Self {
+            key,
+            _ph: PhantomData
+        }
+    }
+    pub fn key(&self) -> EntityKey {
+        self.key
+    }
+    pub fn update<F, R>(&self, f: F) -> StateTransaction
+    where W: Widget<R>, F: Fn(&W, &mut <W as Widget<R>>::State) + Send + Sync + 'static  {
+        StateTransaction::new(self.key, move |ctx: &mut BuildContext| {
+            let state = ctx.get_component_mut::<<W as Widget<R>>::State>().expect("every element always has a state");
+            let data = ctx.get_component::<W>().expect("every element always has a widget");
+            (f)(data, state)
+        })
+    }
+    /// like [`StateHandle::update`], the change is recorded by the history of the gui so it can be undone,
+    /// see [`crate::Gui::with_history`]
+    pub fn update_undoable<F, R>(&self, f: F) -> StateTransaction
+    where W: Widget<R>, <W as Widget<R>>::State: Clone, F: Fn(&W, &mut <W as Widget<R>>::State) + Send + Sync + 'static  {
+        let mut transaction = self.update(f);
+        transaction.snapshot = Some(snapshot::<<W as Widget<R>>::State>);
+        transaction
+    }
+}
+
+/// a change to the state of an entity, cloning it is cheap so it can be dispatched more than once
+#[derive(Clone)]
 pub struct StateTransaction{
-    func: Box<dyn Fn(&mut BuildContext)>
+    /// the entity whose state is changed
+    target: EntityKey,
+    func: Arc<dyn Fn(&mut BuildContext) + Send + Sync>,
+    rebuild: bool,
+    /// copies the state of the target before the transaction, for undoable transactions
+    snapshot: Option<Snapshot>
+}
+impl StateTransaction {
+    /// a transaction that runs `func` with the build context of `target`
+    pub fn new<F>(target: EntityKey, func: F) -> Self
+    where F: Fn(&mut BuildContext) + Send + Sync + 'static
+    {
+        Self {
+            target,
+            func: Arc::new(func),
+            rebuild: true,
+            snapshot: None
+        }
+    }
+    /// the target is not rebuilt after the transaction, for changes that only write signals,
+    /// which rebuild the entities that read them
+    pub fn without_rebuild(mut self) -> Self {
+        self.rebuild = false;
+        self
+    }
+    pub fn target(&self) -> EntityKey {
+        self.target
+    }
+    pub fn rebuilds(&self) -> bool {
+        self.rebuild
+    }
+    pub(crate) fn snapshot(&self) -> Option<Snapshot> {
+        self.snapshot
+    }
+    pub(crate) fn apply(&self, context: &mut BuildContext) {
+        (self.func)(context)
+    }
 }
\ No newline at end of file
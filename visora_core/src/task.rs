@@ -0,0 +1,85 @@
+use std::{future::Future, pin::Pin};
+
+/// a future spawned from a widget, it resolves into a state transaction for the entity that spawned it
+pub type Task = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+
+/// runs the tasks spawned by widgets, implemented by the backends for the async runtime they use
+pub trait Executor: Send + Sync {
+    fn spawn(&self, task: Task) -> Box<dyn TaskHandle>;
+}
+
+pub trait TaskHandle: Send + Sync {
+    /// stops the task, the transaction it would resolve into is never dispatched
+    fn abort(&self);
+    fn is_finished(&self) -> bool;
+}
+
+/// component with the tasks spawned by an entity, they are aborted when the entity is removed
+#[derive(Default)]
+pub struct Tasks {
+    handles: Vec<Box<dyn TaskHandle>>,
+}
+impl Tasks {
+    pub fn push(&mut self, handle: Box<dyn TaskHandle>) {
+        self.handles.retain(|x| !x.is_finished());
+        self.handles.push(handle);
+    }
+    pub fn len(&self) -> usize {
+        self.handles.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.handles.is_empty()
+    }
+}
+impl Drop for Tasks {
+    fn drop(&mut self) {
+        for handle in &self.handles {
+            handle.abort();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        state::StateTransaction,
+        test_utils::{Label, ManualExecutor, TestRenderer},
+        Gui,
+    };
+
+    use super::Tasks;
+
+    #[test]
+    fn spawned_task_dispatches_transaction() {
+        let executor = ManualExecutor::new();
+        let mut gui = Gui::new(TestRenderer::new()).with_executor(executor.clone());
+        let root = gui.tree().root();
+        gui.root_widget_context().insert_component(0u32);
+
+        gui.root_build_context().spawn(async move {
+            StateTransaction::new(root, |context| *context.get_component_mut::<u32>().unwrap() = 5)
+        });
+        assert_eq!(gui.dispatch_pending(), 0);
+
+        executor.run_until_stalled();
+        assert_eq!(gui.dispatch_pending(), 1);
+        assert_eq!(gui.tree().get_component::<u32>(root), Some(&5));
+    }
+    #[test]
+    fn tasks_are_aborted_on_remove() {
+        let executor = ManualExecutor::new();
+        let mut gui = Gui::new(TestRenderer::new()).with_executor(executor.clone());
+        gui.root_widget_context().mount_child(&Label::new("child"));
+        let child = *gui.tree().linkdata(gui.tree().root()).unwrap().children().get_left().unwrap();
+
+        gui.build_context(child).unwrap().spawn(async move {
+            StateTransaction::new(child, |_| panic!("the entity was removed"))
+        });
+        assert_eq!(gui.tree().get_component::<Tasks>(child).map(|x| x.len()), Some(1));
+
+        gui.remove(child);
+        assert_eq!(executor.aborted(), 1);
+        executor.run_until_stalled();
+        assert_eq!(gui.dispatch_pending(), 0);
+    }
+}
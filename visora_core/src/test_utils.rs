@@ -0,0 +1,293 @@
+//! a renderer and widgets to test the gui without a backend
+
+use std::{
+    ops::Range,
+    sync::{atomic::{AtomicBool, AtomicUsize, Ordering}, Arc, Mutex},
+    task::{Context, Poll, Waker},
+};
+
+use crate::{
+    event::Point,
+    layout::Size,
+    renderer::Renderer,
+    state::State,
+    task::{Executor, Task, TaskHandle},
+    treecs::{iterators::breadth::{BreadthInfo, VisibleIter}, query::Query, EntityKey},
+    widget::{Render, RenderAble, Widget},
+    BuildContext, WidgetContext,
+};
+
+/// renders every mounted item as its string, in tree order
+pub struct TestRenderer {
+    rendered: Vec<String>,
+    /// horizontal ranges returned by the hit test, later entries are on top
+    pub areas: Vec<(EntityKey, Range<f32>)>,
+    pub highlighted: Option<EntityKey>,
+    /// the root is laid out in this size when it is set
+    pub viewport: Option<Size>,
+}
+impl TestRenderer {
+    pub fn new() -> Self {
+        Self {
+            rendered: Vec::new(),
+            areas: Vec::new(),
+            highlighted: None,
+            viewport: None,
+        }
+    }
+    pub fn rendered(&self) -> Vec<&str> {
+        self.rendered.iter().map(|x| x.as_str()).collect()
+    }
+}
+impl Renderer for TestRenderer {
+    type RenderItem = String;
+    type QueryType<'gui> = VisibleIter<'gui>;
+    fn render<'gui>(&mut self, q: Query<'gui, (EntityKey, &Self::RenderItem), Self::QueryType<'gui>>) {
+        self.rendered = q
+            .filter(|(info, _)| *info == BreadthInfo::Other)
+            .map(|(_, (_, item))| item.clone())
+            .collect();
+    }
+    fn hit_test(&self, position: Point) -> Option<EntityKey> {
+        self.areas.iter().rev().find(|(_, x)| x.contains(&position.x)).map(|(key, _)| *key)
+    }
+    fn bounds(&self, entity: EntityKey) -> Option<(Point, Point)> {
+        let (_, x) = self.areas.iter().find(|(key, _)| *key == entity)?;
+        Some((Point::new(x.start, 0.0), Point::new(x.end, 0.0)))
+    }
+    fn highlight(&mut self, entity: Option<EntityKey>) {
+        self.highlighted = entity;
+    }
+    fn viewport(&self) -> Option<Size> {
+        self.viewport
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Label {
+    pub text: &'static str,
+}
+impl Label {
+    pub fn new(text: &'static str) -> Self {
+        Self { text }
+    }
+}
+impl Render<Label> for TestRenderer {
+    fn mount<'gui>(widget: &Label, context: &mut WidgetContext<'gui, Self>) {
+        context.mount_renderer(widget.text.to_owned());
+    }
+}
+impl RenderAble<TestRenderer> for Label {
+    fn mount<'gui>(&self, mut context: WidgetContext<'gui, TestRenderer>) -> WidgetContext<'gui, TestRenderer> {
+        context.mount_render(self);
+        context
+    }
+}
+
+/// renders its text and logs how the renderer mounts, updates and unmounts it
+#[derive(Clone)]
+pub struct Tracked {
+    pub text: &'static str,
+    pub log: Log,
+}
+impl Render<Tracked> for TestRenderer {
+    fn mount<'gui>(widget: &Tracked, context: &mut WidgetContext<'gui, Self>) {
+        widget.log.lock().unwrap().push(format!("mount {}", widget.text));
+        context.mount_renderer(widget.text.to_owned());
+    }
+    fn update<'gui>(old: &Tracked, new: &Tracked, context: &mut WidgetContext<'gui, Self>) {
+        new.log.lock().unwrap().push(format!("update {} -> {}", old.text, new.text));
+        if old.text != new.text {
+            *context.get_renderer().unwrap() = new.text.to_owned();
+        }
+    }
+    fn unmount<'gui>(widget: &Tracked, _context: &mut WidgetContext<'gui, Self>) {
+        widget.log.lock().unwrap().push(format!("unmount {}", widget.text));
+    }
+}
+impl RenderAble<TestRenderer> for Tracked {
+    fn mount<'gui>(&self, mut context: WidgetContext<'gui, TestRenderer>) -> WidgetContext<'gui, TestRenderer> {
+        context.mount_render(self);
+        context
+    }
+}
+
+pub type Log = Arc<Mutex<Vec<String>>>;
+
+/// stateful widget that writes every lifecycle call to a shared log
+#[derive(Clone)]
+pub struct Lifecycles {
+    pub name: &'static str,
+    pub log: Log,
+    pub child: LifecyclesChild,
+}
+#[derive(Clone)]
+pub enum LifecyclesChild {
+    Label(Label),
+    Nested(Box<Lifecycles>),
+}
+impl Lifecycles {
+    pub fn new(name: &'static str, child: Label) -> (Self, Log) {
+        let log = Log::default();
+        let widget = Self {
+            name,
+            log: log.clone(),
+            child: LifecyclesChild::Label(child),
+        };
+        (widget, log)
+    }
+    pub fn with_log(mut self, log: Log) -> Self {
+        self.log = log;
+        self
+    }
+    pub fn with_child(mut self, child: Lifecycles) -> Self {
+        self.child = LifecyclesChild::Nested(Box::new(child));
+        self
+    }
+    fn log(&self, event: &str) {
+        self.log.lock().unwrap().push(format!("{event} {}", self.name));
+    }
+}
+impl RenderAble<TestRenderer> for LifecyclesChild {
+    fn mount<'gui>(&self, context: WidgetContext<'gui, TestRenderer>) -> WidgetContext<'gui, TestRenderer> {
+        match self {
+            Self::Label(x) => x.mount(context),
+            Self::Nested(x) => x.mount(context),
+        }
+    }
+}
+impl RenderAble<TestRenderer> for Lifecycles {
+    fn mount<'gui>(&self, context: WidgetContext<'gui, TestRenderer>) -> WidgetContext<'gui, TestRenderer> {
+        context.mount_widget(self)
+    }
+}
+impl Widget<TestRenderer> for Lifecycles {
+    type State = ();
+    fn create_state(&self) -> Self::State {}
+    fn build<'gui>(&self, _state: State<Self>, _context: &mut BuildContext<'gui>) -> impl RenderAble<TestRenderer> + 'static {
+        self.log("build");
+        self.child.clone()
+    }
+    fn init_state<'gui>(&self, _context: &mut BuildContext<'gui>) {
+        self.log("init");
+    }
+    fn did_update_widget<'gui>(&self, _old: &Self, _context: &mut BuildContext<'gui>) {
+        self.log("update");
+    }
+    fn dispose<'gui>(&self, _context: &mut BuildContext<'gui>) {
+        self.log("dispose");
+    }
+}
+
+/// executor that only polls its tasks when asked to
+#[derive(Clone, Default)]
+pub struct ManualExecutor {
+    tasks: Arc<Mutex<Vec<(Task, Arc<ManualTask>)>>>,
+    aborted: Arc<AtomicUsize>,
+}
+#[derive(Default)]
+struct ManualTask {
+    aborted: AtomicBool,
+    finished: AtomicBool,
+}
+struct ManualHandle {
+    task: Arc<ManualTask>,
+    aborted: Arc<AtomicUsize>,
+}
+impl ManualExecutor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// polls every task once, finished and aborted tasks are dropped
+    pub fn run_until_stalled(&self) {
+        let mut context = Context::from_waker(Waker::noop());
+        self.tasks.lock().unwrap().retain_mut(|(future, task)| {
+            if task.aborted.load(Ordering::SeqCst) {
+                return false;
+            }
+            let Poll::Ready(()) = future.as_mut().poll(&mut context) else {
+                return true;
+            };
+            task.finished.store(true, Ordering::SeqCst);
+            false
+        });
+    }
+    /// the amount of unfinished tasks that were aborted
+    pub fn aborted(&self) -> usize {
+        self.aborted.load(Ordering::SeqCst)
+    }
+}
+impl Executor for ManualExecutor {
+    fn spawn(&self, task: Task) -> Box<dyn TaskHandle> {
+        let state = Arc::new(ManualTask::default());
+        self.tasks.lock().unwrap().push((task, state.clone()));
+        Box::new(ManualHandle {
+            task: state,
+            aborted: self.aborted.clone(),
+        })
+    }
+}
+impl TaskHandle for ManualHandle {
+    fn abort(&self) {
+        if !self.is_finished() && !self.task.aborted.swap(true, Ordering::SeqCst) {
+            self.aborted.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+    fn is_finished(&self) -> bool {
+        self.task.finished.load(Ordering::SeqCst)
+    }
+}
+
+/// renders a string that is only known while building
+#[derive(Debug, Clone)]
+pub struct Dynamic {
+    pub text: String,
+}
+impl Render<Dynamic> for TestRenderer {
+    fn mount<'gui>(widget: &Dynamic, context: &mut WidgetContext<'gui, Self>) {
+        context.mount_renderer(widget.text.clone());
+    }
+}
+impl RenderAble<TestRenderer> for Dynamic {
+    fn mount<'gui>(&self, mut context: WidgetContext<'gui, TestRenderer>) -> WidgetContext<'gui, TestRenderer> {
+        context.mount_render(self);
+        context
+    }
+}
+
+/// stateful widget that renders the provided `u32`, or "none" when no ancestor provides one
+#[derive(Clone)]
+pub struct Watcher {
+    pub name: &'static str,
+    pub log: Log,
+    /// reads the value without depending on it
+    pub read: bool,
+}
+impl Watcher {
+    pub fn new(name: &'static str, log: Log) -> Self {
+        Self { name, log, read: false }
+    }
+    pub fn reading(mut self) -> Self {
+        self.read = true;
+        self
+    }
+}
+impl RenderAble<TestRenderer> for Watcher {
+    fn mount<'gui>(&self, context: WidgetContext<'gui, TestRenderer>) -> WidgetContext<'gui, TestRenderer> {
+        context.mount_widget(self)
+    }
+}
+impl Widget<TestRenderer> for Watcher {
+    type State = ();
+    fn create_state(&self) -> Self::State {}
+    fn build<'gui>(&self, _state: State<Self>, context: &mut BuildContext<'gui>) -> impl RenderAble<TestRenderer> + 'static {
+        self.log.lock().unwrap().push(format!("build {}", self.name));
+        let value = match self.read {
+            true => context.read::<u32>(),
+            false => context.watch::<u32>(),
+        };
+        Dynamic {
+            text: value.map_or("none".to_owned(), |x| x.to_string()),
+        }
+    }
+}
@@ -0,0 +1,178 @@
+use std::{sync::{atomic::{AtomicBool, Ordering}, Arc}, time::Duration};
+
+use crate::{runtime::Runtime, state::StateTransaction};
+
+/// cancels a timer created with [`crate::BuildContext::set_timeout`] or [`crate::BuildContext::set_interval`]
+#[derive(Clone)]
+pub struct TimerHandle {
+    cancelled: Arc<AtomicBool>,
+}
+impl TimerHandle {
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+struct Timer {
+    /// the time of the clock at which the timer fires
+    deadline: Duration,
+    /// fires again after this period, a timeout fires once
+    period: Option<Duration>,
+    transaction: StateTransaction,
+    handle: TimerHandle,
+}
+
+/// component with the timers of an entity, they are cancelled when the entity is removed
+#[derive(Default)]
+pub struct Timers {
+    timers: Vec<Timer>,
+}
+impl Timers {
+    pub(crate) fn push(&mut self, deadline: Duration, period: Option<Duration>, transaction: StateTransaction) -> TimerHandle {
+        let handle = TimerHandle {
+            cancelled: Arc::new(AtomicBool::new(false)),
+        };
+        self.timers.push(Timer {
+            deadline,
+            period,
+            transaction,
+            handle: handle.clone(),
+        });
+        handle
+    }
+    /// queues the transactions of the timers that are due at `now`, returns how many fired
+    pub(crate) fn fire(&mut self, now: Duration, runtime: &Runtime) -> usize {
+        let mut fired = 0;
+        self.timers.retain_mut(|timer| {
+            if timer.handle.is_cancelled() {
+                return false;
+            }
+            if timer.deadline > now {
+                return true;
+            }
+            runtime.queue(timer.transaction.clone());
+            fired += 1;
+            let Some(period) = timer.period else {
+                return false;
+            };
+            // an interval that missed several periods fires once and continues from now
+            timer.deadline += period;
+            if timer.deadline <= now {
+                timer.deadline = now + period;
+            }
+            true
+        });
+        fired
+    }
+    /// the earliest deadline of the timers that are not cancelled
+    pub fn next_deadline(&self) -> Option<Duration> {
+        self.timers
+            .iter()
+            .filter(|x| !x.handle.is_cancelled())
+            .map(|x| x.deadline)
+            .min()
+    }
+    pub fn len(&self) -> usize {
+        self.timers.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.timers.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::{Arc, Mutex}, time::Duration};
+
+    use crate::{
+        animation::clock::ManualClock,
+        state::StateTransaction,
+        test_utils::{Label, TestRenderer},
+        Gui,
+    };
+
+    fn gui() -> (Gui<TestRenderer>, ManualClock) {
+        let clock = ManualClock::new();
+        (Gui::new(TestRenderer::new()).with_clock(clock.clone()), clock)
+    }
+    fn counter(gui: &mut Gui<TestRenderer>) -> (StateTransaction, Arc<Mutex<u32>>) {
+        let count = Arc::new(Mutex::new(0));
+        let counted = count.clone();
+        let transaction = StateTransaction::new(gui.tree().root(), move |_| *counted.lock().unwrap() += 1);
+        (transaction, count)
+    }
+    fn millis(x: u64) -> Duration {
+        Duration::from_millis(x)
+    }
+
+    #[test]
+    fn timeout_fires_once() {
+        let (mut gui, clock) = gui();
+        let (transaction, count) = counter(&mut gui);
+        gui.root_build_context().set_timeout(millis(100), transaction);
+        assert_eq!(gui.until_next_timer(), Some(millis(100)));
+
+        clock.advance(millis(99));
+        assert_eq!(gui.fire_timers(), 0);
+        assert_eq!(gui.until_next_timer(), Some(millis(1)));
+        clock.advance(millis(1));
+        assert_eq!(gui.fire_timers(), 1);
+        clock.advance(millis(500));
+        assert_eq!(gui.fire_timers(), 0);
+        assert_eq!(*count.lock().unwrap(), 1);
+        assert_eq!(gui.until_next_timer(), None);
+    }
+    #[test]
+    fn interval_repeats_until_cancelled() {
+        let (mut gui, clock) = gui();
+        let (transaction, count) = counter(&mut gui);
+        let handle = gui.root_build_context().set_interval(millis(10), transaction);
+
+        for _ in 0..3 {
+            clock.advance(millis(10));
+            gui.fire_timers();
+        }
+        // missed periods fire once
+        clock.advance(millis(35));
+        gui.fire_timers();
+        assert_eq!(*count.lock().unwrap(), 4);
+        assert_eq!(gui.until_next_timer(), Some(millis(10)));
+
+        handle.cancel();
+        clock.advance(millis(10));
+        assert_eq!(gui.fire_timers(), 0);
+        assert_eq!(gui.until_next_timer(), None);
+    }
+    #[test]
+    fn timers_are_cancelled_on_remove() {
+        let (mut gui, clock) = gui();
+        gui.root_widget_context().mount_child(&Label::new("toast"));
+        let toast = *gui.tree().linkdata(gui.tree().root()).unwrap().children().get_left().unwrap();
+        let transaction = StateTransaction::new(toast, |_| panic!("the entity was removed"));
+        let handle = gui.build_context(toast).unwrap().set_timeout(millis(10), transaction);
+
+        gui.remove(toast);
+        clock.advance(millis(10));
+        assert_eq!(gui.fire_timers(), 0);
+        assert_eq!(gui.until_next_timer(), None);
+        assert!(!handle.is_cancelled(), "the timer is dropped with its entity");
+    }
+    #[test]
+    fn timers_of_hidden_entities_keep_firing() {
+        let (mut gui, clock) = gui();
+        gui.root_widget_context().mount_child(&Label::new("covered page"));
+        let page = *gui.tree().linkdata(gui.tree().root()).unwrap().children().get_left().unwrap();
+        // like a page below the top of a navigator
+        gui.tree.linkdata_mut(page).unwrap().set_hidden(true);
+        let (transaction, count) = counter(&mut gui);
+        gui.build_context(page).unwrap().set_timeout(millis(10), transaction);
+
+        assert_eq!(gui.until_next_timer(), Some(millis(10)));
+        clock.advance(millis(10));
+        assert_eq!(gui.fire_timers(), 1);
+        assert_eq!(*count.lock().unwrap(), 1);
+    }
+}
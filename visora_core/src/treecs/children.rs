@@ -128,6 +128,16 @@ impl<T> Children<T> {
         };
         let _ = std::mem::replace(self, new_data);
     }
+    /// inserts `data` at `idx`, shifting all later children to the right
+    ///
+    /// panics if `idx > len`
+    pub fn insert(&mut self, idx: usize, data: T){
+        assert!(idx <= self.len(), "insertion index out of range");
+        let old_data = std::mem::replace(self, Children::NoChild);
+        let mut items: VecDeque<T> = old_data.into_iter().collect();
+        items.insert(idx, data);
+        let _ = std::mem::replace(self, Children::new_from(items));
+    }
     pub fn pop_left(&mut self) -> Option<T>{
         let old_data = std::mem::replace(self, Children::NoChild);
         let (new_data, to_return) = match old_data {
@@ -600,6 +610,29 @@ mod tests{
         );
     }
 
+    #[test]
+    fn insert(){
+        let mut data = Children::new();
+        data.insert(0, 2);
+        assert!(matches!(data, Children::SingleChild(2)));
+        data.insert(0, 1);
+        assert!(matches!(data, Children::DoubleChild(1, 2)));
+        data.insert(2, 4);
+        data.insert(2, 3);
+        let mut iter = data.into_iter();
+        assert_eq!(iter.next(), Some(1));
+        assert_eq!(iter.next(), Some(2));
+        assert_eq!(iter.next(), Some(3));
+        assert_eq!(iter.next(), Some(4));
+        assert_eq!(iter.next(), None);
+    }
+    #[test]
+    #[should_panic]
+    fn insert_panic(){
+        let mut data = Children::new_from([1]);
+        data.insert(2, 3);
+    }
+
     #[test]
     fn remove(){
         let mut data = Children::new_from([1,2,3,4,5]);
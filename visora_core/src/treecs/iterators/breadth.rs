@@ -57,38 +57,64 @@ impl Dir for Reversed{}
 pub struct BreadthIter<'world, D: Dir = Forward> {
     world: &'world Treecs,
     entity_stack: VecDeque<(BreadthInfo, EntityKey)>,
+    /// the entity the iteration started at, `None` when it started at every root
+    start: Option<EntityKey>,
+    /// whether hidden entities are skipped with their subtrees, only a [`VisibleIter`] sets it
+    skip_hidden: bool,
     dir: PhantomData<D>
 }    
+impl<'world, D: Dir> BreadthIter<'world, D> {
+    /// `starts` are visited in the reverse order, the stack is popped from the back
+    fn with_stack(world: &'world Treecs, start: Option<EntityKey>, starts: impl Iterator<Item = EntityKey>) -> Self {
+        Self {
+            world,
+            entity_stack: starts.map(|x| (BreadthInfo::Other, x)).collect(),
+            start,
+            skip_hidden: false,
+            dir: PhantomData
+        }
+    }
+}
 
 impl<'world> Iterator for BreadthIter<'world, Forward> {
     type Item = (BreadthInfo, EntityKey);
     fn next(&mut self) -> Option<Self::Item> {
-        let (info, key) = self.entity_stack.pop_back()?;
-        match info {
-            BreadthInfo::Other => {
-                self.entity_stack.push_back((BreadthInfo::MoveUp, key));
-                let linkdata = self.world.linkdata(key)?;
-                //println!("{key:?} -> children = {:?}", linkdata.children());
-                self.entity_stack.extend(linkdata.children().iter().rev().map(|x| (BreadthInfo::Other, *x)));
-                Some((BreadthInfo::Other, key))
-            },
-            BreadthInfo::MoveUp => Some((BreadthInfo::MoveUp, key))
+        loop {
+            let (info, key) = self.entity_stack.pop_back()?;
+            match info {
+                BreadthInfo::Other => {
+                    let linkdata = self.world.linkdata(key)?;
+                    // a hidden entity is skipped with its subtree, including the move up
+                    if self.skip_hidden && linkdata.is_hidden() {
+                        continue;
+                    }
+                    self.entity_stack.push_back((BreadthInfo::MoveUp, key));
+                    self.entity_stack.extend(linkdata.children().iter().rev().map(|x| (BreadthInfo::Other, *x)));
+                    return Some((BreadthInfo::Other, key));
+                },
+                BreadthInfo::MoveUp => return Some((BreadthInfo::MoveUp, key))
+            }
         }
     }
 }
 impl<'world> Iterator for BreadthIter<'world, Reversed> {
     type Item = (BreadthInfo, EntityKey);
     fn next(&mut self) -> Option<Self::Item> {
-        let (info, key) = self.entity_stack.pop_back()?;
-        match info {
-            BreadthInfo::Other => {
-                self.entity_stack.push_back((BreadthInfo::MoveUp, key));
-                let linkdata = self.world.linkdata(key)?;
-                //println!("{key:?} -> children = {:?}", linkdata.children());
-                self.entity_stack.extend(linkdata.children().iter().map(|x| (BreadthInfo::Other, *x)));
-                Some((BreadthInfo::Other, key))
-            },
-            BreadthInfo::MoveUp => Some((BreadthInfo::MoveUp, key))
+        loop {
+            let (info, key) = self.entity_stack.pop_back()?;
+            match info {
+                BreadthInfo::Other => {
+                    let linkdata = self.world.linkdata(key)?;
+                    // a hidden entity is skipped with its subtree, including the move up
+                    if self.skip_hidden && linkdata.is_hidden() {
+                        continue;
+                    }
+                    self.entity_stack.push_back((BreadthInfo::MoveUp, key));
+                    self.entity_stack.extend(linkdata.children().iter().map(|x| (BreadthInfo::Other, *x)));
+                    return Some((BreadthInfo::Other, key));
+                },
+                BreadthInfo::MoveUp => return Some((BreadthInfo::MoveUp, key))
+            }
         }
     }
 }
@@ -98,6 +124,9 @@ impl<'world, Q: QueryAble> QueryIter<'world, Q> for BreadthIter<'world, Forward>
     fn transform(&self, key: EntityKey) -> Option<Q::Output<'world>> {
         Q::get(&self.world, key)
     }
+    fn world(&self) -> &'world Treecs {
+        self.world
+    }
 }
 impl<'world, Q: QueryAble> QueryIter<'world, Q> for BreadthIter<'world, Reversed>
 {
@@ -105,45 +134,109 @@ impl<'world, Q: QueryAble> QueryIter<'world, Q> for BreadthIter<'world, Reversed
     fn transform(&self, key: EntityKey) -> Option<Q::Output<'world>> {
         Q::get(&self.world, key)
     }
+    fn world(&self) -> &'world Treecs {
+        self.world
+    }
 }
 impl<'world, Q: QueryAble> WorldIter<'world, Q> for BreadthIter<'world, Forward> {
     fn new(world: &'world Treecs) -> Self {
-        let mut entity_stack = VecDeque::new();
-        entity_stack.push_back((BreadthInfo::Other, world.root()));
-        Self {
-            world,
-            entity_stack,
-            dir: PhantomData
-        }
+        let roots: Vec<EntityKey> = world.roots().collect();
+        Self::with_stack(world, None, roots.into_iter().rev())
+    }
+    fn new_at(world: &'world Treecs, start: EntityKey) -> Self {
+        Self::with_stack(world, Some(start), std::iter::once(start))
     }
     fn restart(self) -> Self {
-        let Self{world, ..} = self;
-        <Self as WorldIter<'world, Q>>::new(world)
+        match self.start {
+            Some(start) => <Self as WorldIter<'world, Q>>::new_at(self.world, start),
+            None => <Self as WorldIter<'world, Q>>::new(self.world),
+        }
     }
     
 }
 impl<'world, Q: QueryAble> WorldIter<'world, Q> for BreadthIter<'world, Reversed> {
     fn new(world: &'world Treecs) -> Self {
-        let mut entity_stack = VecDeque::new();
-        entity_stack.push_back((BreadthInfo::Other, world.root()));
-        Self {
-            world,
-            entity_stack,
-            dir: PhantomData
-        }
+        Self::with_stack(world, None, world.roots())
+    }
+    fn new_at(world: &'world Treecs, start: EntityKey) -> Self {
+        Self::with_stack(world, Some(start), std::iter::once(start))
     }
     fn restart(self) -> Self {
-        let Self{world, ..} = self;
-        <Self as WorldIter<'world, Q>>::new(world)
+        match self.start {
+            Some(start) => <Self as WorldIter<'world, Q>>::new_at(self.world, start),
+            None => <Self as WorldIter<'world, Q>>::new(self.world),
+        }
     }
 }
 impl<'world> BreadthIter<'world, Forward>{
     pub fn reverse<Q: QueryAble>(self) -> BreadthIter<'world, Reversed>{
-        <BreadthIter<'world, Reversed> as WorldIter<'world, Q>>::new(self.world)
+        match self.start {
+            Some(start) => <BreadthIter<'world, Reversed> as WorldIter<'world, Q>>::new_at(self.world, start),
+            None => <BreadthIter<'world, Reversed> as WorldIter<'world, Q>>::new(self.world),
+        }
     }
 }
 impl<'world> BreadthIter<'world, Reversed>{
     pub fn reverse<Q: QueryAble>(self) -> BreadthIter<'world, Forward>{
-        <BreadthIter<'world, Forward> as WorldIter<'world, Q>>::new(self.world)
+        match self.start {
+            Some(start) => <BreadthIter<'world, Forward> as WorldIter<'world, Q>>::new_at(self.world, start),
+            None => <BreadthIter<'world, Forward> as WorldIter<'world, Q>>::new(self.world),
+        }
+    }
+}
+
+/// a [`BreadthIter`] that skips hidden entities with their subtrees, used by what is drawn or hit
+///
+/// the tickers and the timers of a hidden entity keep running, they are found with a plain [`BreadthIter`]
+pub struct VisibleIter<'world, D: Dir = Forward>(BreadthIter<'world, D>);
+impl<'world, D: Dir> VisibleIter<'world, D> {
+    fn visible(mut iter: BreadthIter<'world, D>) -> Self {
+        iter.skip_hidden = true;
+        Self(iter)
+    }
+}
+impl<'world, D: Dir> Iterator for VisibleIter<'world, D>
+where
+    BreadthIter<'world, D>: Iterator<Item = (BreadthInfo, EntityKey)>
+{
+    type Item = (BreadthInfo, EntityKey);
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+}
+impl<'world, Q: QueryAble, D: Dir> QueryIter<'world, Q> for VisibleIter<'world, D>
+where
+    BreadthIter<'world, D>: QueryIter<'world, Q, Info = (BreadthInfo, EntityKey)>
+{
+    type Info = (BreadthInfo, EntityKey);
+    fn transform(&self, key: EntityKey) -> Option<Q::Output<'world>> {
+        self.0.transform(key)
+    }
+    fn world(&self) -> &'world Treecs {
+        QueryIter::<Q>::world(&self.0)
     }
-}
\ No newline at end of file
+}
+impl<'world, Q: QueryAble, D: Dir> WorldIter<'world, Q> for VisibleIter<'world, D>
+where
+    BreadthIter<'world, D>: WorldIter<'world, Q, Info = (BreadthInfo, EntityKey)>
+{
+    fn new(world: &'world Treecs) -> Self {
+        Self::visible(<BreadthIter<'world, D> as WorldIter<'world, Q>>::new(world))
+    }
+    fn new_at(world: &'world Treecs, start: EntityKey) -> Self {
+        Self::visible(<BreadthIter<'world, D> as WorldIter<'world, Q>>::new_at(world, start))
+    }
+    fn restart(self) -> Self {
+        Self::visible(<BreadthIter<'world, D> as WorldIter<'world, Q>>::restart(self.0))
+    }
+}
+impl<'world> VisibleIter<'world, Forward>{
+    pub fn reverse<Q: QueryAble>(self) -> VisibleIter<'world, Reversed>{
+        VisibleIter::visible(self.0.reverse::<Q>())
+    }
+}
+impl<'world> VisibleIter<'world, Reversed>{
+    pub fn reverse<Q: QueryAble>(self) -> VisibleIter<'world, Forward>{
+        VisibleIter::visible(self.0.reverse::<Q>())
+    }
+}
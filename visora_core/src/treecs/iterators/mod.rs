@@ -29,11 +29,16 @@ impl<'world, T, Q: QueryAble> InfoTransform<'world, Q> for (T, EntityKey){
 pub trait QueryIter<'world, Q: QueryAble>: Iterator<Item = Self::Info> {
     type Info: InfoTransform<'world, Q>;
     fn transform(&self, key: EntityKey) -> Option<Q::Output<'world>>;
+    /// the world that is iterated
+    fn world(&self) -> &'world Treecs;
 }
 
 /// an iterator that can created from only the world
 pub trait WorldIter<'world, Q: QueryAble>: QueryIter<'world, Q> {
+    /// iterates every root of the world
     fn new(world: &'world Treecs) -> Self;
+    /// iterates only the subtree of `start`
+    fn new_at(world: &'world Treecs, start: EntityKey) -> Self;
     fn restart(self) -> Self;
 }
 
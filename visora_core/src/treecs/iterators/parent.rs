@@ -24,6 +24,9 @@ impl<'world, Q: QueryAble> QueryIter<'world, Q> for ParentIter<'world> {
     ) -> Option<Q::Output<'world>> {
         Q::get(&self.world, key)
     }
+    fn world(&self) -> &'world Treecs {
+        self.world
+    }
 }
 impl<'world> ParentIter<'world> {
     pub fn new(world: &'world Treecs, start: EntityKey) -> Self {
@@ -4,6 +4,8 @@ use crate::treecs::{children::Children, EntityKey};
 pub struct LinkData {
     parent: Option<EntityKey>,
     children: Children<EntityKey>,
+    /// hidden entities and their subtrees are skipped by the visible iterators, the layout and the hit testing
+    hidden: bool,
 }
 
 impl LinkData {
@@ -11,6 +13,7 @@ impl LinkData {
         LinkData {
             parent: None,
             children: Children::NoChild,
+            hidden: false,
         }
     };
 
@@ -21,6 +24,7 @@ impl LinkData {
         Self {
             parent: Some(parent),
             children: Children::NoChild,
+            hidden: false,
         }
     }
 
@@ -36,4 +40,10 @@ impl LinkData {
     pub fn parent_mut(&mut self) -> &mut Option<EntityKey> {
         &mut self.parent
     }
+    pub fn is_hidden(&self) -> bool {
+        self.hidden
+    }
+    pub fn set_hidden(&mut self, hidden: bool) {
+        self.hidden = hidden;
+    }
 }
@@ -1,5 +1,6 @@
 use component::{Component, ComponentEntry, ComponentEntryMut, ComponentStore};
 use entity::Entity;
+use iterators::parent::ParentIter;
 use linkdata::LinkData;
 use slotmap::{new_key_type, SlotMap};
 
@@ -21,6 +22,8 @@ new_key_type! {
 pub struct Treecs {
     linkdata: SlotMap<EntityKey, LinkData>,
     root: EntityKey,
+    /// the roots added next to the main one, see [`Treecs::add_root`]
+    roots: Vec<EntityKey>,
     components: ComponentStore,
 }
 impl Treecs {
@@ -30,6 +33,7 @@ impl Treecs {
         Self {
             linkdata,
             root,
+            roots: Vec::new(),
             components: ComponentStore::new(),
         }
     }
@@ -45,6 +49,20 @@ impl Treecs {
     pub fn root(&self) -> EntityKey {
         self.root
     }
+    /// adds an entity without a parent, the root of a tree next to the one of [`Treecs::root`]
+    pub fn add_root(&mut self) -> EntityKey {
+        let key = self.linkdata.insert(LinkData::new_empty());
+        self.roots.push(key);
+        key
+    }
+    /// the main root followed by the roots added with [`Treecs::add_root`]
+    pub fn roots(&self) -> impl Iterator<Item = EntityKey> + '_ {
+        std::iter::once(self.root).chain(self.roots.iter().copied())
+    }
+    /// the root of the tree `entity` belongs to
+    pub fn root_of(&self, entity: EntityKey) -> Option<EntityKey> {
+        ParentIter::new(self, entity).last()
+    }
 
     pub fn contains(&self, entity: EntityKey) -> bool {
         self.linkdata.contains_key(entity)
@@ -64,6 +82,18 @@ impl Treecs {
             .push_right(key);
         Some(key)
     }
+    /// adds a new child to `parent` at position `index` of its children
+    pub fn insert(&mut self, parent: EntityKey, index: usize) -> Option<EntityKey> {
+        if !self.contains(parent) || index > self.linkdata(parent)?.children().len() {
+            return None;
+        }
+        let key = self.linkdata.insert(LinkData::new_with_parent(parent));
+        self.linkdata_mut(parent)
+            .unwrap()
+            .children_mut()
+            .insert(index, key);
+        Some(key)
+    }
     pub fn remove(&mut self, entity: EntityKey) -> Option<()> {
         if !self.contains(entity) {
             return None;
@@ -74,9 +104,14 @@ impl Treecs {
             Some(x) => {
                 self.linkdata_mut(*x).unwrap().children_mut().remove(entity);
             }
+            // an added root is removed with its tree, the other trees stay
+            None if entity != self.root => {
+                self.roots.retain(|x| *x != entity);
+            }
             // if the element has no parent it means we removed the root
             None => {
                 self.linkdata.clear();
+                self.roots.clear();
                 let new_root = self.linkdata.insert(LinkData::new_empty());
                 self.root = new_root;
             }
@@ -158,6 +193,21 @@ mod tests {
         assert_eq!(iter.next(), Some(&entity2));
         assert_eq!(iter.next(), None);
     }
+    #[test]
+    fn insert_child() {
+        let mut world = Treecs::new();
+
+        let entity1 = world.add(world.root()).unwrap();
+        let entity2 = world.add(world.root()).unwrap();
+        let entity3 = world.insert(world.root(), 1).unwrap();
+        assert_eq!(world.insert(world.root(), 4), None);
+
+        let mut iter = world.linkdata(world.root()).unwrap().children().iter();
+        assert_eq!(iter.next(), Some(&entity1));
+        assert_eq!(iter.next(), Some(&entity3));
+        assert_eq!(iter.next(), Some(&entity2));
+        assert_eq!(iter.next(), None);
+    }
     use crate::treecs::test_utils::*;
 
     #[test]
@@ -213,5 +263,48 @@ mod tests {
         );
         assert_eq!(world.get_component::<Name>(entity).as_deref(), None);
     }
+
+    #[test]
+    fn hidden_subtrees_are_skipped_by_the_visible_iterator() {
+        use crate::treecs::{iterators::breadth::{BreadthInfo, BreadthIter, VisibleIter}, query::Query, EntityKey};
+
+        let mut world = Treecs::new();
+        let entity1 = world.add(world.root()).unwrap();
+        let entity1_1 = world.add(entity1).unwrap();
+        let entity2 = world.add(world.root()).unwrap();
+        world.linkdata_mut(entity1).unwrap().set_hidden(true);
+
+        let query: Query<EntityKey, VisibleIter> = Query::new(&world);
+        let visited: Vec<_> = query.filter(|(info, _)| *info == BreadthInfo::Other).map(|(_, x)| x).collect();
+        assert_eq!(visited, vec![world.root(), entity2]);
+        let query: Query<EntityKey, VisibleIter> = Query::new(&world);
+        let visited: Vec<_> = query.reverse().filter(|(info, _)| *info == BreadthInfo::Other).map(|(_, x)| x).collect();
+        assert_eq!(visited, vec![world.root(), entity2]);
+
+        let query: Query<EntityKey, BreadthIter> = Query::new(&world);
+        let visited: Vec<_> = query.filter(|(info, _)| *info == BreadthInfo::Other).map(|(_, x)| x).collect();
+        assert_eq!(visited, vec![world.root(), entity1, entity1_1, entity2], "the tree iteration keeps hidden entities");
+    }
+    #[test]
+    fn roots_are_iterated_in_order() {
+        use crate::treecs::{iterators::breadth::{BreadthInfo, BreadthIter}, query::Query, EntityKey};
+
+        let mut world = Treecs::new();
+        let entity1 = world.add(world.root()).unwrap();
+        let root2 = world.add_root();
+        let entity2 = world.add(root2).unwrap();
+
+        let visited = |query: Query<EntityKey, BreadthIter>| -> Vec<EntityKey> {
+            query.filter(|(info, _)| *info == BreadthInfo::Other).map(|(_, x)| x).collect()
+        };
+        assert_eq!(visited(Query::new(&world)), vec![world.root(), entity1, root2, entity2]);
+        assert_eq!(visited(Query::new_at(&world, root2)), vec![root2, entity2]);
+        assert_eq!(world.root_of(entity2), Some(root2));
+
+        world.remove(root2);
+        assert!(!world.contains(entity2));
+        assert!(world.contains(entity1), "removing an added root keeps the main one");
+        assert_eq!(world.roots().count(), 1);
+    }
 }
 fn main() {}
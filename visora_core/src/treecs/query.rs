@@ -6,7 +6,7 @@ use crate::treecs::{
     EntityKey, Treecs,
 };
 
-use super::{component::ComponentEntry, iterators::{breadth::{BreadthInfo, BreadthIter, Forward, Reversed}, InfoTransform}};
+use super::{component::ComponentEntry, iterators::{breadth::{BreadthInfo, BreadthIter, Forward, Reversed, VisibleIter}, InfoTransform}};
 
 
 pub trait QueryAble {
@@ -42,6 +42,13 @@ impl<T: Component> QueryAble for Option<&T> {
         Some(world.get_component(entity))
     }
 }
+/// the key of the entity itself, to know which entity the other queried components belong to
+impl QueryAble for EntityKey {
+    type Output<'world> = EntityKey;
+    fn get<'world>(_world: &'world Treecs, entity: EntityKey) -> Option<Self::Output<'world>> {
+        Some(entity)
+    }
+}
 impl<A: QueryAble> QueryAble for (A,) {
     type Output<'world> = (A::Output<'world>,);
     fn get<'world>(world: &'world Treecs, entity: EntityKey) -> Option<Self::Output<'world>> {
@@ -72,6 +79,13 @@ impl<'world, Q: QueryAble, I: WorldIter<'world, Q>> Query<'world, Q, I> {
             iter: I::new(&world),
         }
     }
+    /// a query over the subtree of `key`
+    pub fn new_at(world: &'world Treecs, key: EntityKey) -> Self {
+        Self {
+            _ph: PhantomData,
+            iter: I::new_at(world, key),
+        }
+    }
 }
 impl<'world, Q: QueryAble> Query<'world, Q, ParentIter<'world>> {
     pub fn new_parent(world: &'world Treecs, key: EntityKey) -> Self {
@@ -94,6 +108,12 @@ impl<'world, Q: QueryAble, I: QueryIter<'world, Q>> Iterator for Query<'world, Q
         }
     }
 }
+impl<'world, Q: QueryAble, I: QueryIter<'world, Q>> Query<'world, Q, I> {
+    /// a component of any entity of the queried world, like the [`crate::layout::LayoutRect`] of a queried item
+    pub fn component<T: Component>(&self, key: EntityKey) -> Option<&'world T> {
+        self.iter.world().get_component(key)
+    }
+}
 impl<'world, Q: QueryAble, I: WorldIter<'world, Q>> Query<'world, Q, I>{
     pub fn restart(self) -> Self {
         Self {
@@ -118,6 +138,22 @@ impl<'world, Q: QueryAble> Query<'world, Q, BreadthIter<'world, Reversed>> {
         }
     }
 }
+impl<'world, Q: QueryAble> Query<'world, Q, VisibleIter<'world, Forward>> {
+    pub fn reverse(self) -> Query<'world, Q, VisibleIter<'world, Reversed>> {
+        Query {
+            iter: self.iter.reverse::<Q>(),
+            _ph: PhantomData
+        }
+    }
+}
+impl<'world, Q: QueryAble> Query<'world, Q, VisibleIter<'world, Reversed>> {
+    pub fn reverse(self) -> Query<'world, Q, VisibleIter<'world, Forward>> {
+        Query {
+            iter: self.iter.reverse::<Q>(),
+            _ph: PhantomData
+        }
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -127,6 +163,7 @@ mod tests {
     use crate::treecs::iterators::breadth::BreadthIter;
     use crate::treecs::iterators::parent::ParentIter;
     use crate::treecs::test_utils::*;
+    use crate::treecs::EntityKey;
     use crate::treecs::Treecs;
 
     use super::Query;
@@ -189,6 +226,20 @@ mod tests {
         assert_eq!(query.next().as_deref(), None);
     }
     #[test]
+    fn query_entity_key() {
+        let mut world = Treecs::new();
+        let entity_1 = world.add(world.root()).unwrap();
+        let entity_1_1 = world.add(entity_1).unwrap();
+
+        world.register(entity_1, Position::new(0, 0));
+
+        let mut query: Query<(EntityKey, &Position), ParentIter> = Query::new_parent(&world, entity_1_1);
+        let (key, position) = query.next().unwrap();
+        assert_eq!(key, entity_1);
+        assert_eq!(position, &Position::new(0, 0));
+        assert!(query.next().is_none());
+    }
+    #[test]
     fn query_multiple() {
         let mut world = Treecs::new();
         let entity_1 = world.add(world.root()).unwrap();
@@ -1,6 +1,6 @@
-use std::marker::PhantomData;
+use std::{any::TypeId, marker::PhantomData, sync::Arc};
 
-use crate::{BuildContext, WidgetContext, renderer::Renderer, state::State, treecs::component::{Component, ComponentEntry, ComponentEntryMut}};
+use crate::{BuildContext, WidgetContext, profile::{self, Phase}, renderer::Renderer, signal::Signals, state::State, treecs::component::{Component, ComponentEntry, ComponentEntryMut}};
 
 
 
@@ -10,6 +10,20 @@ use crate::{BuildContext, WidgetContext, renderer::Renderer, state::State, treec
 
 pub trait RenderAble<R> {
     fn mount<'gui>(&self, context: WidgetContext<'gui, R>) -> WidgetContext<'gui, R>;
+    /// the concrete type of the widget, also when called through a `dyn RenderAble`
+    fn widget_type(&self) -> WidgetType where Self: 'static {
+        WidgetType::of::<Self>()
+    }
+}
+
+/// a shared widget is mounted as the widget it points to, so stateful widgets can hand out their children on every build
+impl<R, W: RenderAble<R> + ?Sized + 'static> RenderAble<R> for Arc<W> {
+    fn mount<'gui>(&self, context: WidgetContext<'gui, R>) -> WidgetContext<'gui, R> {
+        (**self).mount(context)
+    }
+    fn widget_type(&self) -> WidgetType {
+        (**self).widget_type()
+    }
 }
 
 
@@ -19,6 +33,13 @@ pub trait Widget<R>: RenderAble<R> {
     fn create_state(&self) -> Self::State;
 
     fn build<'gui>(&self, state: State<Self>, context: &mut BuildContext<'gui>) -> impl RenderAble<R> + 'static;
+
+    /// called once after the widget and its subtree are mounted for the first time
+    fn init_state<'gui>(&self, _context: &mut BuildContext<'gui>) {}
+    /// called when a rebuild of a parent gives this entity a new configuration, `old` is the configuration it replaces
+    fn did_update_widget<'gui>(&self, _old: &Self, _context: &mut BuildContext<'gui>) {}
+    /// called right before the entity is removed from the tree
+    fn dispose<'gui>(&self, _context: &mut BuildContext<'gui>) {}
 }
 
 /*impl<R, T> RenderAble<R> for T
@@ -40,8 +61,197 @@ where T: Widget<R>,
 
 pub trait Render<Widget: ?Sized>: Sized + 'static
 {
-    // add a change or update method that allows a user to change data in the renderer as optimisation?
     fn mount<'gui>(widget: &Widget, context: &mut WidgetContext<'gui, Self>);
     fn after_mount<'gui>(widget: &Widget, context: &mut WidgetContext<'gui, Self>){}
+    /// patches the render item mounted for `old` in place, a rebuild reused its entity for `new`
+    ///
+    /// the default mounts a new item, renderers override it to only change what differs.
+    /// only called for widgets mounted with [`WidgetContext::mount_render`]
+    fn update<'gui>(old: &Widget, new: &Widget, context: &mut WidgetContext<'gui, Self>) {
+        let _ = old;
+        Self::mount(new, context);
+    }
+    /// called right before the entity of `widget` is removed, renderers release what they keep for its item
+    fn unmount<'gui>(_widget: &Widget, _context: &mut WidgetContext<'gui, Self>) {}
+}
+
+/// the widget a render item was last mounted for, [`Render::update`] compares it with the next one
+pub(crate) struct Mounted<W>(pub(crate) W);
+
+/// component stored next to [`Mounted`] so the gui can unmount an entity without knowing its widget type
+pub(crate) struct Unmount<R> {
+    pub(crate) unmount: for<'gui> fn(&mut WidgetContext<'gui, R>),
+}
+impl<R> Clone for Unmount<R> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<R> Copy for Unmount<R> {}
+impl<R: 'static> Unmount<R> {
+    pub(crate) fn new<W: Component>() -> Self where R: Render<W> {
+        Self {
+            unmount: unmount::<R, W>
+        }
+    }
+}
+fn unmount<R, W>(context: &mut WidgetContext<'_, R>)
+where R: Render<W> + 'static,
+      W: Component
+{
+    if let Some(Mounted(widget)) = context.tree.remove_component::<Mounted<W>>(context.key) {
+        R::unmount(&widget, context);
+    }
 }
 
+/// component holding the type of the widget that mounted an entity
+///
+/// on a rebuild an entity is only reused when the new widget has the same type
+#[derive(Debug, Clone, Copy)]
+pub struct WidgetType {
+    id: TypeId,
+    name: &'static str
+}
+impl WidgetType {
+    pub fn of<W: ?Sized + 'static>() -> Self {
+        Self {
+            id: TypeId::of::<W>(),
+            name: std::any::type_name::<W>()
+        }
+    }
+    pub fn id(&self) -> TypeId {
+        self.id
+    }
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+impl PartialEq for WidgetType {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+impl Eq for WidgetType {}
+
+/// component stored on the entity of every stateful widget so the gui can rebuild and dispose it without knowing its type
+pub(crate) struct Lifecycle<R> {
+    pub(crate) rebuild: for<'gui> fn(WidgetContext<'gui, R>) -> WidgetContext<'gui, R>,
+    pub(crate) dispose: for<'gui> fn(&mut BuildContext<'gui>),
+}
+impl<R> Clone for Lifecycle<R> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<R> Copy for Lifecycle<R> {}
+impl<R: Renderer + 'static> Lifecycle<R> {
+    pub(crate) fn new<W: Widget<R> + Component>() -> Self {
+        Self {
+            rebuild: rebuild::<R, W>,
+            dispose: dispose::<R, W>
+        }
+    }
+}
+
+/// builds the widget stored on the entity and mounts the result as its only child
+fn rebuild<'gui, R, W>(mut context: WidgetContext<'gui, R>) -> WidgetContext<'gui, R>
+where R: Renderer + 'static,
+      W: Widget<R> + Component
+{
+    // a parent that rebuilds this widget already takes care of a pending rebuild
+    context.runtime.clear_dirty(context.key);
+    if let Some(signals) = context.get_component::<Signals>() {
+        signals.start_build();
+    }
+    let built = {
+        let mut build_context = context.get_buildcontext();
+        let widget = build_context.tree
+            .get_component::<W>(build_context.key)
+            .expect("a stateful widget is always stored on its entity");
+        let state = State::new(context.get_buildcontext());
+        let _phase = profile::enter_span(Phase::Build, tracing::info_span!("build", widget = std::any::type_name::<W>()));
+        profile::count_build();
+        widget.build(state, &mut build_context)
+    };
+    context.cursor = 0;
+    context.mount_child(&built).remove_unmounted_children()
+}
+fn dispose<R, W>(context: &mut BuildContext<'_>)
+where W: Widget<R> + Component
+{
+    if let Some(widget) = context.tree.get_component::<W>(context.key) {
+        widget.dispose(context);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{test_utils::{Label, Lifecycles, Log, TestRenderer, Tracked}, Gui};
+
+    #[test]
+    fn init_state_runs_after_first_mount() {
+        let (lifecycles, log) = Lifecycles::new("a", Label::new("child"));
+        let mut gui = Gui::new(TestRenderer::new());
+        gui.root_widget_context().mount_widget(&lifecycles);
+        assert_eq!(*log.lock().unwrap(), vec!["build a", "init a"]);
+        gui.render();
+        assert_eq!(gui.renderer().rendered(), vec!["child"]);
+    }
+    #[test]
+    fn did_update_widget_keeps_entity() {
+        let (parent, log) = Lifecycles::new("parent", Label::new("child"));
+        let (child, _) = Lifecycles::new("child", Label::new("before"));
+        let child = child.with_log(log.clone());
+        let parent = parent.with_child(child);
+
+        let mut gui = Gui::new(TestRenderer::new());
+        gui.root_widget_context().mount_widget(&parent);
+        let entity_count = gui.tree().entity_count();
+
+        gui.rebuild(gui.tree().root());
+        assert_eq!(gui.tree().entity_count(), entity_count);
+        assert_eq!(*log.lock().unwrap(), vec![
+            "build parent", "build child", "init child", "init parent",
+            "build parent", "update child", "build child"
+        ]);
+        gui.render();
+        assert_eq!(gui.renderer().rendered(), vec!["before"]);
+    }
+    #[test]
+    fn dispose_runs_before_remove() {
+        let (parent, log) = Lifecycles::new("parent", Label::new("child"));
+        let (child, _) = Lifecycles::new("child", Label::new("leaf"));
+        let parent = parent.with_child(child.with_log(log.clone()));
+
+        let mut gui = Gui::new(TestRenderer::new());
+        gui.root_widget_context().mount_widget(&parent);
+        log.lock().unwrap().clear();
+
+        let root = gui.tree().root();
+        let child = *gui.tree().linkdata(root).unwrap().children().get_left().unwrap();
+        gui.remove(child);
+        assert_eq!(*log.lock().unwrap(), vec!["dispose child"]);
+        assert_eq!(gui.tree().entity_count(), 1);
+    }
+    #[test]
+    fn render_items_are_patched_and_unmounted() {
+        let log = Log::default();
+        let mut gui = Gui::new(TestRenderer::new());
+        gui.root_widget_context().mount_child(&Tracked { text: "a", log: log.clone() });
+        let root = gui.tree().root();
+        let child = *gui.tree().linkdata(root).unwrap().children().get_left().unwrap();
+
+        // mounting again at the same position reuses the entity
+        gui.root_widget_context().mount_child(&Tracked { text: "b", log: log.clone() });
+        assert_eq!(*gui.tree().linkdata(root).unwrap().children().get_left().unwrap(), child);
+        gui.render();
+        assert_eq!(gui.renderer().rendered(), vec!["b"]);
+
+        // a different widget type replaces the entity
+        gui.root_widget_context().mount_child(&Label::new("label"));
+        gui.root_widget_context().mount_child(&Label::new("label"));
+        assert_eq!(*log.lock().unwrap(), vec!["mount a", "update a -> b", "unmount b"]);
+        gui.render();
+        assert_eq!(gui.renderer().rendered(), vec!["label"]);
+    }
+}
@@ -0,0 +1,208 @@
+//! serves the devtools inspector of a gui over http and a websocket
+//!
+//! ```ignore
+//! let devtools = DevtoolsServer::start("127.0.0.1:8090")?;
+//! let mut gui = Gui::new(renderer).with_devtools(Devtools::new().with_debuggable::<u32>());
+//! loop {
+//!     // .. handle input and render
+//!     devtools.sync(&mut gui);
+//! }
+//! ```
+
+use std::{
+    io,
+    net::{SocketAddr, ToSocketAddrs},
+    sync::mpsc,
+    thread,
+};
+
+use futures_util::{SinkExt, StreamExt};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    sync::watch,
+};
+use tokio_tungstenite::accept_async;
+use tracing::Instrument;
+use visora_core::{devtools::entity_from_id, renderer::Renderer, treecs::EntityKey, Gui};
+
+/// the web ui of the inspector, it connects to the websocket of the address it was loaded from
+const PAGE: &str = include_str!("inspector.html");
+
+/// serves the inspector from its own thread, so both the async ssr loop and the vello event loop can use it
+///
+/// the page at the address shows the tree sent by [`DevtoolsServer::sync`], clicking an entity selects it and
+/// the renderers of the gui highlight it
+pub struct DevtoolsServer {
+    address: SocketAddr,
+    snapshots: watch::Sender<String>,
+    selections: mpsc::Receiver<Option<EntityKey>>,
+}
+impl DevtoolsServer {
+    /// starts serving on `address`, the page and the websocket share it
+    pub fn start(address: impl ToSocketAddrs) -> io::Result<Self> {
+        let listener = std::net::TcpListener::bind(address)?;
+        listener.set_nonblocking(true)?;
+        let address = listener.local_addr()?;
+        let (snapshots, receiver) = watch::channel(String::new());
+        let (selected, selections) = mpsc::channel();
+        thread::Builder::new().name("visora-devtools".to_owned()).spawn(move || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .expect("the devtools need a tokio runtime");
+            runtime.block_on(serve(listener, receiver, selected));
+        })?;
+        Ok(Self { address, snapshots, selections })
+    }
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+    /// sends the tree of `gui` to the inspectors when it changed and applies the entities they selected
+    ///
+    /// call it after every render, the gui needs [`visora_core::devtools::Devtools`].
+    /// returns whether the selection changed, the gui has to render again to show it
+    pub fn sync<R: Renderer>(&self, gui: &mut Gui<R>) -> bool {
+        let mut changed = false;
+        if let Some(devtools) = gui.devtools_mut() {
+            for entity in self.selections.try_iter() {
+                devtools.select(entity);
+                changed = true;
+            }
+        }
+        if let Some(snapshot) = gui.inspect() {
+            self.snapshots.send_if_modified(|old| {
+                let modified = *old != snapshot;
+                if modified {
+                    *old = snapshot;
+                }
+                modified
+            });
+        }
+        changed
+    }
+}
+
+async fn serve(listener: std::net::TcpListener, snapshots: watch::Receiver<String>, selected: mpsc::Sender<Option<EntityKey>>) {
+    let listener = TcpListener::from_std(listener).expect("the listener is non blocking");
+    loop {
+        let Ok((stream, _)) = listener.accept().await else { continue };
+        tokio::spawn(connection(stream, snapshots.clone(), selected.clone()));
+    }
+}
+
+/// answers a websocket upgrade with the tree, every other request with the page
+async fn connection(stream: TcpStream, mut snapshots: watch::Receiver<String>, selected: mpsc::Sender<Option<EntityKey>>) {
+    if !is_upgrade(&stream).await {
+        return page(stream).await;
+    }
+    let Ok(socket) = accept_async(stream).await else { return };
+    let (mut sink, mut messages) = socket.split();
+    let first = snapshots.borrow_and_update().clone();
+    let span = tracing::info_span!("devtools_send", bytes = first.len());
+    if sink.send(first.into()).instrument(span).await.is_err() {
+        return;
+    }
+    loop {
+        tokio::select! {
+            changed = snapshots.changed() => {
+                if changed.is_err() {
+                    break;
+                }
+                let snapshot = snapshots.borrow_and_update().clone();
+                let span = tracing::info_span!("devtools_send", bytes = snapshot.len());
+                if sink.send(snapshot.into()).instrument(span).await.is_err() {
+                    break;
+                }
+            }
+            message = messages.next() => {
+                let Some(Ok(message)) = message else { break };
+                if let Some(entity) = message.to_text().ok().and_then(parse_selection) {
+                    let _ = selected.send(entity);
+                }
+            }
+        }
+    }
+}
+
+/// whether the request headers ask for a websocket, they are peeked so the handshake can still read them
+async fn is_upgrade(stream: &TcpStream) -> bool {
+    let mut buffer = [0; 4096];
+    let mut read = 0;
+    // a request can arrive in more than one packet
+    for _ in 0..10 {
+        read = stream.peek(&mut buffer).await.unwrap_or_default();
+        if read == 0 || read == buffer.len() || buffer[..read].windows(4).any(|x| x == b"\r\n\r\n") {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+    asks_for_websocket(&String::from_utf8_lossy(&buffer[..read]))
+}
+/// whether a request has an `Upgrade: websocket` header
+fn asks_for_websocket(request: &str) -> bool {
+    request.lines()
+        .skip(1)
+        .take_while(|x| !x.is_empty())
+        .filter_map(|x| x.split_once(':'))
+        .any(|(name, value)| name.trim().eq_ignore_ascii_case("upgrade") && value.trim().eq_ignore_ascii_case("websocket"))
+}
+
+async fn page(mut stream: TcpStream) {
+    let mut request = [0; 4096];
+    let _ = stream.read(&mut request).await;
+    let response = format!(
+        "HTTP/1.1 200 OK\r\n\
+        Content-Length: {}\r\n\
+        Content-Type: text/html; charset=utf-8\r\n\
+        Connection: close\r\n\
+        \r\n\
+        {}",
+        PAGE.len(),
+        PAGE
+    );
+    let _ = stream.write_all(response.as_bytes()).await;
+}
+
+/// `select|id` selects the entity, `select|` clears the selection
+fn parse_selection(message: &str) -> Option<Option<EntityKey>> {
+    let id = message.strip_prefix("select|")?;
+    match id {
+        "" => Some(None),
+        x => Some(Some(entity_from_id(x.parse().ok()?))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use visora_core::{devtools::entity_id, treecs::Treecs};
+
+    use super::{asks_for_websocket, parse_selection};
+
+    #[test]
+    fn selections_are_parsed() {
+        let mut tree = Treecs::new();
+        let entity = tree.add_root();
+        assert_eq!(parse_selection(&format!("select|{}", entity_id(entity))), Some(Some(entity)));
+        assert_eq!(parse_selection("select|"), Some(None));
+        assert_eq!(parse_selection("select|abc"), None);
+        assert_eq!(parse_selection("select|-1"), None);
+        assert_eq!(parse_selection("select|1|2"), None);
+        assert_eq!(parse_selection("select"), None);
+        assert_eq!(parse_selection("hover|1"), None);
+        assert_eq!(parse_selection(""), None);
+    }
+    #[test]
+    fn websocket_requests_are_told_apart() {
+        let request = |headers: &str| format!("GET / HTTP/1.1\r\nHost: 127.0.0.1:8090\r\n{headers}\r\n");
+        assert!(asks_for_websocket(&request("Connection: Upgrade\r\nUpgrade: websocket\r\n")));
+        assert!(asks_for_websocket(&request("connection: upgrade\r\nupgrade:WebSocket\r\n")));
+        assert!(!asks_for_websocket(&request("Accept: text/html\r\n")));
+        assert!(!asks_for_websocket(&request("Upgrade: h2c\r\n")));
+        assert!(!asks_for_websocket(&request("X-Upgrade: websocket\r\n")));
+        // a body isn't a header
+        assert!(!asks_for_websocket(&request("Content-Length: 18\r\n\r\nupgrade: websocket")));
+        assert!(!asks_for_websocket("GET /upgrade:websocket HTTP/1.1\r\n\r\n"));
+        assert!(!asks_for_websocket(""));
+    }
+}
@@ -19,11 +19,11 @@ pub fn renderable(input: TokenStream) -> TokenStream {
     }
 
     let wherec = input.generics.make_where_clause();
-    let predicate = quote::quote!{R: Renderer}.into();
+    let predicate = quote::quote!{R: ::visora_core::renderer::Renderer}.into();
     let predicate = parse_macro_input!(predicate as WherePredicate);
     wherec.predicates.push(predicate);
 
-    let predicate = quote::quote!{Self: Widget<R>}.into();
+    let predicate = quote::quote!{Self: ::visora_core::widget::Widget<R> + ::core::clone::Clone + ::visora_core::Component}.into();
     let predicate = parse_macro_input!(predicate as WherePredicate);
     wherec.predicates.push(predicate);
 
@@ -32,22 +32,11 @@ pub fn renderable(input: TokenStream) -> TokenStream {
     let b = &input.generics.where_clause;
     let name = input.ident;
     quote::quote! {
-        impl < #a > RenderAble<R> for #name #params_before
+        impl < #a > ::visora_core::widget::RenderAble<R> for #name #params_before
         #b
         {
-            type StateHandle = ::visora_core::state::State<Self, <Self as Widget<R>>::State>;
-            fn mount<'gui>(&self, mut context: WidgetContext<'gui, R>) -> WidgetContext<'gui, R> {
-                context.insert_component(self.create_state());
-                let mut build_context = context.get_buildcontext();
-                /*let build = {
-                    let state = context.get_component::<<Self as Widget<R>>::State>().unwrap();
-                    <Self as Widget<R>>::build(&self, state, &mut build_context)
-                };
-                */
-                let state = ::visora_core::state::State::new();
-                let build = <Self as Widget<R>>::build(&self, state, &mut build_context);
-
-                build.mount(context)
+            fn mount<'gui>(&self, context: ::visora_core::WidgetContext<'gui, R>) -> ::visora_core::WidgetContext<'gui, R> {
+                context.mount_widget(self)
             }
         }
     }.into()
@@ -0,0 +1,121 @@
+use visora_core::{event::{Event, Key, KeyState, Modifiers, Point, PointerButton}, layout::Size};
+
+/// translates a message sent by the browser into the id of the element it targets and the event
+///
+/// messages have the form `kind|id|args..`:
+/// - `pointerdown|id|x|y|button`, `pointerup|id|x|y|button`
+/// - `pointermove|id|x|y`, `pointerenter|id|x|y`, `pointerleave|id|x|y`
+/// - `key|id|down or up|shift|ctrl|alt|meta|key`
+/// - `text|id|data`
+/// - `scroll|id|x|y|dx|dy`, the wheel
+/// - `scrolled|id|left|top|width|height`, the offset and the size of an element the browser scrolled
+pub fn parse_event(message: &str) -> Option<(&str, Event)> {
+    let mut parts = message.splitn(3, '|');
+    let kind = parts.next()?;
+    let id = parts.next()?;
+    let args = parts.next().unwrap_or_default();
+    if kind == "text" {
+        return Some((id, Event::TextInput(args.to_owned())));
+    }
+    // the key is last, a character key can be a `|`
+    let args: Vec<&str> = match kind {
+        "key" => args.splitn(6, '|').collect(),
+        _ => args.split('|').collect(),
+    };
+    let number = |idx: usize| args.get(idx)?.parse::<f32>().ok();
+    let flag = |idx: usize| args.get(idx) == Some(&"1");
+    let event = match kind {
+        "pointerdown" | "pointerup" => {
+            let position = Point::new(number(0)?, number(1)?);
+            let button = pointer_button(args.get(2)?.parse().ok()?);
+            match kind {
+                "pointerdown" => Event::PointerDown { position, button },
+                _ => Event::PointerUp { position, button },
+            }
+        }
+        "pointermove" => Event::PointerMove { position: Point::new(number(0)?, number(1)?) },
+        "pointerenter" => Event::PointerEnter { position: Point::new(number(0)?, number(1)?) },
+        "pointerleave" => Event::PointerLeave { position: Point::new(number(0)?, number(1)?) },
+        "key" => Event::Key {
+            state: match *args.first()? {
+                "down" => KeyState::Pressed,
+                _ => KeyState::Released,
+            },
+            key: key(args.get(5)?),
+            modifiers: Modifiers {
+                shift: flag(1),
+                ctrl: flag(2),
+                alt: flag(3),
+                meta: flag(4),
+            },
+        },
+        "scroll" => Event::Scroll {
+            position: Point::new(number(0)?, number(1)?),
+            delta: Point::new(number(2)?, number(3)?),
+        },
+        "scrolled" => Event::Scrolled {
+            offset: Point::new(number(0)?, number(1)?),
+            viewport: Size::new(number(2)?, number(3)?),
+        },
+        _ => return None,
+    };
+    Some((id, event))
+}
+
+/// `MouseEvent.button` of the browser
+fn pointer_button(button: u16) -> PointerButton {
+    match button {
+        0 => PointerButton::Primary,
+        1 => PointerButton::Middle,
+        2 => PointerButton::Secondary,
+        x => PointerButton::Other(x),
+    }
+}
+
+/// `KeyboardEvent.key` of the browser
+fn key(name: &str) -> Key {
+    match name {
+        "Enter" => Key::Enter,
+        " " => Key::Space,
+        "Tab" => Key::Tab,
+        "Escape" => Key::Escape,
+        "Backspace" => Key::Backspace,
+        "Delete" => Key::Delete,
+        "ArrowUp" => Key::ArrowUp,
+        "ArrowDown" => Key::ArrowDown,
+        "ArrowLeft" => Key::ArrowLeft,
+        "ArrowRight" => Key::ArrowRight,
+        "Home" => Key::Home,
+        "End" => Key::End,
+        x if x.chars().count() == 1 => Key::Character(x.to_owned()),
+        _ => Key::Unidentified,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use visora_core::event::{Event, Key, KeyState, Modifiers};
+
+    use super::parse_event;
+
+    fn key_event(message: &str) -> Option<(Key, Modifiers)> {
+        match parse_event(message)? {
+            (_, Event::Key { key, modifiers, .. }) => Some((key, modifiers)),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn keys_keep_their_modifiers() {
+        let (key, modifiers) = key_event("key|e1|down|1|0|1|0|a").unwrap();
+        assert_eq!(key, Key::Character("a".to_owned()));
+        assert!(modifiers.shift && !modifiers.ctrl && modifiers.alt && !modifiers.meta);
+        // the pipe is a key like the others
+        let (key, modifiers) = key_event("key||down|1|0|0|1||").unwrap();
+        assert_eq!(key, Key::Character("|".to_owned()));
+        assert!(modifiers.shift && !modifiers.ctrl && !modifiers.alt && modifiers.meta);
+        assert_eq!(key_event("key||up|0|0|0|0| ").unwrap().0, Key::Space);
+        assert!(matches!(parse_event("key||up|0|0|0|0|Enter"), Some((_, Event::Key { state: KeyState::Released, key: Key::Enter, .. }))));
+        assert_eq!(key_event("key||down|0|0|0|0"), None);
+    }
+}
@@ -0,0 +1,55 @@
+use std::sync::Arc;
+
+use tokio::{runtime::Handle, sync::Notify, task::AbortHandle};
+use visora_core::task::{Executor, Task, TaskHandle};
+
+/// runs the tasks spawned by widgets on a tokio runtime
+#[derive(Clone)]
+pub struct TokioExecutor {
+    handle: Handle,
+    finished: Arc<Notify>,
+}
+/// see [`TokioExecutor::new`], panics outside of a tokio runtime as well
+impl Default for TokioExecutor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl TokioExecutor {
+    /// an executor for the runtime of the current tokio context
+    ///
+    /// panics when called outside of a tokio runtime
+    pub fn new() -> Self {
+        Self::with_handle(Handle::current())
+    }
+    pub fn with_handle(handle: Handle) -> Self {
+        Self {
+            handle,
+            finished: Arc::new(Notify::new()),
+        }
+    }
+    /// resolves when a task finished, after which `Gui::dispatch_pending` has a transaction to dispatch
+    pub async fn task_finished(&self) {
+        self.finished.notified().await
+    }
+}
+impl Executor for TokioExecutor {
+    fn spawn(&self, task: Task) -> Box<dyn TaskHandle> {
+        let finished = self.finished.clone();
+        let handle = self.handle.spawn(async move {
+            task.await;
+            finished.notify_one();
+        });
+        Box::new(TokioTask(handle.abort_handle()))
+    }
+}
+
+struct TokioTask(AbortHandle);
+impl TaskHandle for TokioTask {
+    fn abort(&self) {
+        self.0.abort();
+    }
+    fn is_finished(&self) -> bool {
+        self.0.is_finished()
+    }
+}
@@ -2,8 +2,8 @@ use std::{borrow::Cow, collections::HashMap, fmt::Write, fs::File, io::{Read, Wr
 
 use itertools::Itertools;
 use uuid::Uuid;
-use visora::widget::{button::TextButton, center::Center, list::Hlist, text::{self, RichText, Text}};
-use visora_core::{renderer::Renderer, treecs::iterators::breadth::{BreadthInfo, BreadthIter}, widget::Render};
+use visora::widget::{button::TextButton, center::Center, container::Container, flex::{Column, Expanded, Flexible, Row, Spacer}, grid::{Grid, GridItem}, lazy::{LazyItems, LazySlot}, list::Hlist, opacity::Opacity, scroll::{ScrollViewport, Scrollbar}, text::{self, RichText, Text}};
+use visora_core::{layout::{flex::{CrossAxisAlignment, Flex, FlexChild, MainAxisAlignment, MainAxisSize}, grid::{CellAlignment, Grid as GridLayout, GridChild, Track}, lazy::Lazy, Axis}, profile::{self, Phase}, renderer::Renderer, treecs::{iterators::breadth::{BreadthInfo, VisibleIter}, EntityKey}, widget::Render};
 
 mod tags;
 
@@ -11,6 +11,10 @@ mod tags;
 pub struct Attributes{
     on_click: Option<Cow<'static, str>>,
     styles: HashMap<&'static str, Cow<'static, str>>,
+    /// written as `data-` attributes, for the script of the page
+    data: HashMap<&'static str, String>,
+    /// written on the children of the element that don't grow, see [`flex_styles`]
+    child_styles: &'static [&'static str],
     id: String
 }
 impl Attributes {
@@ -18,6 +22,8 @@ impl Attributes {
         Self {
             on_click: None,
             styles: HashMap::new(),
+            data: HashMap::new(),
+            child_styles: &[],
             id: Uuid::new_v4().to_string()
         }
     }
@@ -32,6 +38,13 @@ impl Attributes {
     pub fn add_style(&mut self, key: &'static str, value: Cow<'static, str>){
         self.styles.insert(key, value);
     }
+    pub fn with_data(mut self, key: &'static str, value: String) -> Self {
+        self.add_data(key, value);
+        self
+    }
+    pub fn add_data(&mut self, key: &'static str, value: String) {
+        self.data.insert(key, value);
+    }
     pub fn with_on_click(mut self, method: Cow<'static, str>) -> Self {
         self.add_on_click(method);
         self
@@ -40,10 +53,18 @@ impl Attributes {
         self.on_click = Some(method);
     }
     pub fn write(&self, dest: &mut String) -> Result<(), std::fmt::Error>{
+        self.write_outlined(dest, false)
+    }
+    /// writes the attributes, an outline marks the element selected in the devtools
+    pub fn write_outlined(&self, dest: &mut String, outline: bool) -> Result<(), std::fmt::Error>{
+        self.write_with(dest, if outline { &[OUTLINE] } else { &[] })
+    }
+    /// writes the attributes with `extra` styles that depend on where the element is
+    fn write_with(&self, dest: &mut String, extra: &[&str]) -> Result<(), std::fmt::Error>{
         dest.write_char(' ')?;
-        if self.styles.len() > 0 {
+        if self.styles.len() > 0 || !extra.is_empty() {
             dest.write_str("style=\"")?;
-            let attrs = self.styles.iter().map(|(key, val)| format!("{key}:{val}")).join(";");
+            let attrs = self.styles.iter().map(|(key, val)| format!("{key}:{val}")).chain(extra.iter().map(|x| x.to_string())).join(";");
             dest.write_str(&attrs)?;
             dest.write_str("\"")?;
             dest.write_char(' ')?;
@@ -53,23 +74,43 @@ impl Attributes {
             dest.write_str(&click)?;
             dest.write_str("\"")?;
         }
+        for (key, value) in &self.data {
+            write!(dest, "data-{key}=\"{value}\" ")?;
+        }
         write!(dest, "id=\"{}\"", self.id)?;
         Ok(())
     }
 }
 
+/// marks the element selected in the devtools
+const OUTLINE: &str = "outline:2px solid #f0f";
+
 pub struct HtmlRenderer{
-    last_render: String
+    last_render: String,
+    /// the entity of every element id in the last render, the browser does the hit testing and sends the id
+    entities: HashMap<String, EntityKey>,
+    /// the entity selected in the devtools
+    highlighted: Option<EntityKey>
 }
 impl HtmlRenderer {
     pub fn new() -> Self {
         Self {
-            last_render: String::new()
+            last_render: String::new(),
+            entities: HashMap::new(),
+            highlighted: None
         }
     }
     pub fn get_render(&self) -> &str {
         &self.last_render
     }
+    /// the entity that rendered the element with `id`
+    pub fn entity(&self, id: &str) -> Option<EntityKey> {
+        self.entities.get(id).copied()
+    }
+    /// the id of the element rendered by `entity`
+    pub fn element_id(&self, entity: EntityKey) -> Option<&str> {
+        self.entities.iter().find(|(_, key)| **key == entity).map(|(id, _)| id.as_str())
+    }
 }
 
 
@@ -86,20 +127,26 @@ pub struct HtmlTag{
 }
 impl HtmlTag {
     pub fn write_open(&self, dest: &mut String) -> Result<(), std::fmt::Error>{
+        self.write_open_outlined(dest, false)
+    }
+    pub fn write_open_outlined(&self, dest: &mut String, outline: bool) -> Result<(), std::fmt::Error>{
+        self.write_open_with(dest, if outline { &[OUTLINE] } else { &[] })
+    }
+    fn write_open_with(&self, dest: &mut String, extra: &[&str]) -> Result<(), std::fmt::Error>{
         match &self.tag {
             Tag::Div => {
                 dest.write_str("<div")?;
-                self.attributes.write(dest)?;
+                self.attributes.write_with(dest, extra)?;
                 dest.write_str(">")
             },
             Tag::Button => {
                 dest.write_str("<button")?;
-                self.attributes.write(dest)?;
+                self.attributes.write_with(dest, extra)?;
                 dest.write_str(">")
             }
             Tag::P(x) => {
                 dest.write_str("<p")?;
-                self.attributes.write(dest)?;
+                self.attributes.write_with(dest, extra)?;
                 dest.write_str(">")?;
                 dest.write_str(x)
             }
@@ -117,17 +164,39 @@ impl HtmlTag {
 
 impl Renderer for HtmlRenderer {
     type RenderItem = HtmlTag;
-    type QueryType<'gui> = BreadthIter<'gui>;
-    fn render<'gui>(&mut self, q: visora_core::treecs::query::Query<'gui, &Self::RenderItem, Self::QueryType<'gui>>) {
+    type QueryType<'gui> = VisibleIter<'gui>;
+    fn render<'gui>(&mut self, q: visora_core::treecs::query::Query<'gui, (EntityKey, &Self::RenderItem), Self::QueryType<'gui>>) {
+        // writing the html is the paint phase of ssr
+        let _phase = profile::enter_span(Phase::Paint, tracing::info_span!("ssr_render"));
         let mut out = String::new();
-        for (info, tag) in q {
+        self.entities.clear();
+        // the styles the parent element of the next one gives its children
+        let mut parents: Vec<&[&str]> = Vec::new();
+        for (info, (key, tag)) in q {
             match info {
-                BreadthInfo::MoveUp => tag.write_close(&mut out).unwrap(),
-                BreadthInfo::Other => tag.write_open(&mut out).unwrap()
+                BreadthInfo::MoveUp => {
+                    parents.pop();
+                    tag.write_close(&mut out).unwrap()
+                }
+                BreadthInfo::Other => {
+                    self.entities.insert(tag.attributes.id.clone(), key);
+                    let mut extra = Vec::new();
+                    if !tag.attributes.styles.contains_key("flex-grow") {
+                        extra.extend(parents.last().copied().unwrap_or_default());
+                    }
+                    if self.highlighted == Some(key) {
+                        extra.push(OUTLINE);
+                    }
+                    tag.write_open_with(&mut out, &extra).unwrap();
+                    parents.push(tag.attributes.child_styles);
+                }
             }
         }
         self.last_render = out;
     }
+    fn highlight(&mut self, entity: Option<EntityKey>) {
+        self.highlighted = entity;
+    }
 }
 
 impl Render<Text> for HtmlRenderer {
@@ -137,6 +206,13 @@ impl Render<Text> for HtmlRenderer {
             attributes: Attributes::new()
         });
     }
+    // the element keeps its id, so the page keeps its focus
+    fn update<'gui>(old: &Text, new: &Text, context: &mut visora_core::WidgetContext<'gui, Self>) {
+        let Some(tag) = context.get_renderer() else { return Self::mount(new, context) };
+        if old.data != new.data {
+            tag.tag = Tag::P(new.data.clone());
+        }
+    }
 }
 
 /*impl Render<Vlist<Self>> for HtmlRenderer {
@@ -159,19 +235,34 @@ impl Render<Hlist<Self>> for HtmlRenderer {
             attributes
         });
     }
+    fn update<'gui>(_old: &Hlist<Self>, new: &Hlist<Self>, context: &mut visora_core::WidgetContext<'gui, Self>) {
+        keep_tag(new, context);
+    }
 }
 impl Render<RichText> for HtmlRenderer {
     fn mount<'gui>(widget: &RichText, context: &mut visora_core::WidgetContext<'gui, Self>) {
         let attributes = Attributes::new()
-            .with_style("color", Cow::Owned(format!("#{:06X}", widget.color().value())));
+            .with_style("color", Cow::Owned(color(widget)));
         context.mount_renderer(HtmlTag {
             tag: Tag::P(widget.text().to_owned()),
             attributes
         });
     }
+    fn update<'gui>(old: &RichText, new: &RichText, context: &mut visora_core::WidgetContext<'gui, Self>) {
+        let Some(tag) = context.get_renderer() else { return Self::mount(new, context) };
+        if old.text() != new.text() {
+            tag.tag = Tag::P(new.text().to_owned());
+        }
+        if old.color() != new.color() {
+            tag.attributes.add_style("color", Cow::Owned(color(new)));
+        }
+    }
+}
+fn color(widget: &RichText) -> String {
+    format!("#{:06X}", widget.color().value())
 }
-impl Render<Center<Self>> for HtmlRenderer {
-    fn mount<'gui>(widget: &Center<Self>, context: &mut visora_core::WidgetContext<'gui, Self>) {
+impl<W: 'static> Render<Center<W>> for HtmlRenderer {
+    fn mount<'gui>(widget: &Center<W>, context: &mut visora_core::WidgetContext<'gui, Self>) {
         let attributes = Attributes::new()
             .with_style("display", Cow::Borrowed("flex"))
             .with_style("justify-content", Cow::Borrowed("center"))
@@ -181,33 +272,742 @@ impl Render<Center<Self>> for HtmlRenderer {
             attributes
         });
     }
+    fn update<'gui>(_old: &Center<W>, new: &Center<W>, context: &mut visora_core::WidgetContext<'gui, Self>) {
+        keep_tag(new, context);
+    }
 }
-/*impl Render<Container<Self>> for HtmlRenderer {
+impl Render<Container<Self>> for HtmlRenderer {
     fn mount<'gui>(widget: &Container<Self>, context: &mut visora_core::WidgetContext<'gui, Self>) {
         let mut attributes = Attributes::new();
-        let padding = widget.insets();
-        if padding.is_even() {
-            attributes.add_style("padding", Cow::Owned(format!("{}px", padding.top())));
-        } else {
-            attributes.add_style("padding", Cow::Owned(format!("{}px {}px {}px {}px", padding.top(), padding.right(), padding.bottom(), padding.bottom())));
+        container_styles(widget, &mut attributes);
+        context.mount_renderer(HtmlTag {
+            tag: Tag::Div,
+            attributes
+        });
+    }
+    fn update<'gui>(old: &Container<Self>, new: &Container<Self>, context: &mut visora_core::WidgetContext<'gui, Self>) {
+        let Some(tag) = context.get_renderer() else { return Self::mount(new, context) };
+        let changed = old.insets() != new.insets() || old.bg() != new.bg()
+            || old.width() != new.width() || old.height() != new.height();
+        if changed {
+            tag.attributes.styles.clear();
+            container_styles(new, &mut tag.attributes);
         }
-
-
+    }
+}
+/// the styles of the div of a container
+fn container_styles(widget: &Container<HtmlRenderer>, attributes: &mut Attributes) {
+    let padding = widget.insets();
+    if padding.is_even() {
+        attributes.add_style("padding", Cow::Owned(format!("{}px", padding.top())));
+    } else {
+        attributes.add_style("padding", Cow::Owned(format!("{}px {}px {}px {}px", padding.top(), padding.right(), padding.bottom(), padding.left())));
+    }
+    // the size includes the padding, like in the layout of the other backends
+    attributes.add_style("box-sizing", Cow::Borrowed("border-box"));
+    let bg = widget.bg();
+    attributes.add_style("background-color", Cow::Owned(format!("rgba({}, {}, {}, {})", bg.red(), bg.green(), bg.blue(), bg.opacity())));
+    if let Some(width) = widget.width() {
+        attributes.add_style("width", Cow::Owned(format!("{width}px")));
+    }
+    if let Some(height) = widget.height() {
+        attributes.add_style("height", Cow::Owned(format!("{height}px")));
+    }
+}
+impl Render<Opacity<Self>> for HtmlRenderer {
+    fn mount<'gui>(widget: &Opacity<Self>, context: &mut visora_core::WidgetContext<'gui, Self>) {
+        let attributes = Attributes::new()
+            .with_style("opacity", Cow::Owned(widget.opacity().to_string()));
         context.mount_renderer(HtmlTag {
             tag: Tag::Div,
             attributes
         });
     }
-}*/
+    fn update<'gui>(old: &Opacity<Self>, new: &Opacity<Self>, context: &mut visora_core::WidgetContext<'gui, Self>) {
+        let Some(tag) = context.get_renderer() else { return Self::mount(new, context) };
+        if old.opacity() != new.opacity() {
+            tag.attributes.add_style("opacity", Cow::Owned(new.opacity().to_string()));
+        }
+    }
+}
 
 impl Render<TextButton> for HtmlRenderer {
     fn mount<'gui>(widget: &TextButton, context: &mut visora_core::WidgetContext<'gui, Self>) {
-        let attributes = Attributes::new()
-            .with_on_click(Cow::Borrowed("socket.send('clicked')"));
+        // clicks reach the button through the pointer events the page sends for every element
+        let attributes = Attributes::new();
 
         context.mount_renderer(HtmlTag {
             tag: Tag::Button,
             attributes
         });
     }
+    // the text is a child, a focused button stays focused when its label changes
+    fn update<'gui>(_old: &TextButton, new: &TextButton, context: &mut visora_core::WidgetContext<'gui, Self>) {
+        keep_tag(new, context);
+    }
+}
+/// keeps the element of a widget whose attributes don't depend on its configuration, so it keeps its id
+fn keep_tag<W>(new: &W, context: &mut visora_core::WidgetContext<'_, HtmlRenderer>)
+where HtmlRenderer: Render<W>
+{
+    if context.get_renderer().is_none() {
+        <HtmlRenderer as Render<W>>::mount(new, context);
+    }
+}
+
+impl Render<Row<Self>> for HtmlRenderer {
+    fn mount<'gui>(widget: &Row<Self>, context: &mut visora_core::WidgetContext<'gui, Self>) {
+        mount_flex(widget.layout(), context);
+    }
+    fn update<'gui>(old: &Row<Self>, new: &Row<Self>, context: &mut visora_core::WidgetContext<'gui, Self>) {
+        update_flex(old.layout(), new.layout(), context);
+    }
+}
+impl Render<Column<Self>> for HtmlRenderer {
+    fn mount<'gui>(widget: &Column<Self>, context: &mut visora_core::WidgetContext<'gui, Self>) {
+        mount_flex(widget.layout(), context);
+    }
+    fn update<'gui>(old: &Column<Self>, new: &Column<Self>, context: &mut visora_core::WidgetContext<'gui, Self>) {
+        update_flex(old.layout(), new.layout(), context);
+    }
+}
+fn mount_flex(layout: &Flex, context: &mut visora_core::WidgetContext<'_, HtmlRenderer>) {
+    let mut attributes = Attributes::new();
+    flex_styles(layout, &mut attributes);
+    context.mount_renderer(HtmlTag {
+        tag: Tag::Div,
+        attributes
+    });
+}
+fn update_flex(old: &Flex, new: &Flex, context: &mut visora_core::WidgetContext<'_, HtmlRenderer>) {
+    let Some(tag) = context.get_renderer() else { return mount_flex(new, context) };
+    if old != new {
+        tag.attributes.styles.clear();
+        flex_styles(new, &mut tag.attributes);
+    }
+}
+/// the styles of the div of a row or a column, the browser places the children like [`Flex`] does
+///
+/// the children without a flex are written with `flex-shrink:0`, they overflow like in the other backends.
+/// a stretched child is as large as the flex box even with a size of its own, like in the layout
+fn flex_styles(layout: &Flex, attributes: &mut Attributes) {
+    attributes.add_style("display", Cow::Borrowed("flex"));
+    let (direction, main, cross) = match layout.axis {
+        Axis::Horizontal => ("row", "width", "height"),
+        Axis::Vertical => ("column", "height", "width"),
+    };
+    attributes.child_styles = match (layout.cross_alignment, layout.axis) {
+        (CrossAxisAlignment::Stretch, Axis::Horizontal) => &["flex-shrink:0", "height:auto"],
+        (CrossAxisAlignment::Stretch, Axis::Vertical) => &["flex-shrink:0", "width:auto"],
+        _ => &["flex-shrink:0"],
+    };
+    attributes.add_style("flex-direction", Cow::Borrowed(direction));
+    attributes.add_style("justify-content", Cow::Borrowed(match layout.main_alignment {
+        MainAxisAlignment::Start => "flex-start",
+        MainAxisAlignment::End => "flex-end",
+        MainAxisAlignment::Center => "center",
+        MainAxisAlignment::SpaceBetween => "space-between",
+        MainAxisAlignment::SpaceAround => "space-around",
+        MainAxisAlignment::SpaceEvenly => "space-evenly",
+    }));
+    attributes.add_style("align-items", Cow::Borrowed(match layout.cross_alignment {
+        CrossAxisAlignment::Start => "flex-start",
+        CrossAxisAlignment::End => "flex-end",
+        CrossAxisAlignment::Center => "center",
+        CrossAxisAlignment::Stretch => "stretch",
+    }));
+    if layout.spacing > 0.0 {
+        attributes.add_style("gap", Cow::Owned(format!("{}px", layout.spacing)));
+    }
+    attributes.add_style(main, Cow::Borrowed(match layout.main_size {
+        MainAxisSize::Max => "100%",
+        MainAxisSize::Min => "fit-content",
+    }));
+    attributes.add_style(cross, Cow::Borrowed(match layout.cross_alignment {
+        CrossAxisAlignment::Stretch => "100%",
+        _ => "fit-content",
+    }));
+}
+impl Render<Expanded<Self>> for HtmlRenderer {
+    fn mount<'gui>(widget: &Expanded<Self>, context: &mut visora_core::WidgetContext<'gui, Self>) {
+        mount_flex_child(widget.flex(), context);
+    }
+    fn update<'gui>(_old: &Expanded<Self>, new: &Expanded<Self>, context: &mut visora_core::WidgetContext<'gui, Self>) {
+        update_flex_child(new.flex(), context);
+    }
+}
+impl Render<Flexible<Self>> for HtmlRenderer {
+    fn mount<'gui>(widget: &Flexible<Self>, context: &mut visora_core::WidgetContext<'gui, Self>) {
+        mount_flex_child(widget.flex(), context);
+    }
+    fn update<'gui>(_old: &Flexible<Self>, new: &Flexible<Self>, context: &mut visora_core::WidgetContext<'gui, Self>) {
+        update_flex_child(new.flex(), context);
+    }
+}
+impl Render<Spacer> for HtmlRenderer {
+    fn mount<'gui>(widget: &Spacer, context: &mut visora_core::WidgetContext<'gui, Self>) {
+        mount_flex_child(widget.flex(), context);
+    }
+    fn update<'gui>(_old: &Spacer, new: &Spacer, context: &mut visora_core::WidgetContext<'gui, Self>) {
+        update_flex_child(new.flex(), context);
+    }
+}
+fn mount_flex_child(flex: FlexChild, context: &mut visora_core::WidgetContext<'_, HtmlRenderer>) {
+    let mut attributes = Attributes::new();
+    flex_child_styles(flex, &mut attributes);
+    context.mount_renderer(HtmlTag {
+        tag: Tag::Div,
+        attributes
+    });
+}
+fn update_flex_child(flex: FlexChild, context: &mut visora_core::WidgetContext<'_, HtmlRenderer>) {
+    let Some(tag) = context.get_renderer() else { return mount_flex_child(flex, context) };
+    flex_child_styles(flex, &mut tag.attributes);
+}
+/// the child grows from nothing to its share
+fn flex_child_styles(flex: FlexChild, attributes: &mut Attributes) {
+    attributes.add_style("flex-grow", Cow::Owned(flex.flex.to_string()));
+    attributes.add_style("flex-shrink", Cow::Borrowed("0"));
+    attributes.add_style("flex-basis", Cow::Borrowed("0px"));
+    attributes.add_style("min-width", Cow::Borrowed("0"));
+    attributes.add_style("min-height", Cow::Borrowed("0"));
+}
+
+impl Render<Grid<Self>> for HtmlRenderer {
+    fn mount<'gui>(widget: &Grid<Self>, context: &mut visora_core::WidgetContext<'gui, Self>) {
+        let mut attributes = Attributes::new();
+        grid_styles(widget.layout(), &mut attributes);
+        context.mount_renderer(HtmlTag {
+            tag: Tag::Div,
+            attributes
+        });
+    }
+    fn update<'gui>(old: &Grid<Self>, new: &Grid<Self>, context: &mut visora_core::WidgetContext<'gui, Self>) {
+        let Some(tag) = context.get_renderer() else { return Self::mount(new, context) };
+        if old.layout() != new.layout() {
+            tag.attributes.styles.clear();
+            grid_styles(new.layout(), &mut tag.attributes);
+        }
+    }
+}
+/// a css grid that sizes its tracks like the layout, the children are put at the cells the widget found for them
+fn grid_styles(layout: &GridLayout, attributes: &mut Attributes) {
+    attributes.add_style("display", Cow::Borrowed("grid"));
+    // a fraction track of the layout can get smaller than its children, auto tracks don't grow into the free space
+    let tracks = |tracks: &[Track]| tracks.iter()
+        .map(|x| match x {
+            Track::Fixed(x) => format!("{x}px"),
+            Track::Fraction(x) => format!("minmax(0, {x}fr)"),
+            Track::Auto => "auto".to_owned(),
+        })
+        .join(" ");
+    if !layout.columns.is_empty() {
+        attributes.add_style("grid-template-columns", Cow::Owned(tracks(&layout.columns)));
+    }
+    if !layout.rows.is_empty() {
+        attributes.add_style("grid-template-rows", Cow::Owned(tracks(&layout.rows)));
+    }
+    attributes.add_style("column-gap", Cow::Owned(format!("{}px", layout.column_gap)));
+    attributes.add_style("row-gap", Cow::Owned(format!("{}px", layout.row_gap)));
+    attributes.add_style("justify-content", Cow::Borrowed("start"));
+    attributes.add_style("align-content", Cow::Borrowed("start"));
+    attributes.add_style("justify-items", Cow::Borrowed(cell_alignment(layout.horizontal_alignment)));
+    attributes.add_style("align-items", Cow::Borrowed(cell_alignment(layout.vertical_alignment)));
+    // a grid with fraction tracks fills its parent along them, like in the layout
+    let fills = |tracks: &[Track]| Cow::Borrowed(match tracks.iter().any(|x| matches!(x, Track::Fraction(_))) {
+        true => "100%",
+        false => "fit-content",
+    });
+    attributes.add_style("width", fills(&layout.columns));
+    attributes.add_style("height", fills(&layout.rows));
+}
+fn cell_alignment(alignment: CellAlignment) -> &'static str {
+    match alignment {
+        CellAlignment::Start => "start",
+        CellAlignment::End => "end",
+        CellAlignment::Center => "center",
+        CellAlignment::Stretch => "stretch",
+    }
+}
+impl Render<GridItem<Self>> for HtmlRenderer {
+    fn mount<'gui>(widget: &GridItem<Self>, context: &mut visora_core::WidgetContext<'gui, Self>) {
+        let mut attributes = Attributes::new();
+        grid_item_styles(widget.grid_child(), &mut attributes);
+        context.mount_renderer(HtmlTag {
+            tag: Tag::Div,
+            attributes
+        });
+    }
+    fn update<'gui>(old: &GridItem<Self>, new: &GridItem<Self>, context: &mut visora_core::WidgetContext<'gui, Self>) {
+        let Some(tag) = context.get_renderer() else { return Self::mount(new, context) };
+        if old.grid_child() != new.grid_child() {
+            tag.attributes.styles.clear();
+            grid_item_styles(new.grid_child(), &mut tag.attributes);
+        }
+    }
+}
+fn grid_item_styles(child: GridChild, attributes: &mut Attributes) {
+    let area = child.area;
+    attributes.add_style("grid-column", Cow::Owned(format!("{} / span {}", area.column + 1, area.column_span)));
+    attributes.add_style("grid-row", Cow::Owned(format!("{} / span {}", area.row + 1, area.row_span)));
+    if let Some(x) = child.horizontal {
+        attributes.add_style("justify-self", Cow::Borrowed(cell_alignment(x)));
+    }
+    if let Some(x) = child.vertical {
+        attributes.add_style("align-self", Cow::Borrowed(cell_alignment(x)));
+    }
+    attributes.add_style("min-width", Cow::Borrowed("0"));
+    attributes.add_style("min-height", Cow::Borrowed("0"));
+}
+
+impl Render<ScrollViewport<Self>> for HtmlRenderer {
+    fn mount<'gui>(widget: &ScrollViewport<Self>, context: &mut visora_core::WidgetContext<'gui, Self>) {
+        let mut attributes = Attributes::new();
+        scroll_attributes(widget, &mut attributes);
+        context.mount_renderer(HtmlTag {
+            tag: Tag::Div,
+            attributes
+        });
+    }
+    // the element keeps its id, the page reports the offset of the element it scrolled
+    fn update<'gui>(old: &ScrollViewport<Self>, new: &ScrollViewport<Self>, context: &mut visora_core::WidgetContext<'gui, Self>) {
+        let Some(tag) = context.get_renderer() else { return Self::mount(new, context) };
+        if old.layout() != new.layout() || old.scrollbars() != new.scrollbars() {
+            tag.attributes.styles.clear();
+            scroll_attributes(new, &mut tag.attributes);
+        }
+    }
+}
+/// the browser scrolls the div itself, the page moves it to the offset of its data after every render
+fn scroll_attributes(widget: &ScrollViewport<HtmlRenderer>, attributes: &mut Attributes) {
+    let layout = widget.layout();
+    let overflow = |scrolls: bool| Cow::Borrowed(if scrolls { "auto" } else { "hidden" });
+    attributes.add_style("overflow-x", overflow(layout.horizontal));
+    attributes.add_style("overflow-y", overflow(layout.vertical));
+    attributes.add_style("max-width", Cow::Borrowed("100%"));
+    attributes.add_style("max-height", Cow::Borrowed("100%"));
+    attributes.add_style("box-sizing", Cow::Borrowed("border-box"));
+    if !widget.scrollbars() {
+        attributes.add_style("scrollbar-width", Cow::Borrowed("none"));
+    }
+    attributes.add_data("scroll-x", layout.offset.x.to_string());
+    attributes.add_data("scroll-y", layout.offset.y.to_string());
+}
+impl Render<Scrollbar> for HtmlRenderer {
+    // the browser draws the scrollbars of the div
+    fn mount<'gui>(_widget: &Scrollbar, context: &mut visora_core::WidgetContext<'gui, Self>) {
+        context.mount_renderer(HtmlTag {
+            tag: Tag::Div,
+            attributes: Attributes::new().with_style("display", Cow::Borrowed("none"))
+        });
+    }
+}
+impl Render<LazyItems<Self>> for HtmlRenderer {
+    // as long as all the items, so the browser scrolls as far as the list goes
+    fn mount<'gui>(widget: &LazyItems<Self>, context: &mut visora_core::WidgetContext<'gui, Self>) {
+        let mut attributes = Attributes::new();
+        lazy_items_styles(widget.layout(), &mut attributes);
+        context.mount_renderer(HtmlTag {
+            tag: Tag::Div,
+            attributes
+        });
+    }
+    fn update<'gui>(old: &LazyItems<Self>, new: &LazyItems<Self>, context: &mut visora_core::WidgetContext<'gui, Self>) {
+        let Some(tag) = context.get_renderer() else { return Self::mount(new, context) };
+        if old.layout() != new.layout() {
+            tag.attributes.styles.clear();
+            lazy_items_styles(new.layout(), &mut tag.attributes);
+        }
+    }
+}
+fn lazy_items_styles(layout: &Lazy, attributes: &mut Attributes) {
+    let (length, thickness) = match layout.axis {
+        Axis::Horizontal => ("width", "height"),
+        Axis::Vertical => ("height", "width"),
+    };
+    attributes.add_style("position", Cow::Borrowed("relative"));
+    attributes.add_style(length, Cow::Owned(format!("{}px", layout.length())));
+    attributes.add_style(thickness, Cow::Borrowed("100%"));
+}
+impl Render<LazySlot<Self>> for HtmlRenderer {
+    // a recycled slot keeps its element and moves to the item it shows
+    fn mount<'gui>(widget: &LazySlot<Self>, context: &mut visora_core::WidgetContext<'gui, Self>) {
+        let mut attributes = Attributes::new();
+        lazy_slot_styles(widget, &mut attributes);
+        context.mount_renderer(HtmlTag {
+            tag: Tag::Div,
+            attributes
+        });
+    }
+    fn update<'gui>(old: &LazySlot<Self>, new: &LazySlot<Self>, context: &mut visora_core::WidgetContext<'gui, Self>) {
+        let Some(tag) = context.get_renderer() else { return Self::mount(new, context) };
+        if old.layout() != new.layout() || old.index() != new.index() {
+            tag.attributes.styles.clear();
+            lazy_slot_styles(new, &mut tag.attributes);
+        }
+    }
+}
+/// the slot is put at its item like in the layout, the items of a list are all as long
+fn lazy_slot_styles(widget: &LazySlot<HtmlRenderer>, attributes: &mut Attributes) {
+    let layout = widget.layout();
+    let (start, length, across) = match layout.axis {
+        Axis::Horizontal => ("left", "width", ["top", "bottom"]),
+        Axis::Vertical => ("top", "height", ["left", "right"]),
+    };
+    let index = widget.index().unwrap_or_default();
+    attributes.add_style("position", Cow::Borrowed("absolute"));
+    attributes.add_style(start, Cow::Owned(format!("{}px", index as f32 * layout.extent)));
+    attributes.add_style(length, Cow::Owned(format!("{}px", layout.extent)));
+    for side in across {
+        attributes.add_style(side, Cow::Borrowed("0"));
+    }
+    attributes.add_style("overflow", Cow::Borrowed("hidden"));
+}
+
+#[cfg(test)]
+mod tests {
+    use visora::widget::{
+        button::TextButton,
+        center::Center,
+        container::Container,
+        flex::{Column, Expanded, Flexible, Row, Spacer},
+        grid::{Grid, GridItem},
+        lazy::LazyList,
+        list::Hlist,
+        scroll::{ScrollController, ScrollView},
+        text::Text,
+    };
+    use visora_core::{
+        event::Point,
+        layout::{flex::{CrossAxisAlignment, MainAxisAlignment, MainAxisSize}, grid::{CellAlignment, Track}, layout_root, LayoutRect, MonospaceMeasurer, Size},
+        state::{State, StateHandle},
+        treecs::{iterators::breadth::{BreadthInfo, BreadthIter}, query::Query, EntityKey},
+        widget::{RenderAble, Widget},
+        BuildContext, Gui, WidgetContext,
+    };
+
+    use crate::event::parse_event;
+
+    use super::{HtmlRenderer, HtmlTag};
+
+    fn style<'a>(tag: &'a HtmlTag, key: &str) -> Option<&'a str> {
+        tag.attributes.styles.get(key).map(|x| x.as_ref())
+    }
+
+    /// a centered button that shows how often it was clicked
+    #[derive(Clone)]
+    struct Clicks;
+    impl RenderAble<HtmlRenderer> for Clicks {
+        fn mount<'gui>(&self, context: WidgetContext<'gui, HtmlRenderer>) -> WidgetContext<'gui, HtmlRenderer> {
+            context.mount_widget(self)
+        }
+    }
+    impl Widget<HtmlRenderer> for Clicks {
+        type State = u32;
+        fn create_state(&self) -> Self::State {
+            0
+        }
+        fn build<'gui>(&self, state: State<Self>, _context: &mut BuildContext<'gui>) -> impl RenderAble<HtmlRenderer> + 'static {
+            let count = *state.read::<HtmlRenderer>();
+            Center::new(Hlist::new().add(TextButton::new(Text::new(&format!("clicked {count}")))))
+        }
+    }
+
+    #[test]
+    fn rebuilt_elements_keep_their_ids() {
+        let mut gui = Gui::new(HtmlRenderer::new());
+        gui.root_widget_context().mount_child(&Clicks);
+        gui.render();
+        let ids = |gui: &Gui<HtmlRenderer>| -> Vec<String> {
+            let query: Query<&HtmlTag, BreadthIter> = Query::new(gui.tree());
+            query.filter(|(info, _)| *info == BreadthInfo::Other).map(|(_, x)| x.attributes.id.clone()).collect()
+        };
+        let before = ids(&gui);
+
+        let clicks = gui.tree().linkdata(gui.tree().root()).unwrap().children()[0];
+        gui.dispatch(&StateHandle::<Clicks>::new(clicks).update::<_, HtmlRenderer>(|_, x| *x += 1));
+        gui.render();
+        assert!(gui.renderer().get_render().contains(">clicked 1<"));
+        assert_eq!(ids(&gui), before, "the center, the list, the button and the text keep their elements");
+    }
+    #[test]
+    fn the_page_scrolls_and_reports_the_offset() {
+        let controller = ScrollController::new();
+        let items = (0..20).fold(Column::new(), |column, i| column.add(Text::new(&format!("item {i}"))));
+        let mut gui = Gui::new(HtmlRenderer::new());
+        gui.root_widget_context().mount_child(&ScrollView::new(items).with_controller(controller.clone()));
+        gui.render();
+        let view = gui.tree().linkdata(controller.key().unwrap()).unwrap().children()[0];
+        let tag = gui.tree().get_component::<HtmlTag>(view).unwrap();
+        assert_eq!(style(tag, "overflow-y"), Some("auto"));
+        assert_eq!(style(tag, "overflow-x"), Some("hidden"));
+        assert!(gui.renderer().get_render().contains("data-scroll-y=\"0\""));
+
+        // the page already shows the offset it reports, so it isn't sent again
+        let id = gui.renderer().element_id(view).unwrap().to_owned();
+        let message = format!("scrolled|{id}|0|40.5|300|100");
+        let (target, event) = parse_event(&message).unwrap();
+        let target = gui.renderer().entity(target).unwrap();
+        let before = gui.renderer().get_render().to_owned();
+        gui.dispatch_event(target, &event);
+        gui.render();
+        assert_eq!(controller.offset(), Point::new(0.0, 40.5));
+        assert_eq!(gui.renderer().get_render(), before);
+
+        let context = gui.root_build_context();
+        controller.jump_to(&context, Point::new(0.0, 90.0));
+        gui.dispatch_pending();
+        gui.render();
+        assert!(gui.renderer().get_render().contains("data-scroll-y=\"90\""));
+        assert_eq!(gui.renderer().element_id(view), Some(id.as_str()), "the element keeps its id");
+    }
+    #[test]
+    fn lazy_lists_render_the_items_the_page_shows() {
+        let controller = ScrollController::new();
+        let list = LazyList::new(100_000, 25.0, |i| Text::new(&format!("item {i}"))).with_controller(controller.clone());
+        let mut gui = Gui::new(HtmlRenderer::new());
+        gui.root_widget_context().mount_child(&list);
+        gui.render();
+        // without a layout the viewport is unknown until the page reports it, the cache extent is rendered
+        let html = gui.renderer().get_render().to_owned();
+        assert!(html.contains("height:2500000px"));
+        assert!(html.contains(">item 9<") && !html.contains(">item 10<"));
+
+        let view = gui.tree().linkdata(controller.key().unwrap()).unwrap().children()[0];
+        let id = gui.renderer().element_id(view).unwrap().to_owned();
+        let message = format!("scrolled|{id}|0|5000|300|100");
+        let (target, event) = parse_event(&message).unwrap();
+        let target = gui.renderer().entity(target).unwrap();
+        gui.dispatch_event(target, &event);
+        gui.dispatch_pending();
+        gui.render();
+        let html = gui.renderer().get_render();
+        assert!(html.contains(">item 190<") && html.contains(">item 213<"));
+        assert!(!html.contains(">item 9<") && !html.contains(">item 214<"));
+        assert!(html.contains("top:5000px"));
+    }
+
+    /// lays `widget` out in a 300 by 200 viewport like vello does, returns the entity of its element
+    fn laid_out(widget: impl RenderAble<HtmlRenderer> + 'static) -> (Gui<HtmlRenderer>, EntityKey) {
+        let mut gui = Gui::new(HtmlRenderer::new());
+        gui.root_widget_context().mount_child(&widget);
+        gui.render();
+        let root = gui.tree().root();
+        layout_root(gui.tree(), root, Size::new(300.0, 200.0), &MonospaceMeasurer);
+        let container = gui.tree().linkdata(root).unwrap().children()[0];
+        (gui, container)
+    }
+    fn children(gui: &Gui<HtmlRenderer>, container: EntityKey) -> Vec<EntityKey> {
+        gui.tree().linkdata(container).unwrap().children().iter().copied().collect()
+    }
+    /// the rects of the children of `container` relative to it
+    fn child_rects(gui: &Gui<HtmlRenderer>, container: EntityKey) -> Vec<LayoutRect> {
+        let rect = |key| *gui.tree().get_component::<LayoutRect>(key).unwrap();
+        let origin = rect(container);
+        children(gui, container).into_iter()
+            .map(rect)
+            .map(|x| LayoutRect::new(x.x - origin.x, x.y - origin.y, x.width, x.height))
+            .collect()
+    }
+    /// the sorted declarations of the style attribute the page gets for the element of `entity`
+    fn written_style(gui: &mut Gui<HtmlRenderer>, entity: EntityKey) -> Vec<String> {
+        let renderer = gui.renderer();
+        let html = renderer.get_render();
+        let end = html.find(&format!("id=\"{}\"", renderer.element_id(entity).unwrap())).unwrap();
+        let tag = &html[html[..end].rfind('<').unwrap()..end];
+        let style = tag.split_once("style=\"").map_or("", |(_, x)| x.split('"').next().unwrap());
+        let mut declarations: Vec<String> = style.split(';').filter(|x| !x.is_empty()).map(str::to_owned).collect();
+        declarations.sort();
+        declarations
+    }
+    fn is_rigid(gui: &mut Gui<HtmlRenderer>, entity: EntityKey) -> bool {
+        written_style(gui, entity).iter().any(|x| x == "flex-shrink:0")
+    }
+
+    // the expected rects are the ones a browser gives the written styles, worked out from the css specifications.
+    // the children have fixed sizes, so they don't depend on the fonts of the browser
+    #[test]
+    fn rows_are_written_as_flex_boxes() {
+        let (mut gui, row) = laid_out(Row::new()
+            .with_main_alignment(MainAxisAlignment::SpaceBetween)
+            .with_cross_alignment(CrossAxisAlignment::Center)
+            .with_spacing(10.0)
+            .add(Container::new().with_width(30.0).with_height(20.0))
+            .add(Container::new().with_width(40.0).with_height(10.0))
+            .add(Container::new().with_width(50.0).with_height(30.0)));
+        assert_eq!(written_style(&mut gui, row), [
+            "align-items:center",
+            "display:flex",
+            "flex-direction:row",
+            "gap:10px",
+            "height:fit-content",
+            "justify-content:space-between",
+            "width:100%",
+        ]);
+        for child in children(&gui, row) {
+            assert!(is_rigid(&mut gui, child));
+        }
+        // 300 - 120 - 2 * 10 is left, 80 goes between two children
+        assert_eq!(child_rects(&gui, row), [
+            LayoutRect::new(0.0, 5.0, 30.0, 20.0),
+            LayoutRect::new(120.0, 10.0, 40.0, 10.0),
+            LayoutRect::new(250.0, 0.0, 50.0, 30.0),
+        ]);
+    }
+    #[test]
+    fn columns_are_written_as_flex_boxes() {
+        let (mut gui, column) = laid_out(Column::new()
+            .with_main_alignment(MainAxisAlignment::End)
+            .with_cross_alignment(CrossAxisAlignment::Stretch)
+            .with_main_size(MainAxisSize::Min)
+            .add(Container::new().with_height(30.0))
+            .add(Container::new().with_width(20.0).with_height(10.0)));
+        assert_eq!(written_style(&mut gui, column), [
+            "align-items:stretch",
+            "display:flex",
+            "flex-direction:column",
+            "height:fit-content",
+            "justify-content:flex-end",
+            "width:100%",
+        ]);
+        // the width of its own comes before the one of the column, the stretch comes last
+        let sized = children(&gui, column)[1];
+        let style = written_style(&mut gui, sized);
+        assert!(style.iter().any(|x| x == "flex-shrink:0") && style.iter().any(|x| x == "width:auto"));
+        let html = gui.renderer().get_render();
+        let element = &html[html.find("width:20px").unwrap()..];
+        assert!(element.find("width:auto").unwrap() < element.find("id=").unwrap());
+        assert_eq!(child_rects(&gui, column), [
+            LayoutRect::new(0.0, 0.0, 300.0, 30.0),
+            LayoutRect::new(0.0, 30.0, 300.0, 10.0),
+        ]);
+    }
+    #[test]
+    fn flexible_children_share_the_free_space() {
+        let (mut gui, row) = laid_out(Row::new()
+            .with_spacing(10.0)
+            .add(Expanded::new(Container::new().with_height(20.0)).with_flex(2))
+            .add(Container::new().with_width(40.0).with_height(10.0))
+            .add(Spacer::new())
+            .add(Flexible::new(Container::new().with_width(10.0).with_height(5.0))));
+        let children = children(&gui, row);
+        // the children that flex grow from nothing, the others keep their size
+        let flexing = ["flex-basis:0px", "flex-grow:2", "flex-shrink:0", "min-height:0", "min-width:0"];
+        assert_eq!(written_style(&mut gui, children[0]), flexing);
+        assert!(is_rigid(&mut gui, children[1]));
+        assert_eq!(written_style(&mut gui, children[2]), flexing.map(|x| if x == "flex-grow:2" { "flex-grow:1" } else { x }));
+        // 300 - 40 - 3 * 10 is shared by 4 flex units of 57.5
+        assert_eq!(child_rects(&gui, row), [
+            LayoutRect::new(0.0, 0.0, 115.0, 20.0),
+            LayoutRect::new(125.0, 0.0, 40.0, 10.0),
+            LayoutRect::new(175.0, 0.0, 57.5, 0.0),
+            LayoutRect::new(242.5, 0.0, 57.5, 5.0),
+        ]);
+    }
+    #[test]
+    fn children_that_dont_fit_overflow() {
+        let (mut gui, row) = laid_out(Row::new()
+            .add(Container::new().with_width(200.0).with_height(10.0))
+            .add(Container::new().with_width(150.0).with_height(10.0))
+            .add(Expanded::new(Container::new())));
+        let children = children(&gui, row);
+        // a browser shrinks the children of a flex box by default
+        assert!(is_rigid(&mut gui, children[0]));
+        assert!(is_rigid(&mut gui, children[1]));
+        assert_eq!(written_style(&mut gui, children[2]).iter().filter(|x| x.starts_with("flex-shrink")).count(), 1);
+        assert_eq!(child_rects(&gui, row), [
+            LayoutRect::new(0.0, 0.0, 200.0, 10.0),
+            LayoutRect::new(200.0, 0.0, 150.0, 10.0),
+            LayoutRect::new(350.0, 0.0, 0.0, 0.0),
+        ]);
+    }
+
+    #[test]
+    fn grids_are_written_as_css_grids() {
+        let (mut gui, grid) = laid_out(Grid::new(vec![Track::Fixed(60.0), Track::Auto, Track::Fraction(1.0), Track::Fraction(2.0)])
+            .with_rows(vec![Track::Auto, Track::Fraction(1.0), Track::Fixed(20.0)])
+            .with_gap(10.0, 5.0)
+            .add(Container::new().with_width(30.0).with_height(15.0))
+            .add(Container::new().with_width(45.0).with_height(25.0))
+            .add(Container::new().with_width(10.0).with_height(10.0))
+            .add_item(GridItem::new(Container::new().with_width(20.0).with_height(40.0)).with_span(2, 1))
+            .add_item(GridItem::new(Container::new().with_width(70.0).with_height(10.0))
+                .at(2, 0)
+                .with_span(1, 2)
+                .with_alignment(CellAlignment::End, CellAlignment::Center))
+            .add(Container::new().with_width(5.0).with_height(5.0)));
+        assert_eq!(written_style(&mut gui, grid), [
+            "align-content:start",
+            "align-items:stretch",
+            "column-gap:10px",
+            "display:grid",
+            "grid-template-columns:60px auto minmax(0, 1fr) minmax(0, 2fr)",
+            "grid-template-rows:auto minmax(0, 1fr) 20px",
+            "height:100%",
+            "justify-content:start",
+            "justify-items:stretch",
+            "row-gap:5px",
+            "width:100%",
+        ]);
+        let cell = |column: &str, row: &str| {
+            let mut style = vec![column.to_owned(), row.to_owned(), "min-height:0".to_owned(), "min-width:0".to_owned()];
+            style.sort();
+            style
+        };
+        let items = children(&gui, grid);
+        assert_eq!(written_style(&mut gui, items[0]), cell("grid-column:1 / span 1", "grid-row:1 / span 1"));
+        assert_eq!(written_style(&mut gui, items[1]), cell("grid-column:2 / span 1", "grid-row:1 / span 1"));
+        assert_eq!(written_style(&mut gui, items[2]), cell("grid-column:3 / span 1", "grid-row:1 / span 1"));
+        assert_eq!(written_style(&mut gui, items[3]), cell("grid-column:4 / span 1", "grid-row:1 / span 2"));
+        let mut aligned = cell("grid-column:1 / span 2", "grid-row:3 / span 1");
+        aligned.extend(["align-self:center".to_owned(), "justify-self:end".to_owned()]);
+        aligned.sort();
+        assert_eq!(written_style(&mut gui, items[4]), aligned);
+        assert_eq!(written_style(&mut gui, items[5]), cell("grid-column:1 / span 1", "grid-row:2 / span 1"));
+
+        // the columns are 60 45 55 110 wide: the auto one fits the 45 of its child, 300 - 60 - 45 - 3 * 10 is left
+        // for 3 fractions. the rows are 25 145 20 high, the child spanning a fraction row doesn't size the auto one
+        assert_eq!(child_rects(&gui, grid), [
+            LayoutRect::new(0.0, 0.0, 60.0, 25.0),
+            LayoutRect::new(70.0, 0.0, 45.0, 25.0),
+            LayoutRect::new(125.0, 0.0, 55.0, 25.0),
+            LayoutRect::new(190.0, 0.0, 110.0, 175.0),
+            LayoutRect::new(45.0, 185.0, 70.0, 10.0),
+            LayoutRect::new(0.0, 30.0, 60.0, 145.0),
+        ]);
+        assert_eq!(gui.tree().get_component::<LayoutRect>(grid).unwrap().size(), Size::new(300.0, 200.0));
+    }
+    #[test]
+    fn grids_of_auto_tracks_fit_their_children() {
+        let (mut gui, grid) = laid_out(Grid::new(vec![Track::Auto, Track::Fixed(20.0), Track::Auto])
+            .with_gap(4.0, 6.0)
+            .with_alignment(CellAlignment::Start, CellAlignment::Center)
+            .add(Container::new().with_width(10.0).with_height(40.0))
+            .add(Container::new().with_width(15.0).with_height(8.0))
+            .add(Container::new().with_width(12.0).with_height(12.0))
+            .add_item(GridItem::new(Container::new().with_width(100.0).with_height(10.0)).with_span(1, 3))
+            .add(Container::new().with_width(6.0).with_height(6.0)));
+        assert_eq!(written_style(&mut gui, grid), [
+            "align-content:start",
+            "align-items:center",
+            "column-gap:4px",
+            "display:grid",
+            "grid-template-columns:auto 20px auto",
+            "height:fit-content",
+            "justify-content:start",
+            "justify-items:start",
+            "row-gap:6px",
+            "width:fit-content",
+        ]);
+        // the child spanning the 3 columns is 100 - 10 - 20 - 12 - 2 * 4 wider than them, the two auto columns
+        // grow by half of that each to 35 and 37. the implicit rows are 40 10 6 high
+        assert_eq!(child_rects(&gui, grid), [
+            LayoutRect::new(0.0, 0.0, 10.0, 40.0),
+            LayoutRect::new(39.0, 16.0, 15.0, 8.0),
+            LayoutRect::new(63.0, 14.0, 12.0, 12.0),
+            LayoutRect::new(0.0, 46.0, 100.0, 10.0),
+            LayoutRect::new(0.0, 62.0, 6.0, 6.0),
+        ]);
+        assert_eq!(gui.tree().get_component::<LayoutRect>(grid).unwrap().size(), Size::new(100.0, 68.0));
+    }
 }
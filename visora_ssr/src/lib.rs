@@ -2,7 +2,7 @@ use std::{fs::{self, File}, io::Write};
 
 use html::HtmlRenderer;
 
-use visora::widget::{button::TextButton, center::Center, text::{RichText, Text}, list::Hlist};
+use visora::widget::{button::TextButton, center::Center, container::Container, flex::{Column, Expanded, Flexible, Row, Spacer}, grid::{Grid, GridItem}, opacity::Opacity, lazy::{LazyItems, LazySlot}, scroll::{ScrollViewport, Scrollbar}, text::{RichText, Text}, list::Hlist};
 use visora_core::{treecs::{iterators::breadth::BreadthInfo, query::Query}, widget::Render, Component, Gui};
 
 
@@ -11,7 +11,20 @@ visora::internal_impl!{
     TextButton,
     Hlist<Self>,
     RichText, 
-    Center<Self>;
+    Center<Self>,
+    Container<Self>,
+    Opacity<Self>,
+    Row<Self>,
+    Column<Self>,
+    Expanded<Self>,
+    Flexible<Self>,
+    Spacer,
+    ScrollViewport<Self>,
+    Scrollbar,
+    LazyItems<Self>,
+    LazySlot<Self>,
+    Grid<Self>,
+    GridItem<Self>;
     ( $($bounds:tt)* ) => (
     pub trait SupportedWidgets: $($bounds)* + visora_core::renderer::Renderer {}
     impl<T: $($bounds)* + visora_core::renderer::Renderer> SupportedWidgets for T{}
@@ -19,3 +32,6 @@ visora::internal_impl!{
 
 
 pub mod html;
+pub mod executor;
+pub mod event;
+pub mod routing;
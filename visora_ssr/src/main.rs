@@ -1,13 +1,27 @@
 
-use std::{fs::File, io::Read, time::Duration};
+use std::{collections::HashMap, fs::File, io::Read, sync::mpsc, time::Duration};
 
 use futures_util::{SinkExt, StreamExt, TryStreamExt};
-use tokio::{io::AsyncWriteExt, net::TcpListener, time::sleep};
-use tokio_tungstenite::{accept_async, tungstenite::accept};
-use visora::widget::{button::TextButton, list::Hlist, text::Text};
-use visora_core::{Gui, WidgetContext, renderer::Renderer, state::State, widget::{RenderAble, Widget}};
+use tokio::{io::{AsyncReadExt, AsyncWriteExt}, net::{TcpListener, TcpStream}, sync::mpsc::UnboundedSender};
+use tracing::Instrument;
+use tracing_subscriber::EnvFilter;
+use tokio_tungstenite::{accept_hdr_async, tungstenite::{accept, handshake::server::{ErrorResponse, Request, Response}}};
+use visora::widget::{
+    boundary::ErrorBoundary,
+    button::TextButton,
+    list::Hlist,
+    navigator::{Navigation, NavigationKind, Navigator, NavigatorState},
+    text::Text,
+};
+use visora_core::{Gui, WidgetContext, animation::clock::ManualClock, devtools::Devtools, treecs::EntityKey, event::Event, record::{Recorder, Session}, renderer::Renderer, state::{State, StateTransaction}, widget::{RenderAble, Widget}};
+use visora_devtools::DevtoolsServer;
 use visora_macros::RenderAble;
-use visora_ssr::html::HtmlRenderer;
+use visora_ssr::{
+    event::parse_event,
+    executor::TokioExecutor,
+    html::HtmlRenderer,
+    routing::{history_message, parse_popstate, request_path},
+};
 
 
 /*#[derive(RenderAble)]
@@ -54,28 +68,11 @@ where
 }*/
 
 
-//#[derive(RenderAble)]
+#[derive(Clone, RenderAble)]
 struct Counter {
     start: u64,
     end: u64
 }
-impl<R> RenderAble<R> for Counter
-where
-    R: Renderer,
-    Self: Widget<R>,
-{
-
-    fn mount<'gui>(
-        &self,
-        mut context: WidgetContext<'gui, R>,
-    ) -> WidgetContext<'gui, R> {
-        context.insert_component(self.create_state());
-        let mut build_context = context.get_buildcontext();
-        let state = State::new(context.get_buildcontext());
-        let build = <Self as Widget<R>>::build(&self, state, &mut build_context);
-        build.mount(context)
-    }
-}
 
 impl<R: visora_ssr::SupportedWidgets> Widget<R> for Counter {
     type State = u64;
@@ -96,6 +93,225 @@ impl<R: visora_ssr::SupportedWidgets> Widget<R> for Counter {
             )
     }
 }
+/// the home route, the counter and a link to the first user
+#[derive(Clone, RenderAble)]
+struct Home;
+
+impl<R: visora_ssr::SupportedWidgets> Widget<R> for Home {
+    type State = ();
+    fn create_state(&self) -> Self::State {}
+
+    fn build<'gui>(&self, _state: State<Self>, context: &mut visora_core::BuildContext<'gui>) -> impl RenderAble<R> + 'static {
+        let navigator = Navigator::<R>::of(context).expect("pages are mounted by a navigator");
+        Hlist::new()
+            // the increment of the counter panics, the boundary shows the error instead of ending the connection
+            .add(ErrorBoundary::new(Counter{start: 1, end: 5}))
+            .add(
+                TextButton::new(Text::new("user 1"))
+                .on_click(StateTransaction::new(context.key(), move |context| navigator.push_named(context, "/users/1")))
+            )
+    }
+}
+
+/// the route `/users/:id`
+#[derive(Clone, RenderAble)]
+struct UserPage {
+    id: u64
+}
+
+impl<R: visora_ssr::SupportedWidgets> Widget<R> for UserPage {
+    type State = ();
+    fn create_state(&self) -> Self::State {}
+
+    fn build<'gui>(&self, _state: State<Self>, context: &mut visora_core::BuildContext<'gui>) -> impl RenderAble<R> + 'static {
+        let navigator = Navigator::<R>::of(context).expect("pages are mounted by a navigator");
+        let next = format!("/users/{}", self.id + 1);
+        Hlist::new()
+            .add(Text::new(&format!("user {}", self.id)))
+            .add(
+                TextButton::new(Text::new("next"))
+                .on_click(StateTransaction::new(context.key(), move |context| navigator.push_named(context, &next)))
+            )
+            .add(
+                TextButton::new(Text::new("back"))
+                .on_click(StateTransaction::new(context.key(), move |context| navigator.pop(context)))
+            )
+    }
+}
+
+/// the routes of the demo, starting at the url the browser asked for
+fn app(path: &str, history: Option<mpsc::Sender<Navigation>>) -> Navigator<HtmlRenderer> {
+    let navigator = Navigator::new(Home)
+        .with_route("/users/:id", |params| UserPage { id: params.get("id").and_then(|x| x.parse().ok()).unwrap_or_default() })
+        .with_unknown_route(|params| Text::new(&format!("nothing at {}", params.path())))
+        .with_initial_path(path);
+    match history {
+        Some(history) => navigator.with_on_change(move |navigation| {
+            let _ = history.send(navigation.clone());
+        }),
+        None => navigator,
+    }
+}
+
+/// what the connections of the browser tabs send to the gui
+enum Incoming {
+    Connected { id: u64, path: String, outgoing: UnboundedSender<String> },
+    Message { id: u64, text: String },
+    Closed { id: u64 },
+}
+
+/// forwards the messages of one websocket to the gui and the messages of the gui to the page
+async fn connection(id: u64, stream: TcpStream, incoming: UnboundedSender<Incoming>) {
+    // the page connects with the path it was loaded from
+    let mut path = String::from("/");
+    let ws = accept_hdr_async(stream, |request: &Request, response: Response| -> Result<Response, ErrorResponse> {
+        path = request.uri().path_and_query().map_or("/", |x| x.as_str()).to_owned();
+        Ok(response)
+    }).await;
+    let Ok(ws) = ws else { return };
+    let (mut sink, mut stream) = ws.split();
+    let (outgoing, mut pending) = tokio::sync::mpsc::unbounded_channel();
+    let _ = incoming.send(Incoming::Connected { id, path, outgoing });
+    loop {
+        tokio::select! {
+            message = stream.next() => {
+                let Some(Ok(message)) = message else { break };
+                if let Ok(text) = message.to_text() {
+                    let _ = incoming.send(Incoming::Message { id, text: text.to_owned() });
+                }
+            }
+            message = pending.recv() => {
+                let Some(message) = message else { break };
+                let span = tracing::info_span!("send", tab = id, bytes = message.len());
+                if sink.send(message.into()).instrument(span).await.is_err() {
+                    break;
+                }
+            }
+        }
+    }
+    let _ = incoming.send(Incoming::Closed { id });
+}
+
+/// a connected browser tab, the root it is rendered from and what the page shows
+struct Tab {
+    root: EntityKey,
+    outgoing: UnboundedSender<String>,
+    navigations: mpsc::Receiver<Navigation>,
+    last_render: String,
+    last_focus: Option<String>,
+}
+impl Tab {
+    fn open(gui: &mut Gui<HtmlRenderer>, path: &str, outgoing: UnboundedSender<String>) -> Self {
+        let (history, navigations) = mpsc::channel();
+        let root = gui.add_root(HtmlRenderer::new());
+        app(path, Some(history.clone())).mount(gui.root_context(root).unwrap());
+        // the entry the page was loaded with gets the depth of the initial stack
+        if let Some(navigator) = Navigator::<HtmlRenderer>::find(gui.tree(), root) {
+            let state = gui.tree().get_component::<NavigatorState<HtmlRenderer>>(navigator.key()).unwrap();
+            let _ = history.send(Navigation {
+                kind: NavigationKind::Replace,
+                depth: state.depth(),
+                path: state.path().map(str::to_owned),
+            });
+        }
+        Self {
+            root,
+            outgoing,
+            navigations,
+            last_render: String::new(),
+            last_focus: None,
+        }
+    }
+    /// sends what changed since the last sync, the render has to be up to date
+    fn sync(&mut self, gui: &mut Gui<HtmlRenderer>) {
+        let focused = gui.focused().filter(|x| gui.tree().root_of(*x) == Some(self.root));
+        let Some(renderer) = gui.root_renderer(self.root) else { return };
+        let rendered = renderer.get_render();
+        if rendered != self.last_render {
+            self.last_render = rendered.to_owned();
+            self.last_focus = None;
+            let _ = self.outgoing.send(format!("replace|root|{}", rendered));
+        }
+        // the page moves its focus to the element of the focused entity
+        let focus = focused.and_then(|x| renderer.element_id(x)).map(str::to_owned);
+        if let Some(id) = focus.filter(|x| Some(x) != self.last_focus.as_ref()) {
+            self.last_focus = Some(id.clone());
+            let _ = self.outgoing.send(format!("focus|{id}"));
+        }
+        // the url of the page follows the navigator
+        for message in self.navigations.try_iter().filter_map(|x| history_message(&x)) {
+            let _ = self.outgoing.send(message);
+        }
+    }
+    fn handle_message(&self, gui: &mut Gui<HtmlRenderer>, message: &str) {
+        // the back and forward buttons of the browser move the navigator
+        if let Some((depth, path)) = parse_popstate(message) {
+            if let Some(navigator) = Navigator::<HtmlRenderer>::find(gui.tree(), self.root) {
+                let context = gui.build_context(navigator.key()).unwrap();
+                navigator.restore(&context, depth, path);
+            }
+            gui.dispatch_pending();
+            return;
+        }
+        let Some((id, event)) = parse_event(message) else { return };
+        match event {
+            // keyboard input goes to the focused entity, the gui does the tab traversal
+            Event::Key { .. } | Event::TextInput(_) => {
+                gui.handle_root_event(self.root, event);
+            }
+            // the browser already did the hit testing, the id is the element under the pointer
+            _ => if let Some(target) = gui.root_renderer(self.root).and_then(|x| x.entity(id)) {
+                gui.dispatch_event(target, &event);
+            }
+        }
+    }
+}
+
+/// replays a session recorded with `--record` on a headless gui and prints the page of every tab that stayed open
+///
+/// the recording marks `open|tab|path`, `close|tab` and the `message|tab|popstate..` of the back and forward buttons,
+/// the gui records the input and the timers itself
+fn replay(path: &str) {
+    let session = Session::load(path).unwrap_or_else(|error| panic!("could not load the recording {path}: {error}"));
+    let clock = ManualClock::new();
+    let mut gui = Gui::new(HtmlRenderer::new()).with_clock(clock.clone());
+    let mut tabs: HashMap<u64, Tab> = HashMap::new();
+    gui.replay(&session, &clock, |gui, mark| {
+        let mut parts = mark.splitn(3, '|');
+        let (kind, id, rest) = (parts.next(), parts.next().and_then(|x| x.parse::<u64>().ok()), parts.next());
+        match (kind, id, rest) {
+            (Some("open"), Some(id), Some(path)) => {
+                // nobody listens, the pages are printed at the end
+                let (outgoing, _) = tokio::sync::mpsc::unbounded_channel();
+                tabs.insert(id, Tab::open(gui, path, outgoing));
+            }
+            (Some("close"), Some(id), _) => if let Some(tab) = tabs.remove(&id) {
+                gui.remove_root(tab.root);
+            }
+            (Some("message"), Some(id), Some(text)) => if let Some(tab) = tabs.get(&id) {
+                tab.handle_message(gui, text);
+            }
+            _ => tracing::warn!(mark, "skipping an unknown mark"),
+        }
+    });
+    gui.render();
+    let mut ids: Vec<_> = tabs.keys().copied().collect();
+    ids.sort();
+    for id in ids {
+        let page = gui.root_renderer(tabs[&id].root).unwrap().get_render();
+        println!("tab {id}: {page}");
+    }
+}
+
+/// the page of `path` rendered on the server, the browser shows it before the websocket connects
+fn render_page(path: &str) -> String {
+    let _span = tracing::info_span!("render_page", path).entered();
+    let mut gui = Gui::new(HtmlRenderer::new());
+    app(path, None).mount(gui.root_widget_context());
+    gui.render();
+    gui.renderer().get_render().to_owned()
+}
+
 // question:
 // what to do when a method has an on_click? 
 // Should State be owned and cheaply clonable?
@@ -104,6 +320,29 @@ impl<R: visora_ssr::SupportedWidgets> Widget<R> for Counter {
 #[tokio::main]
 async fn main() {
     use visora_core::widget::{Widget};
+    // `--record file` writes the session of every tab to the file, `--replay file` repeats it without a browser.
+    // `--devtools address` serves the inspector at the address
+    // `RUST_LOG=info` shows the frames and the messages sent to the pages
+    tracing_subscriber::fmt().with_env_filter(EnvFilter::from_default_env()).init();
+    let mut args = std::env::args().skip(1);
+    let mut recorder = None;
+    let mut devtools = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--replay" => return replay(&args.next().expect("--replay takes the file of a recording")),
+            "--record" => {
+                let path = args.next().expect("--record takes the file to record to");
+                recorder = Some(Recorder::create(&path).unwrap_or_else(|error| panic!("could not create {path}: {error}")));
+            }
+            "--devtools" => {
+                let address = args.next().expect("--devtools takes the address to serve the inspector at");
+                let server = DevtoolsServer::start(&address).unwrap_or_else(|error| panic!("could not serve the devtools at {address}: {error}"));
+                tracing::info!(address = %server.address(), "serving the devtools");
+                devtools = Some(server);
+            }
+            x => tracing::warn!(argument = x, "ignoring an unknown argument"),
+        }
+    }
     let wsserver = TcpListener::bind("0.0.0.0:8081").await.unwrap();
     tokio::spawn(async {
         let server = TcpListener::bind("0.0.0.0:8080").await.unwrap();
@@ -113,6 +352,10 @@ async fn main() {
 
         loop {
             let (mut conn, _) = server.accept().await.unwrap();
+            let mut request = [0; 4096];
+            let read = conn.read(&mut request).await.unwrap_or_default();
+            let request = String::from_utf8_lossy(&request[..read]);
+            let page = content.replace("to_replace", &render_page(request_path(&request).unwrap_or("/")));
 
             let response = format!(
                 "HTTP/1.1 200 OK\r\n\
@@ -121,33 +364,76 @@ async fn main() {
                 Connection: close\r\n\
                 \r\n\
                 {}",
-                content.len(),
-                content
+                page.len(),
+                page
             );
             conn.write_all(response.as_bytes()).await.unwrap();
         }
     });
-    let mut gui = Gui::new(HtmlRenderer::new());
-    let widget = Counter{start: 1, end: 5};
-    let context = gui.root_widget_context();
-    widget.mount(context);
-    gui.render();
-    let rendered = gui.renderer().get_render();
+    let (incoming, mut messages) = tokio::sync::mpsc::unbounded_channel();
+    tokio::spawn(async move {
+        for id in 0.. {
+            let (x, _) = wsserver.accept().await.unwrap();
+            tracing::info!(tab = id, "websocket connected");
+            tokio::spawn(connection(id, x, incoming.clone()));
+        }
+    });
+    let executor = TokioExecutor::new();
+    // the main root stays empty, every tab mounts the app in its own root
+    let mut gui = Gui::new(HtmlRenderer::new()).with_executor(executor.clone());
+    if let Some(recorder) = recorder {
+        gui = gui.with_recorder(recorder);
+    }
+    if devtools.is_some() {
+        gui = gui.with_devtools(Devtools::new().with_debuggable::<NavigatorState<HtmlRenderer>>().with_debuggable::<u64>());
+    }
+    let mut tabs: HashMap<u64, Tab> = HashMap::new();
+    let mut frames = tokio::time::interval(Duration::from_millis(16));
+    // the inspector only reaches the gui through the loop, it is woken up to pick up selections
+    let mut inspections = tokio::time::interval(Duration::from_millis(100));
     loop {
-        let rendered = rendered.to_owned();
-        let (x, _) = wsserver.accept().await.unwrap();
-        println!("got ws connection");
-        tokio::spawn(async move {
-            let mut ws = accept_async(x).await.unwrap();
-            let (mut sender, mut receiver) = ws.split();
-
-            loop {
-                sender.send(format!("replace|root|{}", rendered).into()).await.unwrap();
-                let x = receiver.next().await;
-                println!("{x:?}");
-                sleep(Duration::from_secs(1)).await;
+        gui.render();
+        if devtools.as_ref().is_some_and(|x| x.sync(&mut gui)) {
+            gui.render();
+        }
+        // sending the pages is the present phase of the frame
+        gui.present(|gui| {
+            for tab in tabs.values_mut() {
+                tab.sync(gui);
             }
         });
+        let next_timer = gui.until_next_timer();
+        tokio::select! {
+            message = messages.recv() => match message.unwrap() {
+                Incoming::Connected { id, path, outgoing } => {
+                    gui.record_mark(&format!("open|{id}|{path}"));
+                    tabs.insert(id, Tab::open(&mut gui, &path, outgoing));
+                }
+                Incoming::Message { id, text } => if let Some(tab) = tabs.get(&id) {
+                    // the events are recorded by the gui, the navigator is moved by a transaction
+                    if parse_popstate(&text).is_some() {
+                        gui.record_mark(&format!("message|{id}|{text}"));
+                    }
+                    tab.handle_message(&mut gui, &text);
+                }
+                Incoming::Closed { id } => if let Some(tab) = tabs.remove(&id) {
+                    gui.record_mark(&format!("close|{id}"));
+                    gui.remove_root(tab.root);
+                }
+            },
+            _ = inspections.tick(), if devtools.is_some() => {}
+            _ = executor.task_finished() => {
+                gui.dispatch_pending();
+            }
+            // the page is only sent again when an animation changed the render
+            _ = frames.tick(), if gui.is_animating() => {
+                gui.tick();
+            }
+            // sleeps until the next timer, the deadline is computed again after every message
+            _ = tokio::time::sleep(next_timer.unwrap_or_default()), if next_timer.is_some() => {
+                gui.fire_timers();
+            }
+        }
     }
     /*
 
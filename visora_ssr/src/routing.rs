@@ -0,0 +1,72 @@
+use visora::widget::navigator::{Navigation, NavigationKind};
+
+/// the path of an http request, `GET /users/42 HTTP/1.1` has the path `/users/42`
+pub fn request_path(request: &str) -> Option<&str> {
+    let mut parts = request.lines().next()?.split(' ');
+    let _method = parts.next()?;
+    parts.next().filter(|x| x.starts_with('/'))
+}
+
+/// the message that moves the history of the browser along with the navigator
+///
+/// messages have the form `history|push or replace or pop|depth|path`, the path is empty for unnamed routes.
+/// changes made by [`visora::widget::navigator::NavigatorHandle::restore`] came from the browser and send nothing
+pub fn history_message(navigation: &Navigation) -> Option<String> {
+    let kind = match navigation.kind {
+        NavigationKind::Push => "push",
+        NavigationKind::Replace => "replace",
+        NavigationKind::Pop => "pop",
+        NavigationKind::Restore => return None,
+    };
+    Some(format!("history|{kind}|{}|{}", navigation.depth, navigation.path.as_deref().unwrap_or_default()))
+}
+
+/// the depth and the path of a `popstate||depth|path` message, sent when the back or forward button is used
+pub fn parse_popstate(message: &str) -> Option<(usize, &str)> {
+    let mut parts = message.strip_prefix("popstate||")?.splitn(2, '|');
+    let depth = parts.next()?.parse().ok()?;
+    Some((depth, parts.next()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use visora::widget::navigator::{Navigation, NavigationKind};
+
+    use super::{history_message, parse_popstate, request_path};
+
+    #[test]
+    fn paths_go_around_with_their_query() {
+        let path = request_path("GET /search?q=a|b&page=2 HTTP/1.1\r\nHost: 127.0.0.1\r\n\r\n").unwrap();
+        assert_eq!(path, "/search?q=a|b&page=2");
+        let navigation = Navigation { kind: NavigationKind::Push, depth: 2, path: Some(path.to_owned()) };
+        let message = history_message(&navigation).unwrap();
+        assert_eq!(message, "history|push|2|/search?q=a|b&page=2");
+        // the page sends the path of the entry it went back to the same way
+        let popstate = ["popstate", "", "2", path].join("|");
+        assert_eq!(parse_popstate(&popstate), Some((2, path)));
+    }
+    #[test]
+    fn history_messages_follow_the_navigator() {
+        let message = |kind, path: Option<&str>| history_message(&Navigation { kind, depth: 1, path: path.map(str::to_owned) });
+        assert_eq!(message(NavigationKind::Replace, Some("/")).as_deref(), Some("history|replace|1|/"));
+        assert_eq!(message(NavigationKind::Pop, None).as_deref(), Some("history|pop|1|"));
+        assert_eq!(message(NavigationKind::Restore, Some("/")), None);
+    }
+    #[test]
+    fn malformed_popstates_are_ignored() {
+        assert_eq!(parse_popstate("popstate||1|"), Some((1, "")));
+        assert_eq!(parse_popstate("popstate|||/users"), None);
+        assert_eq!(parse_popstate("popstate||one|/users"), None);
+        assert_eq!(parse_popstate("popstate||-1|/users"), None);
+        assert_eq!(parse_popstate("popstate||1"), None);
+        assert_eq!(parse_popstate("click|id|1|/users"), None);
+    }
+    #[test]
+    fn requests_without_a_path_have_none() {
+        assert_eq!(request_path("GET / HTTP/1.1"), Some("/"));
+        assert_eq!(request_path("GET"), None);
+        assert_eq!(request_path("GET  HTTP/1.1"), None);
+        assert_eq!(request_path("CONNECT 127.0.0.1:8080 HTTP/1.1"), None);
+        assert_eq!(request_path(""), None);
+    }
+}
@@ -0,0 +1,544 @@
+//! a headless harness to test widgets without the vello window or the ssr page
+//!
+//! ```ignore
+//! let mut tester = WidgetTester::new(Counter::default());
+//! tester.tap(&Finder::by_text("increment"));
+//! assert_eq!(tester.texts(), ["1", "increment"]);
+//! ```
+
+pub mod recorder;
+pub mod tester;
+
+pub use recorder::{Recorded, RecordingRenderer};
+pub use tester::{Finder, WidgetTester, FRAME};
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use std::sync::{atomic::{AtomicUsize, Ordering}, Arc, Mutex};
+
+    use visora::widget::{
+        animated::AnimatedOpacity,
+        boundary::ErrorBoundary,
+        button::TextButton,
+        center::Center,
+        container::{Container, EdgeInsets},
+        flex::Column,
+        image::Image,
+        lazy::{LazyList, LazySlot},
+        list::Hlist,
+        navigator::{match_path, Navigation, NavigationKind, Navigator, NavigatorHandle, Route},
+        provider::Provider,
+        reactive::Reactive,
+        scroll::{ScrollController, ScrollView, ScrollViewport, Scrollbar},
+        text::Text,
+    };
+    use visora_core::{
+        event::{Event, Key, Point},
+        layout::{Axis, LayoutRect, Size},
+        state::{State, StateHandle, StateTransaction},
+        widget::{RenderAble, Widget},
+        error::WidgetError,
+        BuildContext, WidgetContext,
+    };
+
+    use crate::{Finder, Recorded, RecordingRenderer, WidgetTester};
+
+    /// counts the taps on its button, hides the count after a second
+    #[derive(Clone)]
+    struct Counter;
+    impl RenderAble<RecordingRenderer> for Counter {
+        fn mount<'gui>(&self, context: WidgetContext<'gui, RecordingRenderer>) -> WidgetContext<'gui, RecordingRenderer> {
+            context.mount_widget(self)
+        }
+    }
+    impl Widget<RecordingRenderer> for Counter {
+        type State = (u32, bool);
+        fn create_state(&self) -> Self::State {
+            (0, true)
+        }
+        fn build<'gui>(&self, state: State<Self>, _context: &mut BuildContext<'gui>) -> impl RenderAble<RecordingRenderer> + 'static {
+            let (count, visible) = *state.read::<RecordingRenderer>();
+            let list = Hlist::new().add(
+                TextButton::new(Text::new("increment"))
+                    .on_click(state.update::<_, RecordingRenderer>(|_, state| state.0 += 1)),
+            );
+            match visible {
+                true => list.add(Text::new(&count.to_string())),
+                false => list,
+            }
+        }
+        fn init_state<'gui>(&self, context: &mut BuildContext<'gui>) {
+            let hide = StateHandle::<Self>::new(context.key()).update::<_, RecordingRenderer>(|_, state| state.1 = false);
+            context.set_timeout(Duration::from_secs(1), hide);
+        }
+    }
+
+    #[test]
+    fn taps_and_keys_update_the_state() {
+        let mut tester = WidgetTester::new(Counter);
+        assert_eq!(tester.texts(), ["increment", "0"]);
+
+        tester.tap(&Finder::by_text("increment"));
+        assert_eq!(tester.texts(), ["increment", "1"]);
+        assert_eq!(tester.state::<Counter>(&Finder::by_type::<Counter>()).0, 1);
+
+        let button = tester.find_one(&Finder::by_type::<TextButton>());
+        assert_eq!(tester.focused(), Some(button), "tapping focuses the button");
+        tester.press_key(Key::Enter);
+        assert!(tester.exists(&Finder::by_text("2")));
+    }
+    #[test]
+    fn timers_fire_when_pumped() {
+        let mut tester = WidgetTester::new(Counter);
+        tester.pump_for(Duration::from_millis(999));
+        assert!(tester.exists(&Finder::by_text("0")));
+        tester.pump_for(Duration::from_millis(1));
+        assert_eq!(tester.texts(), ["increment"]);
+    }
+    #[test]
+    fn settles_without_animations() {
+        let child = Text::new("faded");
+        let mut tester = WidgetTester::new(AnimatedOpacity::new(1.0, Duration::from_millis(100), child));
+        assert_eq!(tester.pump_and_settle(Duration::from_secs(1)), 0);
+        let opacity = tester.recorded().into_iter().find_map(|(_, x)| match x {
+            Recorded::Opacity(x) => Some(*x),
+            _ => None,
+        });
+        assert_eq!(opacity, Some(1.0));
+    }
+
+    /// builds until its button is tapped, like a widget with an unfinished feature
+    #[derive(Clone)]
+    struct Unfinished;
+    impl RenderAble<RecordingRenderer> for Unfinished {
+        fn mount<'gui>(&self, context: WidgetContext<'gui, RecordingRenderer>) -> WidgetContext<'gui, RecordingRenderer> {
+            context.mount_widget(self)
+        }
+    }
+    impl Widget<RecordingRenderer> for Unfinished {
+        type State = bool;
+        fn create_state(&self) -> Self::State {
+            false
+        }
+        fn build<'gui>(&self, state: State<Self>, _context: &mut BuildContext<'gui>) -> impl RenderAble<RecordingRenderer> + 'static {
+            if *state.read::<RecordingRenderer>() {
+                panic!("the next page failed to build")
+            }
+            TextButton::new(Text::new("next")).on_click(state.update::<_, RecordingRenderer>(|_, state| *state = true))
+        }
+    }
+
+    #[test]
+    fn boundaries_show_the_fallback() {
+        let errors: Arc<Mutex<Vec<WidgetError>>> = Arc::default();
+        let reported = errors.clone();
+        let boundary = ErrorBoundary::new(Unfinished)
+            .with_on_error(move |error| reported.lock().unwrap().push(error.clone()));
+        let mut tester = WidgetTester::new(boundary);
+
+        tester.tap(&Finder::by_text("next"));
+        assert!(!tester.exists(&Finder::by_type::<Unfinished>()));
+        let errors = errors.lock().unwrap();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].message(), "the next page failed to build");
+        assert_eq!(errors[0].path().last().map(String::as_str), Some("Unfinished"));
+        // the red box of debug builds
+        let message = errors[0].to_string();
+        assert!(tester.exists(&Finder::by_text(&message)));
+        assert!(tester.recorded().iter().any(|(_, x)| matches!(x, Recorded::Container { .. })));
+    }
+    #[test]
+    fn boundaries_keep_the_siblings() {
+        let boundary = ErrorBoundary::new(Unfinished).with_fallback(|_| Text::new("unavailable")).with_on_error(|_| ());
+        let mut tester = WidgetTester::new(Hlist::new().add(boundary).add(Text::new("sibling")));
+        assert_eq!(tester.texts(), ["next", "sibling"]);
+
+        tester.tap(&Finder::by_text("next"));
+        assert_eq!(tester.texts(), ["unavailable", "sibling"]);
+    }
+    #[test]
+    fn boundaries_catch_the_first_mount() {
+        struct Broken;
+        impl RenderAble<RecordingRenderer> for Broken {
+            fn mount<'gui>(&self, _context: WidgetContext<'gui, RecordingRenderer>) -> WidgetContext<'gui, RecordingRenderer> {
+                panic!("broken")
+            }
+        }
+        let boundary = ErrorBoundary::new(Broken).with_fallback(|error| Text::new(error.message())).with_on_error(|_| ());
+        let mut tester = WidgetTester::new(boundary);
+        assert_eq!(tester.texts(), ["broken"]);
+        tester.pump();
+        assert_eq!(tester.texts(), ["broken"], "the boundary keeps its fallback");
+    }
+
+    /// a page with a counter that logs when it is disposed and pops itself with its count
+    #[derive(Clone)]
+    struct Screen {
+        name: &'static str,
+        log: Arc<Mutex<Vec<String>>>,
+    }
+    impl RenderAble<RecordingRenderer> for Screen {
+        fn mount<'gui>(&self, context: WidgetContext<'gui, RecordingRenderer>) -> WidgetContext<'gui, RecordingRenderer> {
+            context.mount_widget(self)
+        }
+    }
+    impl Widget<RecordingRenderer> for Screen {
+        type State = u32;
+        fn create_state(&self) -> Self::State {
+            0
+        }
+        fn build<'gui>(&self, state: State<Self>, context: &mut BuildContext<'gui>) -> impl RenderAble<RecordingRenderer> + 'static {
+            let count = *state.read::<RecordingRenderer>();
+            let navigator = Navigator::<RecordingRenderer>::of(context).unwrap();
+            let details = Screen { name: "details", log: self.log.clone() };
+            let handle = state.handle();
+            let open = StateTransaction::new(context.key(), move |context| {
+                navigator.push_for_result(context, Route::new(details.clone()), move |result: Option<u32>| {
+                    handle.update::<_, RecordingRenderer>(move |_, state| *state += result.unwrap_or(100))
+                });
+            });
+            let close = StateTransaction::new(context.key(), move |context| navigator.pop_with(context, count));
+            Hlist::new()
+                .add(Text::new(&format!("{} {count}", self.name)))
+                .add(TextButton::new(Text::new("add")).on_click(state.update::<_, RecordingRenderer>(|_, state| *state += 1)))
+                .add(TextButton::new(Text::new("open")).on_click(open))
+                .add(TextButton::new(Text::new("close")).on_click(close))
+        }
+        fn dispose<'gui>(&self, _context: &mut BuildContext<'gui>) {
+            self.log.lock().unwrap().push(format!("dispose {}", self.name));
+        }
+    }
+    fn navigator() -> (WidgetTester, Arc<Mutex<Vec<String>>>) {
+        let log: Arc<Mutex<Vec<String>>> = Arc::default();
+        let settings_log = log.clone();
+        let navigator = Navigator::new(Screen { name: "home", log: log.clone() })
+            .with_route("/settings", move |_| Screen { name: "settings", log: settings_log.clone() });
+        (WidgetTester::new(navigator), log)
+    }
+    fn depth(tester: &WidgetTester) -> usize {
+        tester.state::<Navigator<RecordingRenderer>>(&Finder::by_type::<Navigator<RecordingRenderer>>()).depth()
+    }
+
+    #[test]
+    fn popped_pages_return_results() {
+        let (mut tester, log) = navigator();
+        tester.tap(&Finder::by_text("add"));
+        tester.tap(&Finder::by_text("open"));
+        assert_eq!(depth(&tester), 2);
+        assert_eq!(tester.texts()[0], "details 0", "only the top page is visible");
+
+        for _ in 0..3 {
+            tester.tap(&Finder::by_text("add"));
+        }
+        tester.tap(&Finder::by_text("close"));
+        assert_eq!(depth(&tester), 1);
+        assert_eq!(tester.texts()[0], "home 4", "the home page kept its state and received the result");
+        assert_eq!(log.lock().unwrap().as_slice(), ["dispose details"]);
+    }
+    #[test]
+    fn named_routes_are_pushed_and_replaced() {
+        let (mut tester, log) = navigator();
+        let handle = tester.find_one(&Finder::by_type::<Navigator<RecordingRenderer>>());
+        // navigates from the visible page, like its event handlers would
+        let navigate = |tester: &mut WidgetTester, f: fn(&NavigatorHandle<RecordingRenderer>, &BuildContext)| {
+            let page = tester.find_one(&Finder::by_type::<Screen>());
+            let context = tester.gui_mut().build_context(page).unwrap();
+            f(&Navigator::of(&context).unwrap(), &context);
+            tester.pump();
+        };
+
+        navigate(&mut tester, |navigator, context| navigator.push_named(context, "/settings"));
+        tester.tap(&Finder::by_text("add"));
+        assert_eq!(tester.texts()[0], "settings 1");
+
+        navigate(&mut tester, |navigator, context| navigator.replace_named(context, "/settings"));
+        assert_eq!(tester.texts()[0], "settings 0", "a replaced page starts with a new state");
+        let state = tester.state::<Navigator<RecordingRenderer>>(&Finder::by_key(handle));
+        assert_eq!(state.names(), [Some("/"), Some("/settings")]);
+
+        navigate(&mut tester, |navigator, context| navigator.pop(context));
+        navigate(&mut tester, |navigator, context| navigator.pop(context));
+        assert_eq!(depth(&tester), 1, "the home route is never popped");
+        assert_eq!(log.lock().unwrap().as_slice(), ["dispose settings", "dispose settings"]);
+    }
+    #[test]
+    fn paths_fill_the_route_params() {
+        let changes: Arc<Mutex<Vec<Navigation>>> = Arc::default();
+        let reported = changes.clone();
+        let navigator = Navigator::new(Text::new("home"))
+            .with_route("/users/:id", |params| Text::new(&format!("user {}", params.get("id").unwrap())))
+            .with_unknown_route(|params| Text::new(&format!("nothing at {}", params.path())))
+            .with_initial_path("/users/7?tab=posts")
+            .with_on_change(move |navigation| reported.lock().unwrap().push(navigation.clone()));
+        let mut tester = WidgetTester::new(navigator);
+        assert_eq!(tester.texts(), ["user 7"]);
+        assert_eq!(depth(&tester), 2, "the home route stays below the initial path");
+
+        // like the back and forward buttons of a browser
+        let handle = Navigator::<RecordingRenderer>::find(tester.gui().tree(), tester.gui().tree().root()).unwrap();
+        let restore = |tester: &mut WidgetTester, depth: usize, path: &str| {
+            let context = tester.gui_mut().build_context(handle.key()).unwrap();
+            handle.restore(&context, depth, path);
+            tester.pump();
+        };
+        restore(&mut tester, 1, "/");
+        assert_eq!(tester.texts(), ["home"]);
+        restore(&mut tester, 2, "/missing");
+        assert_eq!(tester.texts(), ["nothing at /missing"]);
+        let navigation = |depth, path: &str| Navigation { kind: NavigationKind::Restore, depth, path: Some(path.to_owned()) };
+        assert_eq!(changes.lock().unwrap().as_slice(), [navigation(1, "/"), navigation(2, "/missing")]);
+        assert_eq!(match_path("/users/:id", "/users"), None);
+        assert_eq!(match_path("/users/:id/posts", "/users/7/posts/").unwrap().get("id"), Some("7"));
+    }
+    #[test]
+    fn unmatched_initial_paths_start_at_home() {
+        let navigator = Navigator::new(Text::new("home"))
+            .with_route("/users/:id", |params| Text::new(&format!("user {}", params.get("id").unwrap())))
+            .with_initial_path("/missing?tab=posts");
+        let tester = WidgetTester::new(navigator);
+        assert_eq!(tester.texts(), ["home"]);
+        assert_eq!(depth(&tester), 1);
+    }
+
+    /// shows the provided step
+    #[derive(Clone)]
+    struct StepLabel {
+        builds: Arc<Mutex<usize>>,
+    }
+    impl RenderAble<RecordingRenderer> for StepLabel {
+        fn mount<'gui>(&self, context: WidgetContext<'gui, RecordingRenderer>) -> WidgetContext<'gui, RecordingRenderer> {
+            context.mount_widget(self)
+        }
+    }
+    impl Widget<RecordingRenderer> for StepLabel {
+        type State = ();
+        fn create_state(&self) -> Self::State {}
+        fn build<'gui>(&self, _state: State<Self>, context: &mut BuildContext<'gui>) -> impl RenderAble<RecordingRenderer> + 'static {
+            *self.builds.lock().unwrap() += 1;
+            Text::new(&format!("step {}", context.watch::<u32>().copied().unwrap_or_default()))
+        }
+    }
+    /// provides a step that its button increments
+    #[derive(Clone)]
+    struct Stepper {
+        label: StepLabel,
+    }
+    impl RenderAble<RecordingRenderer> for Stepper {
+        fn mount<'gui>(&self, context: WidgetContext<'gui, RecordingRenderer>) -> WidgetContext<'gui, RecordingRenderer> {
+            context.mount_widget(self)
+        }
+    }
+    impl Widget<RecordingRenderer> for Stepper {
+        type State = u32;
+        fn create_state(&self) -> Self::State {
+            1
+        }
+        fn build<'gui>(&self, state: State<Self>, _context: &mut BuildContext<'gui>) -> impl RenderAble<RecordingRenderer> + 'static {
+            Hlist::new()
+                .add(Provider::new(*state.read::<RecordingRenderer>(), self.label.clone()))
+                .add(TextButton::new(Text::new("next")).on_click(state.update::<_, RecordingRenderer>(|_, x| *x += 1)))
+        }
+    }
+
+    #[test]
+    fn providers_rebuild_their_watchers() {
+        let builds = Arc::new(Mutex::new(0));
+        let mut tester = WidgetTester::new(Stepper { label: StepLabel { builds: builds.clone() } });
+        assert_eq!(tester.texts(), ["step 1", "next"]);
+
+        tester.tap(&Finder::by_text("next"));
+        assert_eq!(tester.texts(), ["step 2", "next"]);
+        assert_eq!(*builds.lock().unwrap(), 2, "the watcher is rebuilt once with its parent");
+    }
+
+    /// a counter whose label reads a signal, tapping the button rebuilds only the label
+    #[derive(Clone)]
+    struct SignalCounter {
+        builds: Arc<Mutex<usize>>,
+    }
+    impl RenderAble<RecordingRenderer> for SignalCounter {
+        fn mount<'gui>(&self, context: WidgetContext<'gui, RecordingRenderer>) -> WidgetContext<'gui, RecordingRenderer> {
+            context.mount_widget(self)
+        }
+    }
+    impl Widget<RecordingRenderer> for SignalCounter {
+        type State = ();
+        fn create_state(&self) -> Self::State {}
+        fn build<'gui>(&self, _state: State<Self>, context: &mut BuildContext<'gui>) -> impl RenderAble<RecordingRenderer> + 'static {
+            *self.builds.lock().unwrap() += 1;
+            let count = context.use_signal(0u32);
+            Hlist::new()
+                .add(Reactive::new(move |context| Text::new(&format!("count {}", count.get(context)))))
+                .add(TextButton::new(Text::new("increment")).on_click(count.transaction(|x| *x += 1)))
+        }
+    }
+
+    #[test]
+    fn signals_rebuild_only_their_readers() {
+        let builds = Arc::new(Mutex::new(0));
+        let mut tester = WidgetTester::new(SignalCounter { builds: builds.clone() });
+        assert_eq!(tester.texts(), ["count 0", "increment"]);
+        let label = tester.find_one(&Finder::by_text("count 0"));
+
+        tester.tap(&Finder::by_text("increment"));
+        tester.tap(&Finder::by_text("increment"));
+        assert_eq!(tester.texts(), ["count 2", "increment"]);
+        assert_eq!(*builds.lock().unwrap(), 1, "the counter is not rebuilt");
+        assert_eq!(tester.find_one(&Finder::by_text("count 2")), label, "the text is updated in place");
+    }
+
+    #[test]
+    fn widgets_are_laid_out_in_the_viewport() {
+        let list = Hlist::new()
+            .add(Container::new().with_insets(EdgeInsets::all(10)).with_child(Text::new("abcd")))
+            .add(Image::new("cat.png").with_max_width(100).with_max_height(50))
+            .add(Center::new(Text::new("ab")));
+        let mut tester = WidgetTester::new(list);
+        // monospaced text of size 25 is 12.5 wide per character and 25 high
+        assert_eq!(tester.rect(&Finder::by_type::<Hlist<RecordingRenderer>>()), LayoutRect::new(0.0, 0.0, 800.0, 600.0));
+        assert_eq!(tester.rect(&Finder::by_type::<Container<RecordingRenderer>>()), LayoutRect::new(0.0, 0.0, 70.0, 45.0));
+        assert_eq!(tester.rect(&Finder::by_text("abcd")), LayoutRect::new(10.0, 10.0, 50.0, 25.0));
+        assert_eq!(tester.rect(&Finder::by_type::<Image>()), LayoutRect::new(70.0, 0.0, 100.0, 50.0));
+        assert_eq!(tester.rect(&Finder::by_type::<Center<Text>>()), LayoutRect::new(170.0, 0.0, 630.0, 600.0));
+        assert_eq!(tester.rect(&Finder::by_text("ab")), LayoutRect::new(472.5, 287.5, 25.0, 25.0));
+
+        // the image shrinks when the window gets too small for it, the center keeps the rest
+        tester.set_viewport(Size::new(120.0, 40.0));
+        assert_eq!(tester.rect(&Finder::by_type::<Image>()), LayoutRect::new(70.0, 0.0, 50.0, 40.0));
+        assert_eq!(tester.rect(&Finder::by_type::<Center<Text>>()), LayoutRect::new(120.0, 0.0, 0.0, 40.0));
+    }
+
+    #[test]
+    fn scroll_views_follow_the_wheel_drags_and_controller() {
+        let controller = ScrollController::new();
+        let items = (0..20).fold(Column::new(), |column, i| column.add(Text::new(&format!("item {i}"))));
+        let mut tester = WidgetTester::new(ScrollView::new(items).with_controller(controller.clone()));
+        tester.set_viewport(Size::new(200.0, 100.0));
+        let view = Finder::by_type::<ScrollViewport<RecordingRenderer>>();
+        let offset = |tester: &WidgetTester| {
+            tester.state::<ScrollView<RecordingRenderer>>(&Finder::by_type::<ScrollView<RecordingRenderer>>()).offset()
+        };
+        // 20 lines of 25 in a window of 100
+        assert_eq!(tester.rect(&view).height, 100.0);
+
+        tester.scroll(&Finder::by_text("item 2"), Point::new(40.0, 30.0));
+        assert_eq!(offset(&tester), Point::new(0.0, 30.0), "the view only scrolls vertically");
+        assert_eq!(tester.rect(&Finder::by_text("item 0")).y, -30.0);
+        // the content follows the pointer up
+        tester.drag(&view, Point::new(10.0, 80.0), Point::new(10.0, 40.0));
+        assert_eq!(offset(&tester), Point::new(0.0, 70.0));
+        tester.scroll(&view, Point::new(0.0, -500.0));
+        assert_eq!(offset(&tester), Point::default());
+
+        // a jump past the end shows the end, the wheel goes on from there
+        let context = tester.gui_mut().root_build_context();
+        controller.jump_to(&context, Point::new(0.0, 1000.0));
+        tester.pump();
+        assert_eq!(tester.rect(&Finder::by_text("item 19")).bottom(), 100.0);
+        let key = tester.find_one(&view);
+        let wheel = Event::Scroll { position: Point::default(), delta: Point::new(0.0, 10.0) };
+        assert!(!tester.gui_mut().dispatch_event(key, &wheel), "the wheel bubbles on at the end");
+        tester.scroll(&view, Point::new(0.0, -50.0));
+        assert_eq!(offset(&tester), Point::new(0.0, 350.0));
+        assert_eq!(controller.offset(), Point::new(0.0, 350.0));
+        // the thumb is 100 / 500 of the track and moved 350 / 400 of the rest
+        let thumb = Finder::by_type::<Scrollbar>();
+        assert!(tester.recorded().iter().any(|(_, x)| **x == Recorded::Scrollbar(Axis::Vertical)));
+        assert_eq!(tester.rect(&thumb), LayoutRect::new(tester.rect(&view).right() - 6.0, 70.0, 6.0, 20.0));
+
+        // a page that scrolled itself reports the offset
+        let viewport = tester.rect(&view).size();
+        tester.gui_mut().dispatch_event(key, &Event::Scrolled { offset: Point::new(0.0, 120.0), viewport });
+        assert_eq!(offset(&tester), Point::new(0.0, 120.0));
+        assert_eq!(controller.offset(), Point::new(0.0, 120.0));
+    }
+
+    #[test]
+    fn lazy_lists_keep_a_bounded_amount_of_entities() {
+        let builds = Arc::new(AtomicUsize::new(0));
+        let counted = builds.clone();
+        let controller = ScrollController::new();
+        let list = LazyList::new(100_000, 25.0, move |i| {
+            counted.fetch_add(1, Ordering::Relaxed);
+            Text::new(&format!("item {i}"))
+        })
+        .with_cache_extent(50.0)
+        .with_controller(controller.clone());
+        let mut tester = WidgetTester::new(list);
+        tester.set_viewport(Size::new(200.0, 100.0));
+        let view = Finder::by_type::<ScrollViewport<RecordingRenderer>>();
+        // 4 items are visible and 2 more are cached below them
+        assert_eq!(tester.texts(), (0..6).map(|i| format!("item {i}")).collect::<Vec<_>>());
+        let rect = tester.rect(&Finder::by_text("item 3"));
+        assert_eq!((rect.y, rect.height), (75.0, 25.0));
+        assert!(builds.load(Ordering::Relaxed) < 100);
+
+        // an item keeps its entity while it stays in view
+        let item = tester.find_one(&Finder::by_text("item 5"));
+        tester.scroll(&view, Point::new(0.0, 40.0));
+        assert_eq!(tester.find_one(&Finder::by_text("item 5")), item);
+        assert_eq!(tester.rect(&Finder::by_text("item 5")).y, 125.0 - 40.0);
+
+        // away from the start every slot shows an item
+        tester.scroll(&view, Point::new(0.0, 1000.0));
+        let entities = tester.gui().tree().entity_count();
+        let slots = tester.find(&Finder::by_type::<LazySlot<RecordingRenderer>>()).len();
+        for _ in 0..200 {
+            tester.scroll(&view, Point::new(0.0, 1000.0));
+            assert!(tester.gui().tree().entity_count() <= entities);
+        }
+        assert_eq!(tester.find(&Finder::by_type::<LazySlot<RecordingRenderer>>()).len(), slots);
+        assert!(tester.exists(&Finder::by_text("item 8041")));
+
+        // only the items in view are built for a jump to the end
+        builds.store(0, Ordering::Relaxed);
+        let context = tester.gui_mut().root_build_context();
+        controller.jump_to(&context, Point::new(0.0, f32::INFINITY));
+        tester.pump();
+        assert_eq!(tester.rect(&Finder::by_text("item 99999")).bottom(), 100.0);
+        assert!(builds.load(Ordering::Relaxed) <= slots);
+        assert!(tester.gui().tree().entity_count() <= entities);
+    }
+
+    /// an item that remembers the index it was created for
+    #[derive(Clone)]
+    struct Tile(usize);
+    impl RenderAble<RecordingRenderer> for Tile {
+        fn mount<'gui>(&self, context: WidgetContext<'gui, RecordingRenderer>) -> WidgetContext<'gui, RecordingRenderer> {
+            context.mount_widget(self)
+        }
+    }
+    impl Widget<RecordingRenderer> for Tile {
+        type State = usize;
+        fn create_state(&self) -> Self::State {
+            self.0
+        }
+        fn build<'gui>(&self, state: State<Self>, _context: &mut BuildContext<'gui>) -> impl RenderAble<RecordingRenderer> + 'static {
+            Text::new(&format!("tile {} of {}", self.0, state.read::<RecordingRenderer>()))
+        }
+    }
+
+    #[test]
+    fn recycled_slots_start_with_a_new_state() {
+        let mut tester = WidgetTester::new(LazyList::new(1000, 25.0, Tile).with_cache_extent(50.0));
+        tester.set_viewport(Size::new(200.0, 100.0));
+        let view = Finder::by_type::<ScrollViewport<RecordingRenderer>>();
+        let tiles = |tester: &WidgetTester| {
+            let texts = tester.texts();
+            assert!(!texts.is_empty());
+            for text in texts {
+                let (index, state) = text.strip_prefix("tile ").unwrap().split_once(" of ").unwrap();
+                assert_eq!(index, state);
+            }
+        };
+        tiles(&tester);
+        // the slots of the first items show the next ones, and the first ones again after scrolling back
+        for delta in [60.0, 340.0, -400.0] {
+            tester.scroll(&view, Point::new(0.0, delta));
+            tiles(&tester);
+        }
+        assert!(tester.exists(&Finder::by_text("tile 3 of 3")));
+    }
+}
@@ -0,0 +1,188 @@
+use std::path::PathBuf;
+
+use visora::widget::{button::TextButton, center::Center, container::Container, flex::{Column, Expanded, Flexible, Row, Spacer}, grid::{Grid, GridItem}, image::Image, lazy::{LazyItems, LazySlot}, list::Hlist, opacity::Opacity, scroll::{ScrollViewport, Scrollbar}, text::{RichText, Text}};
+use visora_core::{color::Color, layout::{flex::{Flex, FlexChild}, grid::GridChild, lazy::Lazy, scroll::Scroll, Axis, Size}, renderer::Renderer, treecs::{iterators::breadth::{BreadthInfo, VisibleIter}, query::Query, EntityKey}, widget::Render, WidgetContext};
+
+/// what a widget mounted, with the values a test wants to assert on
+#[derive(Debug, Clone, PartialEq)]
+pub enum Recorded {
+    Text(String),
+    RichText { text: String, color: Color },
+    Button,
+    Center,
+    Hlist,
+    Container { bg: Color, width: Option<f32>, height: Option<f32> },
+    Opacity(f32),
+    Image(PathBuf),
+    /// a row or a column
+    Flex(Flex),
+    /// an expanded, a flexible or a spacer
+    FlexChild(FlexChild),
+    /// the viewport of a scroll view, with the offset it was built with
+    Scroll(Scroll),
+    /// the thumb of a scrollbar along the axis
+    Scrollbar(Axis),
+    /// the content of a lazy list
+    LazyItems(Lazy),
+    /// a slot of a lazy list with the index of the item it shows
+    LazySlot(Option<usize>),
+    Grid,
+    /// a child of a grid with the area it was put at
+    GridItem(GridChild),
+}
+impl Recorded {
+    /// the text of a text widget
+    pub fn text(&self) -> Option<&str> {
+        match self {
+            Self::Text(x) | Self::RichText { text: x, .. } => Some(x),
+            _ => None,
+        }
+    }
+}
+
+/// renderer without output that keeps the items of the last render, in tree order
+///
+/// the tree is laid out in [`RecordingRenderer::viewport`] with monospaced text, so the rects are the same on every machine
+pub struct RecordingRenderer {
+    rendered: Vec<(EntityKey, Recorded)>,
+    frames: usize,
+    pub viewport: Size,
+}
+impl RecordingRenderer {
+    pub fn new() -> Self {
+        Self {
+            rendered: Vec::new(),
+            frames: 0,
+            viewport: Size::new(800.0, 600.0),
+        }
+    }
+    pub fn rendered(&self) -> &[(EntityKey, Recorded)] {
+        &self.rendered
+    }
+    /// the amount of renders so far
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+}
+impl Default for RecordingRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl Renderer for RecordingRenderer {
+    type RenderItem = Recorded;
+    type QueryType<'gui> = VisibleIter<'gui>;
+    fn render<'gui>(&mut self, q: Query<'gui, (EntityKey, &Self::RenderItem), Self::QueryType<'gui>>) {
+        self.rendered = q
+            .filter(|(info, _)| *info == BreadthInfo::Other)
+            .map(|(_, (key, item))| (key, item.clone()))
+            .collect();
+        self.frames += 1;
+    }
+    fn viewport(&self) -> Option<Size> {
+        Some(self.viewport)
+    }
+}
+
+impl Render<Text> for RecordingRenderer {
+    fn mount<'gui>(widget: &Text, context: &mut WidgetContext<'gui, Self>) {
+        context.mount_renderer(Recorded::Text(widget.data.clone()));
+    }
+}
+impl Render<RichText> for RecordingRenderer {
+    fn mount<'gui>(widget: &RichText, context: &mut WidgetContext<'gui, Self>) {
+        context.mount_renderer(Recorded::RichText {
+            text: widget.text().to_owned(),
+            color: *widget.color(),
+        });
+    }
+}
+impl Render<TextButton> for RecordingRenderer {
+    fn mount<'gui>(_widget: &TextButton, context: &mut WidgetContext<'gui, Self>) {
+        context.mount_renderer(Recorded::Button);
+    }
+}
+impl<W: 'static> Render<Center<W>> for RecordingRenderer {
+    fn mount<'gui>(_widget: &Center<W>, context: &mut WidgetContext<'gui, Self>) {
+        context.mount_renderer(Recorded::Center);
+    }
+}
+impl Render<Hlist<Self>> for RecordingRenderer {
+    fn mount<'gui>(_widget: &Hlist<Self>, context: &mut WidgetContext<'gui, Self>) {
+        context.mount_renderer(Recorded::Hlist);
+    }
+}
+impl Render<Container<Self>> for RecordingRenderer {
+    fn mount<'gui>(widget: &Container<Self>, context: &mut WidgetContext<'gui, Self>) {
+        context.mount_renderer(Recorded::Container {
+            bg: *widget.bg(),
+            width: widget.width(),
+            height: widget.height(),
+        });
+    }
+}
+impl Render<Opacity<Self>> for RecordingRenderer {
+    fn mount<'gui>(widget: &Opacity<Self>, context: &mut WidgetContext<'gui, Self>) {
+        context.mount_renderer(Recorded::Opacity(widget.opacity()));
+    }
+}
+impl Render<Image> for RecordingRenderer {
+    fn mount<'gui>(widget: &Image, context: &mut WidgetContext<'gui, Self>) {
+        context.mount_renderer(Recorded::Image(widget.path.clone()));
+    }
+}
+impl Render<Row<Self>> for RecordingRenderer {
+    fn mount<'gui>(widget: &Row<Self>, context: &mut WidgetContext<'gui, Self>) {
+        context.mount_renderer(Recorded::Flex(*widget.layout()));
+    }
+}
+impl Render<Column<Self>> for RecordingRenderer {
+    fn mount<'gui>(widget: &Column<Self>, context: &mut WidgetContext<'gui, Self>) {
+        context.mount_renderer(Recorded::Flex(*widget.layout()));
+    }
+}
+impl Render<Expanded<Self>> for RecordingRenderer {
+    fn mount<'gui>(widget: &Expanded<Self>, context: &mut WidgetContext<'gui, Self>) {
+        context.mount_renderer(Recorded::FlexChild(widget.flex()));
+    }
+}
+impl Render<Flexible<Self>> for RecordingRenderer {
+    fn mount<'gui>(widget: &Flexible<Self>, context: &mut WidgetContext<'gui, Self>) {
+        context.mount_renderer(Recorded::FlexChild(widget.flex()));
+    }
+}
+impl Render<Spacer> for RecordingRenderer {
+    fn mount<'gui>(widget: &Spacer, context: &mut WidgetContext<'gui, Self>) {
+        context.mount_renderer(Recorded::FlexChild(widget.flex()));
+    }
+}
+impl Render<ScrollViewport<Self>> for RecordingRenderer {
+    fn mount<'gui>(widget: &ScrollViewport<Self>, context: &mut WidgetContext<'gui, Self>) {
+        context.mount_renderer(Recorded::Scroll(*widget.layout()));
+    }
+}
+impl Render<Scrollbar> for RecordingRenderer {
+    fn mount<'gui>(widget: &Scrollbar, context: &mut WidgetContext<'gui, Self>) {
+        context.mount_renderer(Recorded::Scrollbar(widget.axis));
+    }
+}
+impl Render<LazyItems<Self>> for RecordingRenderer {
+    fn mount<'gui>(widget: &LazyItems<Self>, context: &mut WidgetContext<'gui, Self>) {
+        context.mount_renderer(Recorded::LazyItems(*widget.layout()));
+    }
+}
+impl Render<LazySlot<Self>> for RecordingRenderer {
+    fn mount<'gui>(widget: &LazySlot<Self>, context: &mut WidgetContext<'gui, Self>) {
+        context.mount_renderer(Recorded::LazySlot(widget.index()));
+    }
+}
+impl Render<Grid<Self>> for RecordingRenderer {
+    fn mount<'gui>(_widget: &Grid<Self>, context: &mut WidgetContext<'gui, Self>) {
+        context.mount_renderer(Recorded::Grid);
+    }
+}
+impl Render<GridItem<Self>> for RecordingRenderer {
+    fn mount<'gui>(widget: &GridItem<Self>, context: &mut WidgetContext<'gui, Self>) {
+        context.mount_renderer(Recorded::GridItem(widget.grid_child()));
+    }
+}
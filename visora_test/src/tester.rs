@@ -0,0 +1,205 @@
+use std::time::Duration;
+
+use visora_core::{
+    animation::clock::ManualClock,
+    event::{Event, Key, KeyState, Modifiers, Point, PointerButton},
+    focus::FocusNode,
+    layout::{LayoutRect, Size},
+    treecs::{iterators::breadth::{BreadthInfo, VisibleIter}, query::Query, EntityKey},
+    widget::{RenderAble, Widget, WidgetType},
+    Component, Gui,
+};
+
+use crate::recorder::{Recorded, RecordingRenderer};
+
+/// the time one frame advances the clock in [`WidgetTester::pump_for`] and [`WidgetTester::pump_and_settle`]
+pub const FRAME: Duration = Duration::from_millis(16);
+
+/// selects entities of the tree of a [`WidgetTester`]
+#[derive(Debug, Clone)]
+pub enum Finder {
+    /// entities mounted by a widget of this type
+    Type(WidgetType),
+    /// entities that rendered a text widget with exactly this text
+    Text(String),
+    Key(EntityKey),
+}
+impl Finder {
+    pub fn by_type<W: ?Sized + 'static>() -> Self {
+        Self::Type(WidgetType::of::<W>())
+    }
+    pub fn by_text(text: &str) -> Self {
+        Self::Text(text.to_owned())
+    }
+    pub fn by_key(key: EntityKey) -> Self {
+        Self::Key(key)
+    }
+    fn matches(&self, gui: &Gui<RecordingRenderer>, key: EntityKey) -> bool {
+        let tree = gui.tree();
+        match self {
+            Self::Type(x) => tree.get_component::<WidgetType>(key) == Some(x),
+            Self::Text(x) => tree.get_component::<Recorded>(key).and_then(Recorded::text) == Some(x.as_str()),
+            Self::Key(x) => *x == key,
+        }
+    }
+}
+
+/// mounts a widget into a gui without a window or a browser and drives it like a user would
+///
+/// the clock only moves when the tester pumps, so animations and timers are deterministic
+pub struct WidgetTester {
+    gui: Gui<RecordingRenderer>,
+    clock: ManualClock,
+}
+impl WidgetTester {
+    pub fn new(widget: impl RenderAble<RecordingRenderer> + 'static) -> Self {
+        let clock = ManualClock::new();
+        let mut gui = Gui::new(RecordingRenderer::new()).with_clock(clock.clone());
+        gui.root_widget_context().mount_child(&widget);
+        gui.render();
+        Self { gui, clock }
+    }
+    pub fn gui(&self) -> &Gui<RecordingRenderer> {
+        &self.gui
+    }
+    pub fn gui_mut(&mut self) -> &mut Gui<RecordingRenderer> {
+        &mut self.gui
+    }
+    pub fn clock(&self) -> &ManualClock {
+        &self.clock
+    }
+
+    /// the matching visible entities in tree order
+    pub fn find(&self, finder: &Finder) -> Vec<EntityKey> {
+        let query: Query<EntityKey, VisibleIter> = Query::new(self.gui.tree());
+        query
+            .filter(|(info, key)| *info == BreadthInfo::Other && finder.matches(&self.gui, *key))
+            .map(|(_, key)| key)
+            .collect()
+    }
+    /// the only matching entity, panics when there is none or more than one
+    pub fn find_one(&self, finder: &Finder) -> EntityKey {
+        match self.find(finder).as_slice() {
+            [key] => *key,
+            found => panic!("expected one entity for {finder:?}, found {}", found.len()),
+        }
+    }
+    pub fn exists(&self, finder: &Finder) -> bool {
+        !self.find(finder).is_empty()
+    }
+    /// the texts of the mounted tree, in tree order
+    pub fn texts(&self) -> Vec<&str> {
+        self.recorded().into_iter().filter_map(|(_, x)| x.text()).collect()
+    }
+    /// what every visible entity of the mounted tree recorded, in tree order
+    pub fn recorded(&self) -> Vec<(EntityKey, &Recorded)> {
+        let query: Query<(EntityKey, &Recorded), VisibleIter> = Query::new(self.gui.tree());
+        query
+            .filter(|(info, _)| *info == BreadthInfo::Other)
+            .map(|(_, x)| x)
+            .collect()
+    }
+    /// where the matching entity was laid out in the last frame
+    pub fn rect(&self, finder: &Finder) -> LayoutRect {
+        let key = self.find_one(finder);
+        *self.gui.tree().get_component::<LayoutRect>(key).expect("the entity was not laid out")
+    }
+    /// lays the tree out in `size` from the next frame on, like a resized window
+    pub fn set_viewport(&mut self, size: Size) {
+        self.gui.renderer().viewport = size;
+        self.pump();
+    }
+    /// the stateful widget stored on the matching entity
+    pub fn widget<W: Component>(&self, finder: &Finder) -> &W {
+        let key = self.find_one(finder);
+        self.gui.tree().get_component::<W>(key).expect("the entity holds no widget of this type")
+    }
+    /// the state of the stateful widget mounted on the matching entity
+    pub fn state<W>(&self, finder: &Finder) -> &W::State
+    where W: Widget<RecordingRenderer>
+    {
+        let key = self.find_one(finder);
+        self.gui.tree().get_component::<W::State>(key).expect("the entity holds no state of this widget")
+    }
+
+    /// presses and releases the primary button on the matching entity, the events bubble up from it
+    pub fn tap(&mut self, finder: &Finder) {
+        let target = self.find_one(finder);
+        let position = Point::default();
+        let button = PointerButton::Primary;
+        self.gui.dispatch_event(target, &Event::PointerDown { position, button });
+        self.gui.dispatch_event(target, &Event::PointerUp { position, button });
+        self.pump();
+    }
+    /// turns the wheel over the matching entity by `delta`, a positive delta scrolls towards the end
+    pub fn scroll(&mut self, finder: &Finder, delta: Point) {
+        let target = self.find_one(finder);
+        let rect = self.rect(finder);
+        let position = Point::new(rect.x, rect.y);
+        self.gui.dispatch_event(target, &Event::Scroll { position, delta });
+        self.pump();
+    }
+    /// presses the primary button at `from` on the matching entity, moves the pointer to `to` and releases it there
+    pub fn drag(&mut self, finder: &Finder, from: Point, to: Point) {
+        let target = self.find_one(finder);
+        let button = PointerButton::Primary;
+        self.gui.dispatch_event(target, &Event::PointerDown { position: from, button });
+        self.gui.dispatch_event(target, &Event::PointerMove { position: to });
+        self.gui.dispatch_event(target, &Event::PointerUp { position: to, button });
+        self.pump();
+    }
+    /// presses and releases `key`, it goes to the focused entity like any keyboard input
+    pub fn press_key(&mut self, key: Key) {
+        self.press_key_with(key, Modifiers::default());
+    }
+    pub fn press_key_with(&mut self, key: Key, modifiers: Modifiers) {
+        for state in [KeyState::Pressed, KeyState::Released] {
+            self.gui.handle_event(Event::Key { key: key.clone(), state, modifiers });
+        }
+        self.pump();
+    }
+    pub fn enter_text(&mut self, text: &str) {
+        self.gui.handle_event(Event::TextInput(text.to_owned()));
+        self.pump();
+    }
+    pub fn focus(&mut self, finder: &Finder) {
+        let key = self.find_one(finder);
+        FocusNode::new(key).request_focus(self.gui.runtime());
+        self.pump();
+    }
+    pub fn focused(&self) -> Option<EntityKey> {
+        self.gui.focused()
+    }
+
+    /// draws a frame without moving the clock, dispatching the pending transactions and the due timers first
+    pub fn pump(&mut self) {
+        self.gui.dispatch_pending();
+        self.gui.fire_timers();
+        self.gui.tick();
+        self.gui.render();
+    }
+    /// moves the clock by `duration` one frame at a time, drawing every frame
+    pub fn pump_for(&mut self, duration: Duration) {
+        let mut left = duration;
+        while !left.is_zero() {
+            let step = left.min(FRAME);
+            self.clock.advance(step);
+            left -= step;
+            self.pump();
+        }
+    }
+    /// draws frames until no animation is running, returns the amount of frames
+    ///
+    /// panics after `timeout` of clock time, an animation that repeats never settles
+    pub fn pump_and_settle(&mut self, timeout: Duration) -> usize {
+        let mut frames = 0;
+        self.pump();
+        while self.gui.is_animating() {
+            assert!(FRAME * frames < timeout, "the animations did not settle within {timeout:?}");
+            self.clock.advance(FRAME);
+            self.pump();
+            frames += 1;
+        }
+        frames as usize
+    }
+}
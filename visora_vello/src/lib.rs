@@ -1,9 +1,9 @@
-use std::{collections::{HashMap, VecDeque}, fmt::Display, ops::{IndexMut, Sub}, path::PathBuf, sync::{Arc, LazyLock}};
+use std::{any::Any, path::PathBuf, sync::{Arc, LazyLock}};
 
 use image::DynamicImage;
-use visora::widget::{self, container::EdgeInsets};
-use visora_core::{color, renderer, treecs::iterators::breadth::{BreadthInfo, BreadthIter, Reversed}, widget::Render};
-use vello::{kurbo::{Affine, Rect, Vec2}, peniko::{self, Blob, Brush, Color, Font, Style}, skrifa::{prelude::Size as SSize, FontRef, MetadataProvider}, Glyph, Scene};
+use visora::widget;
+use visora_core::{color, layout::{LayoutRect, Size, TextMeasurer, TextStyle}, profile::{self, Phase}, renderer, treecs::{iterators::breadth::{BreadthInfo, VisibleIter}, EntityKey}, widget::Render};
+use vello::{kurbo::{Affine, Rect, Stroke, Vec2}, peniko::{self, Blob, Brush, Color, Font, Style}, skrifa::{prelude::Size as SSize, FontRef, MetadataProvider}, Glyph, Scene};
 use winit::window::Window;
 
 pub mod runner;
@@ -12,289 +12,68 @@ pub mod runner;
 pub struct ModulaRenderer{
     pub scene: Scene,
     pub font: Font,
-    pub window: Option<Arc<Window>>
+    pub window: Option<Arc<Window>>,
+    /// the entity selected in the devtools, it is outlined
+    pub highlighted: Option<EntityKey>
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
-pub struct Constraint {
-    width: f32,
-    height: f32
-}
-impl Sub<Constraint> for Constraint {
-    type Output = Constraint;
-    fn sub(self, rhs: Constraint) -> Self::Output {
-        Self {
-            height: self.height - rhs.height,
-            width: self.width - rhs.width
-        }
-    }
-}
-impl Display for Constraint {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "({}, {})", self.width, self.height)
-    }
-}
-pub type Size = Constraint;
-#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
-pub struct Area {
-    top: f32,
-    left: f32,
-    width: f32,
-    height: f32,
-}
-impl Display for Area {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "({}, {}, {}, {})", self.left, self.top, self.width, self.height)
-    }
-}
-#[derive(Debug, Clone, Copy)]
-pub enum Direction {
-    Horizontal,
-    Vertical
-}
-/*
-pub trait Drawable{
-    fn pass_constraint(&self, constraint: Constraint) -> Constraint;
-    fn get_area(&self, self_constraint: Constraints, child_size: Option<Size>, renderer: &ModulaRenderer) -> Size;
-    fn cut_direction(&self) -> Option<Direction> {
-        None
-    }
-    fn draw(&self, area: Area, renderer: &mut ModulaRenderer);
-    fn get_child_area(&self, own_area: Area, child_size: Size) -> Area {
-        own_area
-    }
-}
-
-
-pub struct Text{
-    data: String,
-    fontsize: f32
-}
-impl Drawable for Text {
-    fn pass_constraint(&self, constraint: Constraint) -> Constraint {
-        constraint
-    }
-    fn get_area(&self, self_constraint: Constraints, child_size: Option<Size>, renderer: &ModulaRenderer) -> Size {
-        let fontref = to_font_ref(&renderer.font).unwrap();
-        let fontsize = SSize::new(self.fontsize);
-        let axes = fontref.axes();
-        let var_loc = axes.location::<&[(&str, f32)]>(&[]);
-        let metrics = fontref.metrics(fontsize, &var_loc);
-        let line_height = metrics.ascent - metrics.descent + metrics.leading;
-        let glyph_metrics = fontref.glyph_metrics(fontsize, &var_loc);
-        let charmap = fontref.charmap();
-
-        let mut current_width = 0.0;
-        let mut max_width = 0.0;
-        let mut current_height = 0.0;
-        for x in self.data.chars() {
-            let gid = charmap.map(x).unwrap_or_default();
-            let advance = glyph_metrics.advance_width(gid).unwrap_or_default();
-            current_width += advance;
-            if current_width > self_constraint.modified.width {
-                max_width = self_constraint.modified.width;
-                current_width = 0.0;
-                current_height += line_height;
-            }
-        }
-        current_height += line_height; // since we added another line
-        Constraint { 
-            height: current_height,
-            width: max_width
-        }
-    }
-    fn draw(&self, area: Area, renderer: &mut ModulaRenderer) {
-        let fontsize = SSize::new(self.fontsize);
-        let fontref = to_font_ref(&renderer.font).unwrap();
-        let axes = fontref.axes();
-        let var_loc = axes.location::<&[(&str, f32)]>(&[]);
-        let metrics = fontref.metrics(fontsize, &var_loc);
-        let line_height = metrics.ascent - metrics.descent + metrics.leading;
-        let glyph_metrics = fontref.glyph_metrics(fontsize, &var_loc);
-        let charmap = fontref.charmap();
-        let style = Style::Fill(vello::peniko::Fill::NonZero);
-        let mut pen_y = 0.0;
-        let mut pen_x = 0.0;
-        let offset = Affine::IDENTITY.with_translation(Vec2::new(area.left as f64, (area.top as f64) + line_height  as f64));
-        renderer.scene
-            .draw_glyphs(&renderer.font)
-            .transform(offset)
-            .font_size(self.fontsize)
-            //.normalized_coords(var_loc.coords())
-            .brush(&Brush::Solid(Color::WHITE))
-            .draw(
-                &style,
-                self.data.chars().filter_map(|ch| {
-                    if ch == '\n' {
-                        pen_y += line_height;
-                        pen_x = 0.0;
-                        return None;
-                    }
-                    let gid = charmap.map(ch).unwrap_or_default();
-                    let advance = glyph_metrics.advance_width(gid).unwrap_or_default();
-                    if pen_x + advance >= area.width as f32 {
-                        pen_x = 0.0;
-                        pen_y += line_height;
-                    }
-                    let x = pen_x;
-                    pen_x += advance;
-                    Some(Glyph {
-                        id: gid.to_u32(),
-                        x,
-                        y: pen_y,
-                    })
-                }),
-            );
-    }
-}
-
-
-pub struct Container {
-    insets: EdgeInsets,
-    bg: color::Color
-}
-impl Drawable for Container {
-    fn pass_constraint(&self, constraint: Constraint) -> Constraint {
-        let insets = self.insets;
-        Constraint {
-            height: constraint.height - (insets.top() + insets.bottom()) as f32,
-            width: constraint.width - (insets.left() + insets.right()) as f32
-        }
-    }
-    fn get_child_area(&self, own_area: Area, child_size: Size) -> Area {
-        Area {
-            top: own_area.top + self.insets.top() as f32,
-            left: own_area.left + self.insets.left() as f32,
-            height: child_size.height,
-            width: child_size.width
-        }
-    }
-    fn get_area(&self, self_constraint: Constraints, child_size: Option<Size>, renderer: &ModulaRenderer) -> Size {
-        let child_size = child_size.unwrap();
-        Size {
-            height: child_size.height + (self.insets.top() + self.insets.bottom()) as f32,
-            width: child_size.width + (self.insets.left() + self.insets.right()) as f32
-        }        
-    }
-    fn draw(&self, area: Area, renderer: &mut ModulaRenderer) {
-        let brush = Brush::Solid(Color::rgba8(self.bg.red(), self.bg.green(), self.bg.blue(), self.bg.alpha()));
-        let rect = Rect::new(area.left as f64, area.top as f64, (area.left + area.width) as f64, (area.top + area.height) as f64);
-        let fill = vello::peniko::Fill::NonZero;
-        renderer.scene
-            .fill(fill, Affine::IDENTITY, &brush, None, &rect);
-    }
-}
-
-pub struct Centered;
-impl Drawable for Centered {
-    fn pass_constraint(&self, constraint: Constraint) -> Constraint {
-        constraint
-    }
-    fn get_area(&self, self_constraint: Constraints, child_size: Option<Size>, renderer: &ModulaRenderer) -> Size {
-        self_constraint.original
-    }
-    fn draw(&self, area: Area, renderer: &mut ModulaRenderer) {
-        // nothing to draw
-    }
-    fn get_child_area(&self, own_area: Area, child_size: Size) -> Area {
-        let child_left = own_area.left + own_area.width / 2.0 - child_size.width / 2.0;
-        let child_top = own_area.top + own_area.height / 2.0 - child_size.height / 2.0;
-        Area {
-            left: child_left,
-            top: child_top,
-            height: child_size.height,
-            width: child_size.width
-        }
-    }
+/// paints a render item in the [`LayoutRect`] the gui computed for its entity
+pub trait Drawable: Any + Send + Sync {
+    fn name(&self) -> &'static str;
+    fn draw(&self, renderer: &mut ModulaRenderer, rect: LayoutRect);
+    /// called after the children are drawn
+    fn after_children(&self, _renderer: &mut ModulaRenderer){}
 }
 
+pub type Element = Box<dyn Drawable>;
 
-pub struct List{
-    direction: Direction
-}
-impl Drawable for List {
-    fn cut_direction(&self) -> Option<Direction> {
-        Some(self.direction)
-    }
-    fn draw(&self, area: Area, renderer: &mut ModulaRenderer) {
-        
-    }
-    fn get_area(&self, self_constraint: Constraints, child_size: Option<Size>, renderer: &ModulaRenderer) -> Size {
-        child_size.unwrap_or(self_constraint.original)
-    }
-    fn get_child_area(&self, own_area: Area, child_size: Size) -> Area {
-        own_area
-    }
-    fn pass_constraint(&self, constraint: Constraint) -> Constraint {
-        constraint
-    }
-}
-
-pub enum Element {
-    Text(Text),
-    Container(Container),
-    Centered,
-    List(List)
-}
-impl Drawable for Element {
-    fn get_area(&self, self_constraint: Constraints, child_size: Option<Size>, renderer: &ModulaRenderer) -> Size {
-        match self {
-            Self::Container(x) => x.get_area(self_constraint, child_size, renderer),
-            Self::Text(x) => x.get_area(self_constraint, child_size, renderer),
-            Self::Centered => Centered.get_area(self_constraint, child_size, renderer),
-            Self::List(x) => x.get_area(self_constraint, child_size, renderer)
+/// the drawable mounted on the entity of `context`, when it is a `D`
+fn drawable<'a, D: Drawable>(context: &'a mut visora_core::WidgetContext<'_, ModulaRenderer>) -> Option<&'a mut D> {
+    let element: &mut dyn Any = &mut **context.get_renderer()?;
+    element.downcast_mut()
+}
+
+/// the glyphs of `text` wrapped at `max_width`, with the size they take and the height of a line
+///
+/// the layout measures with it and the text is drawn with it, so both wrap the same lines
+fn layout_glyphs(font: &Font, text: &str, style: &TextStyle, max_width: f32) -> Option<(Vec<Glyph>, Size, f32)> {
+    let fontref = to_font_ref(font)?;
+    let fontsize = SSize::new(style.size);
+    let axes = fontref.axes();
+    let var_loc = if style.bold {
+        axes.location([("wght", 700.0)])
+    } else {
+        axes.location([("wght", 300.0)])
+    };
+    let metrics = fontref.metrics(fontsize, &var_loc);
+    let line_height = metrics.ascent - metrics.descent + metrics.leading;
+    let glyph_metrics = fontref.glyph_metrics(fontsize, &var_loc);
+    let charmap = fontref.charmap();
+
+    let mut glyphs = Vec::new();
+    let (mut pen_x, mut pen_y, mut width) = (0.0f32, 0.0f32, 0.0f32);
+    for ch in text.chars() {
+        if ch == '\n' {
+            width = width.max(pen_x);
+            pen_x = 0.0;
+            pen_y += line_height;
+            continue;
         }
-    }
-    fn pass_constraint(&self, constraint: Constraint) -> Constraint{
-        match self {
-            Self::Container(x) => x.pass_constraint(constraint),
-            Self::Text(x) => x.pass_constraint(constraint),
-            Self::Centered => Centered.pass_constraint(constraint),
-            Self::List(x) => x.pass_constraint(constraint)
-        }
-    }
-    fn draw(&self, area: Area, renderer: &mut ModulaRenderer) {
-        match self {
-            Self::Centered => Centered.draw(area, renderer),
-            Self::Container(x) => x.draw(area, renderer),
-            Self::Text(x) => x.draw(area, renderer),
-            Self::List(x) => x.draw(area, renderer),
-        }
-    }
-    fn get_child_area(&self, own_area: Area, child_size: Size) -> Area {
-        match self {
-            Self::Centered => Centered.get_child_area(own_area, child_size),
-            Self::Container(x) => x.get_child_area(own_area, child_size),
-            Self::Text(x) => x.get_child_area(own_area, child_size),
-            Self::List(x) => x.get_child_area(own_area, child_size)
+        let gid = charmap.map(ch).unwrap_or_default();
+        let advance = glyph_metrics.advance_width(gid).unwrap_or_default();
+        // a line keeps at least one glyph
+        if pen_x > 0.0 && pen_x + advance > max_width {
+            width = width.max(pen_x);
+            pen_x = 0.0;
+            pen_y += line_height;
         }
+        glyphs.push(Glyph { id: gid.to_u32(), x: pen_x, y: pen_y });
+        pen_x += advance;
     }
+    width = width.max(pen_x);
+    Some((glyphs, Size::new(width, pen_y + line_height), line_height))
 }
-impl Element {
-    pub fn name(&self) -> &'static str {
-        match self {
-            Self::Container(_) => "container",
-            Self::Text(_) => "text",
-            Self::Centered => "centered",
-            Self::List(_) => "list"
-        }
-    }
-}*/
 
-pub trait Drawable: Send+ Sync {
-    fn name(&self) -> &'static str;
-    /// get the constraint to push to the constraint stack
-    fn constraint(&self, parent: Constraints, renderer: &ModulaRenderer) -> Option<Constraint>;
-    fn calc_size(&self, parent: Constraints, renderer: &ModulaRenderer) -> Size;
-    fn update_parent(&self, self_size: Size, parent: &mut Constraints, renderer: &ModulaRenderer);
-    fn lays_constraint(&self) -> bool;
-    fn generate_child_area(&self, working_area: &mut Area, child_size: Size) -> Option<Area>{
-        None
-    }
-    fn draw(&self, renderer: &mut ModulaRenderer, area: Area);
-}
-
-pub type Element = Box<dyn Drawable>;
 pub struct Text {
     data: String,
     fontsize: f32,
@@ -304,123 +83,16 @@ impl Drawable for Text {
     fn name(&self) -> &'static str {
         "text"
     }
-    fn lays_constraint(&self) -> bool {
-        false
-    }
-    fn calc_size(&self, parent: Constraints, renderer: &ModulaRenderer) -> Size {
-        let work_area = parent.original;
-        //let fontref = to_font_ref( if self.bold { &renderer.font_bold } else { &renderer.font }).unwrap();
-        let fontref = to_font_ref(&renderer.font).unwrap();
-        
-        let fontsize = SSize::new(self.fontsize);
-        let axes = fontref.axes();
-        let var_loc = if self.bold {
-            axes.location(&[("wght", 700.0)])
-        } else {
-            axes.location(&[("wght", 300.0)])
-        };
-        let metrics = fontref.metrics(fontsize, &var_loc);
-        let line_height = metrics.ascent - metrics.descent + metrics.leading;
-        let glyph_metrics = fontref.glyph_metrics(fontsize, &var_loc);
-        let charmap = fontref.charmap();
-
-        let mut current_width = 0.0;
-        let mut max_width = 0.0;
-        let mut current_height = 0.0;
-        for x in self.data.chars() {
-            let gid = charmap.map(x).unwrap_or_default();
-            let advance = glyph_metrics.advance_width(gid).unwrap_or_default();
-            current_width += advance;
-            if current_width > work_area.width {
-                max_width = work_area.width;
-                current_width = 0.0;
-                current_height += line_height;
-            }
-        }
-        if max_width == 0.0 {
-            max_width = current_width + 1.0; //TODO: this should be better
-        }
-        current_height += line_height; // since we added another line
-
-        Constraint { 
-            height: current_height,
-            width: max_width
-        }
-        /*Constraint {
-            height: if parent.remaining.width == 70.0 { 20.0 } else { 10.0 },
-            width: parent.remaining.width
-        }*/
-    }
-
-
-    fn constraint(&self, parent: Constraints, renderer: &ModulaRenderer) -> Option<Constraint> {
-        None
-    }
-    fn update_parent(&self, self_size: Size, parent: &mut Constraints, renderer: &ModulaRenderer) {
-        parent.remaining.height -= self_size.height;
-        if parent.remaining.width == parent.original.width {
-            parent.remaining.width = self_size.width;
-        } else {
-            parent.remaining.width = self_size.width.max(parent.remaining.width);
-        }
-    }
-    fn draw(&self, renderer: &mut ModulaRenderer, area: Area) {
-        /*println!("drawn text at {area:?}");
-        let brush = Brush::Solid(Color::rgba8(255, 0, 0, 255));
-        let rect = Rect::new(area.left as f64, area.top as f64, (area.left + area.width) as f64, (area.top + area.height) as f64);
-        let fill = vello::peniko::Fill::NonZero;
-        renderer.scene
-            .fill(fill, Affine::IDENTITY, &brush, None, &rect);
-        */
-        let fontref = to_font_ref(&renderer.font).unwrap();
-
-        let fontsize = SSize::new(self.fontsize);
-        let axes = fontref.axes();
-        let var_loc = if self.bold {
-            axes.location([("wght", 700.0)])
-        } else {
-            axes.location(&[("wght", 300.0)])
-
-        };
-        println!("axes = {var_loc:?}");
-        let metrics = fontref.metrics(fontsize, &var_loc);
-        let line_height = metrics.ascent - metrics.descent + metrics.leading;
-        let glyph_metrics = fontref.glyph_metrics(fontsize, &var_loc);
-        let charmap = fontref.charmap();
-
-        let style = Style::Fill(vello::peniko::Fill::NonZero);
-        let mut pen_y = 0.0;
-        let mut pen_x = 0.0;
-        let offset = Affine::IDENTITY.with_translation(Vec2::new(area.left as f64, (area.top as f64) + line_height  as f64));
+    fn draw(&self, renderer: &mut ModulaRenderer, rect: LayoutRect) {
+        let style = TextStyle { size: self.fontsize, bold: self.bold };
+        let Some((glyphs, _, line_height)) = layout_glyphs(&renderer.font, &self.data, &style, rect.width) else { return };
+        let offset = Affine::IDENTITY.with_translation(Vec2::new(rect.x as f64, (rect.y + line_height) as f64));
         renderer.scene
             .draw_glyphs(&renderer.font)
             .transform(offset)
             .font_size(self.fontsize)
-            //.normalized_coords(var_loc.coords())
             .brush(&Brush::Solid(Color::BLACK))
-            .draw(
-                &style,
-                self.data.chars().filter_map(|ch| {
-                    if ch == '\n' {
-                        pen_y += line_height;
-                        pen_x = 0.0;
-                        return None;
-                    }
-                    let gid = charmap.map(ch).unwrap_or_default();
-                    let advance = glyph_metrics.advance_width(gid).unwrap_or_default();
-                    if pen_x + advance >= area.width as f32 {
-                        pen_x = 0.0;
-                        pen_y += line_height;
-                    }
-                    let x = pen_x;
-                    pen_x += advance;
-                    Some(Glyph {
-                        id: gid.to_u32(),
-                        x,
-                        y: pen_y,
-                    })
-                }),
-            );
+            .draw(&Style::Fill(vello::peniko::Fill::NonZero), glyphs.into_iter());
     }
 }
 
@@ -501,304 +173,144 @@ impl Drawable for Image {
     fn name(&self) -> &'static str {
         "image"
     }
-    fn lays_constraint(&self) -> bool {
-        false
-    }
-    fn constraint(&self, parent: Constraints, renderer: &ModulaRenderer) -> Option<Constraint> {
-        None
-    }
-    fn calc_size(&self, parent: Constraints, renderer: &ModulaRenderer) -> Size {
-        let width = self.get_width(parent.remaining);
-        let height = self.get_height(parent.remaining);
-        Size {
-            width: width as f32,
-            height: height as f32
-        }
-    }
-    fn update_parent(&self, self_size: Size, parent: &mut Constraints, renderer: &ModulaRenderer) {
-        parent.remaining.height -= self_size.height;
-        if parent.remaining.width == parent.original.width {
-            parent.remaining.width = self_size.width;
-        } else {
-            parent.remaining.width = self_size.width.max(parent.remaining.width);
-        }
-    }
-    fn generate_child_area(&self, working_area: &mut Area, child_size: Size) -> Option<Area> {
-        None
-    }
-    fn draw(&self, renderer: &mut ModulaRenderer, area: Area) {
-        println!("image offset: {area:?}");
-        let img = self.generate_image(Size{ height: area.height, width: area.width });
-        println!("------ {}x{} {}", img.width,img.height, img.data.len());
-        renderer.scene.draw_image(&img, Affine::translate(Vec2::new(area.left as f64, area.top as f64)));
+    fn draw(&self, renderer: &mut ModulaRenderer, rect: LayoutRect) {
+        let img = self.generate_image(rect.size());
+        tracing::trace!(?rect, width = img.width, height = img.height, bytes = img.data.len(), "image");
+        renderer.scene.draw_image(&img, Affine::translate(Vec2::new(rect.x as f64, rect.y as f64)));
     }
 }
 
+/// fills its rect, the insets and the size are part of the layout
 pub struct Container {
-    insets: EdgeInsets,
     bg: color::Color
 }
 impl Drawable for Container {
     fn name(&self) -> &'static str {
         "container"
     }
-    fn lays_constraint(&self) -> bool {
-        true
-    }
-    fn constraint(&self, parent: Constraints, renderer: &ModulaRenderer) -> Option<Constraint> {
-        let a = Some(Constraint {
-            height: parent.remaining.height - (self.insets.top() + self.insets.bottom()) as f32,
-            width: parent.remaining.width - (self.insets.left() + self.insets.right()) as f32
-        });
-        //println!("c: {parent} -> {a:?}");
-        a
-    }
-    fn calc_size(&self, parent: Constraints, renderer: &ModulaRenderer) -> Size {
-        // parent in this context is the constraint the widget itself laid upon the child
-        let work_area = parent.work_area();
-        Size {
-            width: parent.remaining.width + (self.insets.left() + self.insets.right()) as f32,
-            height: work_area.height + (self.insets.top() + self.insets.bottom()) as f32
-        }
-    }
-    fn update_parent(&self, self_size: Size, parent: &mut Constraints, renderer: &ModulaRenderer) {
-        parent.remaining.height -= self_size.height;
-        if parent.remaining.width == parent.original.width {
-            parent.remaining.width = self_size.width;
-        } else {
-            parent.remaining.width = self_size.width.max(parent.remaining.width);
-        }
-        // not needed, when we are in this widget we pop from the constraint stack and this updates the parent anyway
-    }
-    fn generate_child_area(&self, working_area: &mut Area, child_size: Size) -> Option<Area> {
-        Some(Area {
-            top: working_area.top + self.insets.top() as f32,
-            left: working_area.left + self.insets.left() as f32,
-            width: working_area.width - (self.insets.left() + self.insets.right()) as f32,
-            height: working_area.height - (self.insets.top() + self.insets.bottom()) as f32
-        })
-    }
-    fn draw(&self, renderer: &mut ModulaRenderer, area: Area) {
+    fn draw(&self, renderer: &mut ModulaRenderer, rect: LayoutRect) {
         let brush = Brush::Solid(Color::rgba8(self.bg.red(), self.bg.green(), self.bg.blue(), self.bg.alpha()));
-        let rect = Rect::new(area.left as f64, area.top as f64, (area.left + area.width) as f64, (area.top + area.height) as f64);
         let fill = vello::peniko::Fill::NonZero;
         renderer.scene
-            .fill(fill, Affine::IDENTITY, &brush, None, &rect);
+            .fill(fill, Affine::IDENTITY, &brush, None, &to_rect(rect));
     }
 }
-pub struct List{
-    dir: Direction
-}
+pub struct List;
 impl Drawable for List {
     fn name(&self) -> &'static str {
         "list"
     }
-    fn lays_constraint(&self) -> bool {
-        false
+    fn draw(&self, _renderer: &mut ModulaRenderer, _rect: LayoutRect) {
+        // a list doesnt draw and only reserves space
     }
-    fn calc_size(&self, parent: Constraints, renderer: &ModulaRenderer) -> Size {
-        Size {
-            width: parent.remaining.width,
-            height: parent.original.height - parent.remaining.height
-        }
+}
+/// draws the children into a translucent layer
+pub struct Opacity {
+    opacity: f32
+}
+impl Drawable for Opacity {
+    fn name(&self) -> &'static str {
+        "opacity"
     }
-    fn constraint(&self, parent: Constraints, renderer: &ModulaRenderer) -> Option<Constraint> {
-        None
+    fn draw(&self, renderer: &mut ModulaRenderer, rect: LayoutRect) {
+        renderer.scene.push_layer(peniko::Mix::Normal, self.opacity, Affine::IDENTITY, &to_rect(rect));
     }
-    fn update_parent(&self, self_size: Size, parent: &mut Constraints, renderer: &ModulaRenderer) {
-        // TODO: does this need something?
+    fn after_children(&self, renderer: &mut ModulaRenderer) {
+        renderer.scene.pop_layer();
     }
-    fn generate_child_area(&self, working_area: &mut Area, child_size: Size) -> Option<Area> {
-        let out = Area {
-            top: working_area.top,
-            left: working_area.left,
-            width: working_area.width,
-            height: working_area.height
-        };
-        working_area.top += child_size.height;
-        working_area.height -= child_size.height;
-        Some(out)
+}
+/// the space of a flexible child of a row or column
+pub struct Space;
+impl Drawable for Space {
+    fn name(&self) -> &'static str {
+        "space"
     }
-    fn draw(&self, renderer: &mut ModulaRenderer, area: Area) {
-        // a list doesnt draw and only reserves space
+    fn draw(&self, _renderer: &mut ModulaRenderer, _rect: LayoutRect) {
+        // only the child of an expanded draws
     }
 }
-pub struct Center;
-impl Drawable for Center {
+/// clips the children of a scroll view to its rect
+pub struct Clip;
+impl Drawable for Clip {
     fn name(&self) -> &'static str {
-        "center"
+        "clip"
     }
-    fn lays_constraint(&self) -> bool {
-        true
+    fn draw(&self, renderer: &mut ModulaRenderer, rect: LayoutRect) {
+        renderer.scene.push_layer(peniko::Mix::Clip, 1.0, Affine::IDENTITY, &to_rect(rect));
     }
-    fn constraint(&self, parent: Constraints, renderer: &ModulaRenderer) -> Option<Constraint> {
-        Some(parent.remaining)
+    fn after_children(&self, renderer: &mut ModulaRenderer) {
+        renderer.scene.pop_layer();
     }
-    fn calc_size(&self, parent: Constraints, renderer: &ModulaRenderer) -> Size {
-        parent.remaining
+}
+/// the thumb of a scrollbar, the layout only gives it a size when the content overflows
+pub struct Thumb;
+impl Drawable for Thumb {
+    fn name(&self) -> &'static str {
+        "thumb"
     }
-    fn update_parent(&self, self_size: Size, parent: &mut Constraints, renderer: &ModulaRenderer) {
-        // TODO: check if this shouldnt do anything
+    fn draw(&self, renderer: &mut ModulaRenderer, rect: LayoutRect) {
+        if rect.width == 0.0 || rect.height == 0.0 {
+            return;
+        }
+        let radius = rect.width.min(rect.height) as f64 / 2.0;
+        let brush = Brush::Solid(Color::rgba8(0, 0, 0, 100));
+        renderer.scene
+            .fill(peniko::Fill::NonZero, Affine::IDENTITY, &brush, None, &to_rect(rect).to_rounded_rect(radius));
     }
-    fn generate_child_area(&self, working_area: &mut Area, child_size: Size) -> Option<Area> {
-        let child_top = working_area.height / 2.0 - child_size.height / 2.0;
-        let child_left = working_area.width / 2.0 - child_size.width / 2.0;
-        Some(Area {
-            height: child_size.height,
-            width: child_size.width,
-            top: child_top,
-            left: child_left
-        })
+}
+pub struct Center;
+impl Drawable for Center {
+    fn name(&self) -> &'static str {
+        "center"
     }
-    fn draw(&self, renderer: &mut ModulaRenderer, area: Area) {
+    fn draw(&self, _renderer: &mut ModulaRenderer, _rect: LayoutRect) {
         
     }
 }
 
-
-#[derive(Clone, Copy)]
-pub struct Constraints {
-    original: Constraint,
-    remaining: Constraint
-}
-impl Display for Constraints {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{{og: {},rem: {} }}", self.original, self.remaining)
-    }
-}
-impl Constraints {
-    pub fn new(constraint: Constraint) -> Self {
-        Self{
-            original: constraint,
-            remaining: constraint
-        }
-    }
-    pub fn work_area(&self) -> Constraint {
-        self.original - self.remaining
-    }
+fn to_rect(rect: LayoutRect) -> Rect {
+    Rect::new(rect.x as f64, rect.y as f64, rect.right() as f64, rect.bottom() as f64)
 }
 
-impl ModulaRenderer {
-    pub fn generate_sizes<'gui>(&self, window: &Window, q: &mut visora_core::treecs::query::Query<'gui, &<Self as renderer::Renderer>::RenderItem, <Self as renderer::Renderer>::QueryType<'gui>>) -> VecDeque<Size>{
-        let mut constraints = Vec::new();
-        constraints.push(Constraints::new(Constraint{
-            width: window.outer_size().width as f32,
-            height: window.outer_size().height as f32
-            //width: 100.0,
-            //height: 100.0
-        }));
-        //let mut cut_directions = Vec::new();
-        let mut sizes = VecDeque::new();
-        println!("\nsizes");
-        
-        while let Some((info, ell)) = q.next() {
-            println!("{} {}: ", ell.name(), if info == BreadthInfo::MoveUp { '^' } else { 'V' });
-            println!("\t{}", constraints.last().unwrap());
-            match info {
-                BreadthInfo::Other => {
-                    let parent_constraint = *constraints.last().unwrap();
-                    if let Some(x) = ell.constraint(parent_constraint, self){
-                        println!("\tpush({x})");
-                        constraints.push(Constraints::new(x));
-                    }
-                    // when going down we always push the childs constraint
-                    /*let self_constraint = constraints.last().unwrap();
-                    
-                    let constraint_of_child = ell.pass_constraint(self_constraint.modified);
-                    println!("c {}: self {} child: {}", ell.name(), self_constraint.modified, constraint_of_child);
-                    constraints.push(Constraints::new(constraint_of_child));
-                    */
-                    /*if let Some(x) = ell.cut_direction() {
-                        cut_directions.push(x);
-                    }*/
-                },
-                BreadthInfo::MoveUp => {
-                    let parent_constraint = *constraints.last().unwrap();
-                    let size: Size = ell.calc_size(parent_constraint, self);
-                    println!("\tcalc_size({}) == {}", parent_constraint, size);
-                    //print!("p {} {}", *parent_constraint, size);
-                    if ell.lays_constraint() {
-                        let a = constraints.pop();
-                        println!("\tpop({}) ", a.unwrap())
-                    }
-                    let p_before = parent_constraint;
-                    let parent_constraint = constraints.last_mut().unwrap();
-                    ell.update_parent(size, parent_constraint, self);
-                    println!("\tupdate parent {p_before} -> {parent_constraint}");                  
-                    sizes.push_back(size);
-
-                }
-            }
-        }
-
-        sizes
+impl TextMeasurer for ModulaRenderer {
+    fn measure(&self, text: &str, style: &TextStyle, max_width: f32) -> Size {
+        layout_glyphs(&self.font, text, style, max_width).map(|(_, size, _)| size).unwrap_or_default()
     }
 }
 impl renderer::Renderer for ModulaRenderer {
-    type QueryType<'gui> = BreadthIter<'gui, Reversed>;
+    type QueryType<'gui> = VisibleIter<'gui>;
     type RenderItem = Element;
-    fn render<'gui>(&mut self, mut q: visora_core::treecs::query::Query<'gui, &Self::RenderItem, Self::QueryType<'gui>>) {
-        let Some(x) = &self.window else { return ; };
-        let mut sizes = self.generate_sizes(&x, &mut q);
-        let mut area_stack = Vec::new();
-        area_stack.push(Area{
-            top: 0.0,
-            left: 0.0,
-            height: x.outer_size().height as f32,
-            width: x.outer_size().width as f32
-        });
-        /*area_stack.push(Area{
-            top: 0.0,
-            left: 0.0,
-            height: 100.0,
-            width: 100.0
-        });*/
-        println!("");
-        println!("astack = {area_stack:?}");
-        println!("sizestack = {sizes:?}");
-        //sizes.pop_back();
-        let q = q.restart().reverse();
-        for (info, ell) in q {
+    fn highlight(&mut self, entity: Option<EntityKey>) {
+        self.highlighted = entity;
+    }
+    fn viewport(&self) -> Option<Size> {
+        let size = self.window.as_ref()?.outer_size();
+        Some(Size::new(size.width as f32, size.height as f32))
+    }
+    fn text_measurer(&self) -> &dyn TextMeasurer {
+        self
+    }
+    fn render<'gui>(&mut self, mut q: visora_core::treecs::query::Query<'gui, (EntityKey, &Self::RenderItem), Self::QueryType<'gui>>) {
+        if self.window.is_none() {
+            return;
+        }
+        let _phase = profile::enter(Phase::Paint);
+        while let Some((info, (key, ell))) = q.next() {
             match info {
                 BreadthInfo::Other => {
-                    println!("{}: ", ell.name());
-                    println!("\tself_area: {}", area_stack.last().unwrap());
-                    println!("\tself_size: {:?}", sizes.back().unwrap());
-                    let self_size = sizes.pop_back().unwrap();
-                    if let Some(child_size) = sizes.back(){
-                        let self_area = area_stack.last_mut().unwrap();
-                        let child_area = ell.generate_child_area(self_area, *child_size);
-                        if let Some(child_area) = child_area {
-                            area_stack.push(child_area);
-                        }
-                        println!("\tchild_area: {child_area:?}");
-                    }
-                    let self_area = if ell.generate_child_area(&mut Area{ top: 0.0, left: 0.0, width: 0.0, height: 0.0}, self_size).is_some(){
-                        area_stack.index_mut(area_stack.len()-2)
-                    } else {
-                        area_stack.last_mut().unwrap()
-                    };
-                    ell.draw(self, *self_area);
-                    self_area.top += self_size.height;
-                    println!("\tmodified_area: {self_area}");
-                    //let child_area = sizes.pop_back().unwrap();
-                    //let area = ell.generate_child_area(self_area, child_area);
-                    //println!("area: {area:?}");
-                    /*if let Some(c_size) = sizes.pop_back() {
-                        let area = ell.get_child_area(self_area, c_size);
-                        println!("{}: sa {} cs {} ca {}", ell.name(), self_area, c_size, area);
-                        area_stack.push(area);
-                    }*/
-                    //println!("draw {} at {}", ell.name(), self_area);
-                    //ell.draw(self_area, self);
-                },
-                BreadthInfo::MoveUp => {
-                    if ell.lays_constraint(){
-                        area_stack.pop();
-                    }
+                    // the gui laid out every visible entity before the render
+                    let rect = q.component::<LayoutRect>(key).copied().unwrap_or_default();
+                    tracing::trace!(element = ell.name(), ?rect, "draw");
+                    ell.draw(self, rect);
                 }
+                BreadthInfo::MoveUp => ell.after_children(self)
             }
         }
+        // the outline is drawn over everything, so it isn't hidden by the children of the entity
+        if let Some(rect) = self.highlighted.and_then(|x| q.component::<LayoutRect>(x)) {
+            let brush = Brush::Solid(Color::rgba8(255, 0, 255, 255));
+            self.scene.stroke(&Stroke::new(2.0), Affine::IDENTITY, &brush, None, &to_rect(*rect));
+        }
     }
 }
 
@@ -811,21 +323,101 @@ impl Render<widget::text::Text> for ModulaRenderer {
             bold: widget.is_bold
         }))
     }
+    fn update<'gui>(old: &widget::text::Text, new: &widget::text::Text, context: &mut visora_core::WidgetContext<'gui, Self>) {
+        let Some(text) = drawable::<Text>(context) else { return Self::mount(new, context) };
+        if old.data != new.data {
+            text.data = new.data.clone();
+        }
+        text.bold = new.is_bold;
+    }
 }
 impl Render<widget::container::Container<Self>> for ModulaRenderer {
     fn mount<'gui>(widget: &widget::container::Container<Self>, context: &mut visora_core::WidgetContext<'gui, Self>) {
         context.mount_renderer(Box::new(Container {
-            bg: *widget.bg(),
-            insets: *widget.insets()
+            bg: *widget.bg()
         }));
     }
+    fn update<'gui>(_old: &widget::container::Container<Self>, new: &widget::container::Container<Self>, context: &mut visora_core::WidgetContext<'gui, Self>) {
+        let Some(container) = drawable::<Container>(context) else { return Self::mount(new, context) };
+        container.bg = *new.bg();
+    }
 }
-impl Render<widget::text::Vlist<Self>> for ModulaRenderer {
-    fn mount<'gui>(widget: &widget::text::Vlist<Self>, context: &mut visora_core::WidgetContext<'gui, Self>) {
-        context.mount_renderer(Box::new(List {
-            dir: Direction::Vertical
+impl Render<widget::opacity::Opacity<Self>> for ModulaRenderer {
+    fn mount<'gui>(widget: &widget::opacity::Opacity<Self>, context: &mut visora_core::WidgetContext<'gui, Self>) {
+        context.mount_renderer(Box::new(Opacity {
+            opacity: widget.opacity()
         }));
     }
+    fn update<'gui>(_old: &widget::opacity::Opacity<Self>, new: &widget::opacity::Opacity<Self>, context: &mut visora_core::WidgetContext<'gui, Self>) {
+        let Some(opacity) = drawable::<Opacity>(context) else { return Self::mount(new, context) };
+        opacity.opacity = new.opacity();
+    }
+}
+impl Render<widget::list::Hlist<Self>> for ModulaRenderer {
+    fn mount<'gui>(_widget: &widget::list::Hlist<Self>, context: &mut visora_core::WidgetContext<'gui, Self>) {
+        context.mount_renderer(Box::new(List));
+    }
+}
+impl Render<widget::flex::Row<Self>> for ModulaRenderer {
+    fn mount<'gui>(_widget: &widget::flex::Row<Self>, context: &mut visora_core::WidgetContext<'gui, Self>) {
+        context.mount_renderer(Box::new(List));
+    }
+}
+impl Render<widget::flex::Column<Self>> for ModulaRenderer {
+    fn mount<'gui>(_widget: &widget::flex::Column<Self>, context: &mut visora_core::WidgetContext<'gui, Self>) {
+        context.mount_renderer(Box::new(List));
+    }
+}
+impl Render<widget::flex::Expanded<Self>> for ModulaRenderer {
+    fn mount<'gui>(_widget: &widget::flex::Expanded<Self>, context: &mut visora_core::WidgetContext<'gui, Self>) {
+        context.mount_renderer(Box::new(Space));
+    }
+}
+impl Render<widget::flex::Flexible<Self>> for ModulaRenderer {
+    fn mount<'gui>(_widget: &widget::flex::Flexible<Self>, context: &mut visora_core::WidgetContext<'gui, Self>) {
+        context.mount_renderer(Box::new(Space));
+    }
+}
+impl Render<widget::flex::Spacer> for ModulaRenderer {
+    fn mount<'gui>(_widget: &widget::flex::Spacer, context: &mut visora_core::WidgetContext<'gui, Self>) {
+        context.mount_renderer(Box::new(Space));
+    }
+}
+impl Render<widget::scroll::ScrollViewport<Self>> for ModulaRenderer {
+    fn mount<'gui>(_widget: &widget::scroll::ScrollViewport<Self>, context: &mut visora_core::WidgetContext<'gui, Self>) {
+        context.mount_renderer(Box::new(Clip));
+    }
+    // the layout moves the content, the clip stays the same
+    fn update<'gui>(_old: &widget::scroll::ScrollViewport<Self>, new: &widget::scroll::ScrollViewport<Self>, context: &mut visora_core::WidgetContext<'gui, Self>) {
+        if drawable::<Clip>(context).is_none() {
+            Self::mount(new, context);
+        }
+    }
+}
+impl Render<widget::scroll::Scrollbar> for ModulaRenderer {
+    fn mount<'gui>(_widget: &widget::scroll::Scrollbar, context: &mut visora_core::WidgetContext<'gui, Self>) {
+        context.mount_renderer(Box::new(Thumb));
+    }
+}
+impl Render<widget::grid::Grid<Self>> for ModulaRenderer {
+    fn mount<'gui>(_widget: &widget::grid::Grid<Self>, context: &mut visora_core::WidgetContext<'gui, Self>) {
+        context.mount_renderer(Box::new(List));
+    }
+}
+impl Render<widget::grid::GridItem<Self>> for ModulaRenderer {
+    fn mount<'gui>(_widget: &widget::grid::GridItem<Self>, context: &mut visora_core::WidgetContext<'gui, Self>) {
+        context.mount_renderer(Box::new(Space));
+    }
+}
+impl Render<widget::lazy::LazyItems<Self>> for ModulaRenderer {
+    fn mount<'gui>(_widget: &widget::lazy::LazyItems<Self>, context: &mut visora_core::WidgetContext<'gui, Self>) {
+        context.mount_renderer(Box::new(List));
+    }
+}
+impl Render<widget::lazy::LazySlot<Self>> for ModulaRenderer {
+    fn mount<'gui>(_widget: &widget::lazy::LazySlot<Self>, context: &mut visora_core::WidgetContext<'gui, Self>) {
+        context.mount_renderer(Box::new(Space));
+    }
 }
 impl Render<widget::center::Center<Self>> for ModulaRenderer {
     fn mount<'gui>(widget: &widget::center::Center<Self>, context: &mut visora_core::WidgetContext<'gui, Self>) {
@@ -10,6 +10,7 @@ use vello::{peniko::{Blob, Font}, Scene};
 use visora::widget::{center::Center, container::Container, image::Image, text::{Text, Vlist}};
 use visora_core::{color::Color, renderer::Renderer, widget::{Render, StatelessWidget, Widget}};
 use visora_macros::StatelessWidget;
+use tracing_subscriber::EnvFilter;
 use visora_vello::{runner::run_app, ModulaRenderer};
 
 
@@ -43,6 +44,8 @@ where R: Render<Container<R>> + Render<Text> + Render<Vlist<R>> + Render<Center<
 //const FONT: &[u8] = include_bytes!("/usr/share/fonts/cantarell/Cantarell-VF.otf");
     
 fn main(){
+    // `RUST_LOG=visora_vello=trace` shows the layout of every frame
+    tracing_subscriber::fmt().with_env_filter(EnvFilter::from_default_env()).init();
     let path = "/usr/share/fonts/cantarell/Cantarell-VF.otf";
     //let path = "/usr/share/fonts/opentype/urw-base35/NimbusRoman-Regular.otf";
     let data = std::fs::read(path).unwrap();
@@ -52,7 +55,8 @@ fn main(){
     let renderer = ModulaRenderer {
         font,
         scene: Scene::new(),
-        window: None
+        window: None,
+        highlighted: None
     };
     run_app(renderer, App);
 }
@@ -4,8 +4,10 @@ use visora::widget::container::{Container, EdgeInsets};
 use visora::widget::image::Image;
 use visora::widget::text::{Text, Vlist};
 use visora_core::{color, renderer, Gui};
+use visora_core::event::{Event, Key, KeyState, Modifiers, Point, PointerButton};
 use visora_core::treecs::iterators::breadth::{BreadthInfo, BreadthIter, Reversed};
-use visora_core::widget::{Render, StatelessWidget, Widget};
+use visora_core::treecs::EntityKey;
+use visora_core::widget::{Render, RenderAble, StatelessWidget, Widget};
 use visora_macros::StatelessWidget;
 use crate::ModulaRenderer;
 use vello::skrifa::prelude::Size;
@@ -13,6 +15,7 @@ use vello::skrifa::{FontRef, MetadataProvider};
 use std::collections::VecDeque;
 use std::num::NonZeroUsize;
 use std::sync::Arc;
+use std::time::Instant;
 use vello::kurbo::{Affine, Circle, Ellipse, Line, Rect, RoundedRect, Stroke, Vec2};
 use vello::peniko::{Blob, Brush, Color, Font, Style};
 use vello::util::{RenderContext, RenderSurface};
@@ -20,7 +23,8 @@ use vello::{AaConfig, Glyph, Renderer, RendererOptions, Scene};
 use winit::application::ApplicationHandler;
 use winit::dpi::LogicalSize;
 use winit::event::*;
-use winit::event_loop::{ActiveEventLoop, EventLoop};
+use winit::keyboard::{self, NamedKey};
+use winit::event_loop::{ActiveEventLoop, ControlFlow, EventLoop};
 use winit::window::Window;
 
 use vello::wgpu;
@@ -38,6 +42,36 @@ enum RenderState<'s> {
     Suspended(Option<Arc<Window>>),
 }
 
+/// a window of [`run_windows`], its widget is mounted in a root of its own
+pub struct AppWindow {
+    title: String,
+    size: LogicalSize<u32>,
+    widget: Box<dyn RenderAble<ModulaRenderer>>,
+}
+impl AppWindow {
+    pub fn new(title: &str, widget: impl RenderAble<ModulaRenderer> + 'static) -> Self {
+        Self {
+            title: title.to_owned(),
+            size: LogicalSize::new(1044, 800),
+            widget: Box::new(widget),
+        }
+    }
+    pub fn with_size(mut self, width: u32, height: u32) -> Self {
+        self.size = LogicalSize::new(width, height);
+        self
+    }
+}
+
+/// a window and the root of the gui it shows
+struct WindowState<'s> {
+    root: EntityKey,
+    title: String,
+    size: LogicalSize<u32>,
+    state: RenderState<'s>,
+    // winit reports the cursor separately from the events that need it
+    cursor: Point,
+}
+
 struct AppRunner<'s> {
     // The vello RenderContext which is a global context that lasts for the
     // lifetime of the application
@@ -46,46 +80,89 @@ struct AppRunner<'s> {
     // An array of renderers, one per wgpu device
     renderers: Vec<Option<Renderer>>,
 
-    // State for our example where we store the winit Window and the wgpu Surface
-    state: RenderState<'s>,
+    // the first window shows the main root, closing it ends the app
+    windows: Vec<WindowState<'s>>,
+
+    gui: Gui<ModulaRenderer>,
+
+    // winit reports the modifiers separately from the events that need them
+    modifiers: Modifiers,
 
-    gui: Gui<ModulaRenderer>
+    // serves the inspector when the VISORA_DEVTOOLS environment variable holds an address
+    #[cfg(feature = "devtools")]
+    devtools: Option<visora_devtools::DevtoolsServer>,
+}
+impl<'s> AppRunner<'s> {
+    fn request_redraw(&self) {
+        for window in &self.windows {
+            if let RenderState::Active(state) = &window.state {
+                state.window.request_redraw();
+            }
+        }
+    }
 }
 
 impl<'s> ApplicationHandler for AppRunner<'s> {
     fn resumed(&mut self, event_loop: &ActiveEventLoop) {
-        let RenderState::Suspended(cached_window) = &mut self.state else {
-            return;
-        };
+        for window_state in &mut self.windows {
+            let RenderState::Suspended(cached_window) = &mut window_state.state else {
+                continue;
+            };
+
+            // Get the winit window cached in a previous Suspended event or else create a new window
+            let window = cached_window
+                .take()
+                .unwrap_or_else(|| create_winit_window(event_loop, &window_state.title, window_state.size));
+            self.gui.root_renderer(window_state.root).unwrap().window = Some(window.clone());
+            // Create a vello Surface
+            let size = window.inner_size();
+            let surface_future = self.context.create_surface(
+                window.clone(),
+                size.width,
+                size.height,
+                wgpu::PresentMode::AutoVsync,
+            );
+            let surface = pollster::block_on(surface_future).expect("Error creating surface");
 
-        // Get the winit window cached in a previous Suspended event or else create a new window
-        let window = cached_window
-            .take()
-            .unwrap_or_else(|| create_winit_window(event_loop));
-        self.gui.renderer().window = Some(window.clone());
-        // Create a vello Surface
-        let size = window.inner_size();
-        let surface_future = self.context.create_surface(
-            window.clone(),
-            size.width,
-            size.height,
-            wgpu::PresentMode::AutoVsync,
-        );
-        let surface = pollster::block_on(surface_future).expect("Error creating surface");
-
-        // Create a vello Renderer for the surface (using its device id)
-        self.renderers
-            .resize_with(self.context.devices.len(), || None);
-        self.renderers[surface.dev_id]
-            .get_or_insert_with(|| create_vello_renderer(&self.context, &surface));
-
-        // Save the Window and Surface to a state variable
-        self.state = RenderState::Active(ActiveRenderState { window, surface });
+            // Create a vello Renderer for the surface (using its device id)
+            self.renderers
+                .resize_with(self.context.devices.len(), || None);
+            self.renderers[surface.dev_id]
+                .get_or_insert_with(|| create_vello_renderer(&self.context, &surface));
+
+            // Save the Window and Surface to a state variable
+            window_state.state = RenderState::Active(ActiveRenderState { window, surface });
+        }
     }
 
     fn suspended(&mut self, _event_loop: &ActiveEventLoop) {
-        if let RenderState::Active(state) = &self.state {
-            self.state = RenderState::Suspended(Some(state.window.clone()));
+        for window_state in &mut self.windows {
+            if let RenderState::Active(state) = &window_state.state {
+                window_state.state = RenderState::Suspended(Some(state.window.clone()));
+            }
+        }
+    }
+
+    fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
+        if self.gui.fire_timers() > 0 {
+            self.request_redraw();
+        }
+        // the loop sleeps until the next timer is due instead of polling for it
+        let next_timer = self.gui.until_next_timer();
+        // the inspector can only select entities while the loop is awake
+        #[cfg(feature = "devtools")]
+        let next_timer = match &self.devtools {
+            Some(devtools) => {
+                if devtools.sync(&mut self.gui) {
+                    self.request_redraw();
+                }
+                Some(next_timer.map_or(DEVTOOLS_POLL, |x| x.min(DEVTOOLS_POLL)))
+            }
+            None => next_timer,
+        };
+        match next_timer {
+            Some(x) => event_loop.set_control_flow(ControlFlow::WaitUntil(Instant::now() + x)),
+            None => event_loop.set_control_flow(ControlFlow::Wait),
         }
     }
 
@@ -96,85 +173,169 @@ impl<'s> ApplicationHandler for AppRunner<'s> {
         event: WindowEvent,
     ) {
         // Ignore the event (return from the function) if
-        //   - we have no render_state
-        //   - OR the window id of the event doesn't match the window id of our render_state
+        //   - no window has a render_state
+        //   - OR the window id of the event doesn't match the window id of any render_state
         //
         // Else extract a mutable reference to the render state from its containing option for use below
-        let render_state = match &mut self.state {
-            RenderState::Active(state) if state.window.id() == window_id => state,
-            _ => return,
+        let Some(index) = self.windows.iter().position(|x| matches!(&x.state, RenderState::Active(state) if state.window.id() == window_id)) else {
+            return;
+        };
+        let root = self.windows[index].root;
+        let RenderState::Active(render_state) = &mut self.windows[index].state else {
+            return;
         };
 
         match event {
-            // Exit the event loop when a close is requested (e.g. window's close button is pressed)
-            WindowEvent::CloseRequested => event_loop.exit(),
+            // Exit the event loop when the main window is closed, the other windows only remove their root
+            WindowEvent::CloseRequested if index == 0 => event_loop.exit(),
+            WindowEvent::CloseRequested => {
+                self.windows.remove(index);
+                self.gui.remove_root(root);
+                self.request_redraw();
+            }
 
             // Resize the surface when the window is resized
             WindowEvent::Resized(size) => {
                 self.context
                     .resize_surface(&mut render_state.surface, size.width, size.height);
             }
+            WindowEvent::ModifiersChanged(modifiers) => {
+                let state = modifiers.state();
+                self.modifiers = Modifiers {
+                    shift: state.shift_key(),
+                    ctrl: state.control_key(),
+                    alt: state.alt_key(),
+                    meta: state.super_key(),
+                };
+            }
+            WindowEvent::CursorMoved { .. }
+            | WindowEvent::CursorLeft { .. }
+            | WindowEvent::MouseInput { .. }
+            | WindowEvent::MouseWheel { .. }
+            | WindowEvent::KeyboardInput { .. } => {
+                for event in translate(&event, &mut self.windows[index].cursor, self.modifiers) {
+                    self.gui.handle_root_event(root, event);
+                }
+                // the roots share their state, a change in one window can show in the others
+                self.request_redraw();
+            }
             // This is where all the rendering happens
             WindowEvent::RedrawRequested => {
                 // Empty the scene of objects to draw. You could create a new Scene each time, but in this case
                 // the same Scene is reused so that the underlying memory allocation can also be reused.
-                self.gui.renderer().scene.reset();
-                self.gui.render();
-
-                // Get the RenderSurface (surface + config)
-                let surface = &render_state.surface;
-
-                // Get the window size
-                let width = surface.config.width;
-                let height = surface.config.height;
-
-                // Get a handle to the device
-                let device_handle = &self.context.devices[surface.dev_id];
-
-                // Get the surface's texture
-                let surface_texture = surface
-                    .surface
-                    .get_current_texture()
-                    .expect("failed to get surface texture");
-
-                // Render to the surface's texture
-                self.renderers[surface.dev_id]
-                    .as_mut()
-                    .unwrap()
-                    .render_to_surface(
-                        &device_handle.device,
-                        &device_handle.queue,
-                        &self.gui.renderer().scene,
-                        &surface_texture,
-                        &vello::RenderParams {
-                            base_color: Color::BLACK, // Background color
-                            width,
-                            height,
-                            antialiasing_method: AaConfig::Msaa16,
-                        },
-                    )
-                    .expect("failed to render to surface");
-
-                // Queue the texture to be presented on the surface
-                surface_texture.present();
-
-                device_handle.device.poll(wgpu::Maintain::Poll);
+                self.gui.root_renderer(root).unwrap().scene.reset();
+                self.gui.tick();
+                self.gui.render_root(root);
+
+                // drawing to the surface and presenting it is the present phase of the frame
+                let (renderers, context) = (&mut self.renderers, &self.context);
+                self.gui.present(|gui| {
+                    // Get the RenderSurface (surface + config)
+                    let surface = &render_state.surface;
+
+                    // Get the window size
+                    let width = surface.config.width;
+                    let height = surface.config.height;
+
+                    // Get a handle to the device
+                    let device_handle = &context.devices[surface.dev_id];
+
+                    // Get the surface's texture
+                    let surface_texture = surface
+                        .surface
+                        .get_current_texture()
+                        .expect("failed to get surface texture");
+
+                    // Render to the surface's texture
+                    renderers[surface.dev_id]
+                        .as_mut()
+                        .unwrap()
+                        .render_to_surface(
+                            &device_handle.device,
+                            &device_handle.queue,
+                            &gui.root_renderer(root).unwrap().scene,
+                            &surface_texture,
+                            &vello::RenderParams {
+                                base_color: Color::BLACK, // Background color
+                                width,
+                                height,
+                                antialiasing_method: AaConfig::Msaa16,
+                            },
+                        )
+                        .expect("failed to render to surface");
+
+                    // Queue the texture to be presented on the surface
+                    surface_texture.present();
+
+                    device_handle.device.poll(wgpu::Maintain::Poll);
+                });
+
+                // animations draw a new frame as soon as this one is presented
+                if self.gui.is_animating() {
+                    render_state.window.request_redraw();
+                }
             }
             _ => {}
         }
     }
 }
 
-pub fn run_app(renderer: ModulaRenderer, x: impl Widget<ModulaRenderer>){
+/// how often the loop wakes up to check for selections of the inspector
+#[cfg(feature = "devtools")]
+const DEVTOOLS_POLL: std::time::Duration = std::time::Duration::from_millis(100);
+
+/// opens a window titled "Vello Shapes" that shows `x`
+pub fn run_app(renderer: ModulaRenderer, x: impl Widget<ModulaRenderer> + 'static){
+    run_windows(renderer, vec![AppWindow::new("Vello Shapes", x)]);
+}
+
+/// opens every window of `windows`, each with a root of the same gui
+///
+/// the first window is drawn with `renderer` and ends the app when it is closed,
+/// the others get a renderer with the same font, like tool palettes or a detached inspector
+pub fn run_windows(renderer: ModulaRenderer, windows: Vec<AppWindow>) {
+    let font = renderer.font.clone();
     let mut gui = Gui::new(renderer);
-    let context = gui.root_widget_context();
-    x.mount(context);
+    #[cfg(feature = "devtools")]
+    let devtools = std::env::var("VISORA_DEVTOOLS").ok().map(|address| {
+        let server = visora_devtools::DevtoolsServer::start(&address)
+            .unwrap_or_else(|error| panic!("could not serve the devtools at {address}: {error}"));
+        tracing::info!(address = %server.address(), "serving the devtools");
+        server
+    });
+    #[cfg(feature = "devtools")]
+    if devtools.is_some() {
+        gui = gui.with_devtools(visora_core::devtools::Devtools::new());
+    }
+    let mut states = Vec::new();
+    for (i, window) in windows.into_iter().enumerate() {
+        let root = match i {
+            0 => gui.tree().root(),
+            _ => gui.add_root(ModulaRenderer {
+                scene: Scene::new(),
+                font: font.clone(),
+                window: None,
+                highlighted: None,
+            }),
+        };
+        window.widget.mount(gui.root_context(root).unwrap());
+        states.push(WindowState {
+            root,
+            title: window.title,
+            size: window.size,
+            state: RenderState::Suspended(None),
+            cursor: Point::default(),
+        });
+    }
 
     let mut app = AppRunner {
         context: RenderContext::new(),
         renderers: vec![],
-        state: RenderState::Suspended(None),
-        gui
+        windows: states,
+        gui,
+        modifiers: Modifiers::default(),
+        #[cfg(feature = "devtools")]
+        devtools,
     };
     let event_loop = EventLoop::new().expect("failed to create event loop");
     event_loop.run_app(&mut app)
@@ -184,12 +345,75 @@ pub fn run_app(renderer: ModulaRenderer, x: impl Widget<ModulaRenderer>){
 
 
 
+/// translates the input of winit into the events of the gui, `cursor` keeps the last pointer position
+fn translate(event: &WindowEvent, cursor: &mut Point, modifiers: Modifiers) -> Vec<Event> {
+    match event {
+        WindowEvent::CursorMoved { position, .. } => {
+            *cursor = Point::new(position.x as f32, position.y as f32);
+            vec![Event::PointerMove { position: *cursor }]
+        }
+        // moving the pointer outside of every entity sends the leave events
+        WindowEvent::CursorLeft { .. } => vec![Event::PointerMove { position: Point::new(f32::MIN, f32::MIN) }],
+        WindowEvent::MouseInput { state, button, .. } => {
+            let button = match button {
+                MouseButton::Left => PointerButton::Primary,
+                MouseButton::Right => PointerButton::Secondary,
+                MouseButton::Middle => PointerButton::Middle,
+                MouseButton::Back => PointerButton::Other(3),
+                MouseButton::Forward => PointerButton::Other(4),
+                MouseButton::Other(x) => PointerButton::Other(*x),
+            };
+            match state {
+                ElementState::Pressed => vec![Event::PointerDown { position: *cursor, button }],
+                ElementState::Released => vec![Event::PointerUp { position: *cursor, button }],
+            }
+        }
+        WindowEvent::MouseWheel { delta, .. } => {
+            // winit moves the content by the delta, the gui scrolls towards the end for a positive delta like the browser
+            let delta = match delta {
+                // a line is scrolled as the height of a line of text
+                MouseScrollDelta::LineDelta(x, y) => Point::new(-x * 25.0, -y * 25.0),
+                MouseScrollDelta::PixelDelta(x) => Point::new(-x.x as f32, -x.y as f32),
+            };
+            vec![Event::Scroll { position: *cursor, delta }]
+        }
+        WindowEvent::KeyboardInput { event, .. } => {
+            let key = match &event.logical_key {
+                keyboard::Key::Named(NamedKey::Enter) => Key::Enter,
+                keyboard::Key::Named(NamedKey::Space) => Key::Space,
+                keyboard::Key::Named(NamedKey::Tab) => Key::Tab,
+                keyboard::Key::Named(NamedKey::Escape) => Key::Escape,
+                keyboard::Key::Named(NamedKey::Backspace) => Key::Backspace,
+                keyboard::Key::Named(NamedKey::Delete) => Key::Delete,
+                keyboard::Key::Named(NamedKey::ArrowUp) => Key::ArrowUp,
+                keyboard::Key::Named(NamedKey::ArrowDown) => Key::ArrowDown,
+                keyboard::Key::Named(NamedKey::ArrowLeft) => Key::ArrowLeft,
+                keyboard::Key::Named(NamedKey::ArrowRight) => Key::ArrowRight,
+                keyboard::Key::Named(NamedKey::Home) => Key::Home,
+                keyboard::Key::Named(NamedKey::End) => Key::End,
+                keyboard::Key::Character(x) => Key::Character(x.to_string()),
+                _ => Key::Unidentified,
+            };
+            let state = match event.state {
+                ElementState::Pressed => KeyState::Pressed,
+                ElementState::Released => KeyState::Released,
+            };
+            let mut events = vec![Event::Key { key, state, modifiers }];
+            if let (ElementState::Pressed, Some(text)) = (event.state, &event.text) {
+                events.push(Event::TextInput(text.to_string()));
+            }
+            events
+        }
+        _ => Vec::new(),
+    }
+}
+
 /// Helper function that creates a Winit window and returns it (wrapped in an Arc for sharing between threads)
-fn create_winit_window(event_loop: &ActiveEventLoop) -> Arc<Window> {
+fn create_winit_window(event_loop: &ActiveEventLoop, title: &str, size: LogicalSize<u32>) -> Arc<Window> {
     let attr = Window::default_attributes()
-        .with_inner_size(LogicalSize::new(1044, 800))
+        .with_inner_size(size)
         .with_resizable(true)
-        .with_title("Vello Shapes");
+        .with_title(title);
     Arc::new(event_loop.create_window(attr).unwrap())
 }
 